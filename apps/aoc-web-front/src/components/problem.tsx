@@ -1,7 +1,7 @@
 /* eslint-disable react-hooks/exhaustive-deps */
 import { FC, useEffect, useState } from 'react';
 import { environment } from '../environments/environment';
-import { Part, run } from '@aoc-web/lib-rs';
+import { Example, Part, examples, run, run_example } from '@aoc-web/lib-rs';
 import SyntaxHighlighter from 'react-syntax-highlighter';
 import { tomorrowNight as codeStyle } from 'react-syntax-highlighter/dist/esm/styles/hljs';
 import '../styles.scss';
@@ -147,6 +147,28 @@ export const ProblemPart: FC<{
     const [time, setTime] = useState<number>();
     const [solving, setSolving] = useState(false);
     const [result, setResult] = useState<string>();
+    const [error, setError] = useState<string>();
+    const [example, setExample] = useState<{
+        answer?: string;
+        expected?: string;
+        error?: string;
+    }>();
+
+    const runFirstExample = () => {
+        try {
+            const list = examples(year, day.value) as Example[];
+            const expectedOf = (example: Example) =>
+                (part === Part.First ? example.first : example.second) ?? undefined;
+            const index = Math.max(
+                list.findIndex((example) => expectedOf(example) !== undefined),
+                0
+            );
+            const answer = run_example(year, day.value, index, part);
+            setExample({ answer, expected: list[index] && expectedOf(list[index]) });
+        } catch (e) {
+            setExample({ error: e instanceof Error ? e.message : String(e) });
+        }
+    };
 
     const runPromise = function(
         year: number,
@@ -174,13 +196,19 @@ export const ProblemPart: FC<{
                     const end = performance.now();
                     setTime(end - start);
                 })
-                .catch(console.error);
+                .catch((e) => {
+                    console.error(e);
+                    setError(e instanceof Error ? e.message : String(e));
+                    setSolving(false);
+                });
         }
     }, [solving]);
 
     useEffect(() => {
         setResult(undefined);
+        setError(undefined);
         setTime(undefined);
+        setExample(undefined);
     }, [year, day]);
 
     const isLongBruteforce = (part: Part) => {
@@ -202,12 +230,21 @@ export const ProblemPart: FC<{
                     onClick={() => {
                         if (problem) {
                             setResult(undefined);
+                            setError(undefined);
                             setSolving(true);
                         }
                     }}
                 >
                     {solving ? '[Solving...]' : '[Solve]'}
                 </button>
+                <button
+                    data-test={`example-${part === Part.First ? 'first' : 'second'}`}
+                    className='accent-button'
+                    disabled={solving}
+                    onClick={runFirstExample}
+                >
+                    [Example]
+                </button>
                 {
                     isLongBruteforce(part) &&
                     <span className='bruteforce-warning' title={"Hacky bruteforce solution, may crash your browser"}>
@@ -227,6 +264,32 @@ export const ProblemPart: FC<{
                     result={result}
                 />
             )}
+            {!!example && (
+                <div
+                    data-test={`example-result-${part === Part.First ? 'first' : 'second'}`}
+                    className={
+                        example.error ||
+                        (example.expected !== undefined &&
+                            example.expected !== example.answer)
+                            ? 'solution-error'
+                            : 'example-result'
+                    }
+                >
+                    {example.error ??
+                        `Example: ${example.answer}` +
+                            (example.expected !== undefined
+                                ? `, expected ${example.expected}`
+                                : '')}
+                </div>
+            )}
+            {!!error && !solving && (
+                <div
+                    data-test={`error-${part === Part.First ? 'first' : 'second'}`}
+                    className='solution-error'
+                >
+                    {error}
+                </div>
+            )}
         </div>
     );
 };
@@ -0,0 +1,190 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use crate::utils::get_answers_path;
+use crate::Part;
+
+/// Confirmed answers of a single year, stored as `data/<year>/answers.json`
+/// and keyed by day, then by input profile.
+///
+/// ```json
+/// { "1": { "default": { "first": "1602", "second": "1633" } } }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers {
+    days: BTreeMap<usize, BTreeMap<String, ProfileAnswers>>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProfileAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub second: Option<String>,
+}
+
+impl Answers {
+    pub fn from_json(json: &str) -> Result<Answers, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Loads the answers of `year`, a missing file means nothing is confirmed yet.
+    pub fn load(year: usize) -> io::Result<Answers> {
+        let json = match fs::read_to_string(get_answers_path(year)) {
+            Ok(json) => json,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(err) => return Err(err),
+        };
+        Answers::from_json(&json).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default() + "\n"
+    }
+
+    pub fn save(&self, year: usize) -> io::Result<()> {
+        fs::write(get_answers_path(year), self.to_json())
+    }
+
+    pub fn expected(&self, day: usize, profile: &str, part: Part) -> Option<&str> {
+        let answers = self.days.get(&day)?.get(profile)?;
+        match part {
+            Part::First => answers.first.as_deref(),
+            Part::Second => answers.second.as_deref(),
+        }
+    }
+
+    /// Profiles with at least one recorded answer for `day`.
+    pub fn profiles(&self, day: usize) -> Vec<&str> {
+        self.days.get(&day).map_or(vec![], |profiles| {
+            profiles.keys().map(String::as_str).collect()
+        })
+    }
+
+    pub fn record(&mut self, day: usize, profile: &str, part: Part, answer: &str) {
+        let answers = self
+            .days
+            .entry(day)
+            .or_default()
+            .entry(profile.to_string())
+            .or_default();
+        let slot = match part {
+            Part::First => &mut answers.first,
+            Part::Second => &mut answers.second,
+        };
+        *slot = Some(answer.to_string());
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    use super::Answers;
+    use crate::utils::read_to_string;
+    use crate::Part;
+
+    pub struct Case {
+        pub profile: String,
+        pub input: String,
+        pub expected: String,
+    }
+
+    /// Every input profile of a day that has a recorded answer for `part`.
+    ///
+    /// Panics when there is none or when a profile has no input, so a test cannot pass
+    /// without checking anything.
+    pub fn real_cases(year: u32, day: u8, part: Part) -> Vec<Case> {
+        let answers = Answers::load(year as usize)
+            .unwrap_or_else(|err| panic!("Cannot load answers for {year}: {err}"));
+        let cases: Vec<Case> = answers
+            .profiles(day as usize)
+            .into_iter()
+            .filter_map(|profile| {
+                let expected = answers.expected(day as usize, profile, part)?.to_string();
+                Some(Case {
+                    input: read_to_string(year, day, profile),
+                    profile: profile.to_string(),
+                    expected,
+                })
+            })
+            .collect();
+        assert!(
+            !cases.is_empty(),
+            "No answer recorded for {year} day {day} {part:?}"
+        );
+        cases
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        // given
+        let json = r###"{
+            "1": { "default": { "first": "7" }, "alice": { "first": "8", "second": "9" } },
+            "10": { "default": { "first": "13140", "second": "##..\n#..#" } }
+        }"###;
+
+        // when
+        let answers = Answers::from_json(json).unwrap();
+
+        // then
+        assert_eq!(answers.expected(1, "default", Part::First), Some("7"));
+        assert_eq!(answers.expected(1, "default", Part::Second), None);
+        assert_eq!(answers.expected(1, "alice", Part::Second), Some("9"));
+        assert_eq!(answers.expected(1, "bob", Part::First), None);
+        assert_eq!(
+            answers.expected(10, "default", Part::Second),
+            Some("##..\n#..#")
+        );
+        assert_eq!(answers.expected(2, "default", Part::First), None);
+        assert_eq!(answers.profiles(1), ["alice", "default"]);
+        assert!(answers.profiles(2).is_empty());
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        // given
+        let mut answers = Answers::default();
+
+        // when
+        answers.record(10, "default", Part::Second, "EHPZPJGL");
+        answers.record(2, "default", Part::First, "13682");
+        answers.record(2, "default", Part::First, "12881");
+        answers.record(2, "alice", Part::Second, "1");
+
+        // then
+        assert_eq!(
+            answers.to_json(),
+            r#"{
+  "2": {
+    "alice": {
+      "second": "1"
+    },
+    "default": {
+      "first": "12881"
+    }
+  },
+  "10": {
+    "default": {
+      "second": "EHPZPJGL"
+    }
+  }
+}
+"#
+        );
+        assert_eq!(Answers::from_json(&answers.to_json()).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_every_year_loads() {
+        for year in crate::registry::years() {
+            assert!(Answers::load(year).is_ok(), "answers of {year}");
+        }
+    }
+}
@@ -1,27 +1,48 @@
-pub fn run_first(input: &str) -> String {
-    let as_num: Vec<usize> = input
-        .split('\n')
-        .filter(|l| !l.is_empty())
-        .map(|i| i.parse::<usize>().unwrap())
-        .collect();
-    let count_sliding = count_sliding_increased(&as_num);
-    count_sliding.to_string()
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day01;
+
+const EXAMPLE: &str = "199
+200
+208
+210
+200
+207
+240
+269
+260
+263";
+
+impl Solution for Day01 {
+    type Input = Vec<usize>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("7").second("5")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|l| !l.is_empty())
+            .map(|l| parse_value(input, l))
+            .collect()
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(count_increased(input).to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(count_sliding_increased(input).to_string())
+    }
 }
 
 pub fn count_increased(input: &[usize]) -> usize {
     input.windows(2).map(|w| usize::from(w[1] > w[0])).sum()
 }
 
-pub fn run_second(input: &str) -> String {
-    let as_num: Vec<usize> = input
-        .split('\n')
-        .filter(|l| !l.is_empty())
-        .map(|i| i.parse::<usize>().unwrap())
-        .collect();
-    let count = count_increased(&as_num);
-    count.to_string()
-}
-
 pub fn count_sliding_increased(input: &[usize]) -> usize {
     input
         .windows(4)
@@ -1,31 +1,54 @@
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
 #[derive(PartialEq, Eq, Debug)]
-struct SubmarineCommand {
+pub struct SubmarineCommand {
     command_type: SubmarineCommandType,
     value: i64,
 }
 
 #[derive(PartialEq, Eq, Debug)]
-enum SubmarineCommandType {
+pub enum SubmarineCommandType {
     Forward = 0,
     Up = 1,
     Down = 2,
 }
 
-pub fn run_first(input: &str) -> String {
-    let commands = get_commands(input);
-    total_area(&commands).to_string()
-}
+pub struct Day02;
+
+const EXAMPLE: &str = "forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+impl Solution for Day02 {
+    type Input = Vec<SubmarineCommand>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("150").second("900")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        get_commands(input)
+    }
 
-pub fn run_second(input: &str) -> String {
-    let commands = get_commands(input);
-    total_aim_area(&commands).to_string()
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(total_area(input).to_string())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(total_aim_area(input).to_string())
+    }
 }
 
-fn get_commands(input: &str) -> Vec<SubmarineCommand> {
+fn get_commands(input: &str) -> Result<Vec<SubmarineCommand>, SolveError> {
     input
         .split('\n')
         .filter(|line| !line.is_empty())
-        .map(parse_command)
+        .map(|line| parse_command(input, line))
         .collect()
 }
 
@@ -78,32 +101,26 @@ fn total_aim_area(commands: &[SubmarineCommand]) -> i64 {
     total_x * total_y
 }
 
-fn parse_command(cmd: &str) -> SubmarineCommand {
-    let tokens: Vec<&str> = cmd.split(' ').collect();
-    let command_str = tokens[0];
-    let value_str = tokens[1];
-    let value = value_str.parse::<i64>().unwrap();
+fn parse_command(input: &str, cmd: &str) -> Result<SubmarineCommand, SolveError> {
+    let (command_str, value_str) = cmd.split_once(' ').ok_or(SolveError::at(input, cmd))?;
+    let value = parse_value(input, value_str)?;
 
     let command_type = match command_str {
         "forward" => SubmarineCommandType::Forward,
         "up" => SubmarineCommandType::Up,
         "down" => SubmarineCommandType::Down,
-        _ => panic!("Could not match command {cmd}"),
+        _ => return Err(SolveError::at(input, command_str)),
     };
 
-    SubmarineCommand {
+    Ok(SubmarineCommand {
         command_type,
         value,
-    }
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::read_to_string;
-
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 2;
 
     fn mock_commands() -> Vec<SubmarineCommand> {
         vec![
@@ -159,30 +176,4 @@ mod tests {
         let expected = 900;
         assert_eq!(result, expected);
     }
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "1804520";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "1971095320";
-        assert_eq!(result, expected.to_string());
-    }
 }
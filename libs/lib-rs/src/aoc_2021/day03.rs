@@ -2,24 +2,61 @@ use std::cmp::Ordering;
 
 use itertools::Itertools;
 
-pub fn run_first(input: &str) -> String {
-    let lines: Vec<_> = input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .map(|line| line.to_string())
-        .collect();
-
-    power_consumption(&lines).to_string()
-}
+use crate::common::parse::find_invalid;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day03;
+
+const EXAMPLE: &str = "00100
+11110
+10110
+10111
+10101
+01111
+00111
+11100
+10000
+11001
+00010
+01010";
+
+impl Solution for Day03 {
+    type Input = Vec<String>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("198").second("230")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        let lines = input.split('\n').filter(|line| !line.is_empty());
+        let width = lines.clone().next().map_or(0, |line| line.len());
+
+        lines
+            .map(|line| {
+                match find_invalid(line, |c| c == '0' || c == '1') {
+                    Some(invalid) => return Err(SolveError::at(input, invalid)),
+                    None if line.len() != width => return Err(SolveError::at(input, line)),
+                    None => {}
+                }
+                Ok(line.to_string())
+            })
+            .collect()
+    }
 
-pub fn run_second(input: &str) -> String {
-    let lines: Vec<_> = input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .map(|line| line.to_string())
-        .collect();
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        if input.is_empty() {
+            return Err(SolveError::NoSolution);
+        }
+        Ok(power_consumption(input).to_string())
+    }
 
-    life_support(&lines).to_string()
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        if input.is_empty() {
+            return Err(SolveError::NoSolution);
+        }
+        Ok(life_support(input).to_string())
+    }
 }
 
 pub fn power_consumption(input: &[String]) -> usize {
@@ -43,14 +80,13 @@ pub fn get_rating(
     input: &[String],
     filter: fn(input: &[String], position: usize) -> Vec<String>,
 ) -> usize {
-    let row_length = input.iter().next().unwrap().len();
+    let row_length = input.first().map_or(0, |row| row.len());
     let mut curr_input: Vec<String> = input.to_vec();
 
     for position in 0..row_length {
         curr_input = filter(&curr_input, position);
-        if curr_input.len() == 1 {
-            let result_vec = curr_input.get(0).unwrap();
-            return usize::from_str_radix(result_vec, 2).unwrap();
+        if let [result] = curr_input.as_slice() {
+            return usize::from_str_radix(result, 2).unwrap_or(0);
         }
     }
 
@@ -75,40 +111,42 @@ pub fn filter_by_rating(
 
     input
         .iter()
-        .filter(|i| i.chars().nth(position).unwrap() == ch_to_keep)
+        .filter(|i| i.as_bytes()[position] == ch_to_keep as u8)
         .cloned()
         .collect()
 }
 
 pub fn filter_by_oxygen_rating(input: &[String], position: usize) -> Vec<String> {
-    filter_by_rating(input, position, |zeros_count, ones_count| match zeros_count
-        .partial_cmp(&ones_count)
-        .unwrap()
-    {
-        Ordering::Greater => '0',
-        Ordering::Less => '1',
-        Ordering::Equal => '1',
-    })
+    filter_by_rating(
+        input,
+        position,
+        |zeros_count, ones_count| match zeros_count.cmp(&ones_count) {
+            Ordering::Greater => '0',
+            Ordering::Less => '1',
+            Ordering::Equal => '1',
+        },
+    )
 }
 
 pub fn filter_by_co2_rating(input: &[String], position: usize) -> Vec<String> {
-    filter_by_rating(input, position, |zeros_count, ones_count| match zeros_count
-        .partial_cmp(&ones_count)
-        .unwrap()
-    {
-        Ordering::Greater => '1',
-        Ordering::Less => '0',
-        Ordering::Equal => '0',
-    })
+    filter_by_rating(
+        input,
+        position,
+        |zeros_count, ones_count| match zeros_count.cmp(&ones_count) {
+            Ordering::Greater => '1',
+            Ordering::Less => '0',
+            Ordering::Equal => '0',
+        },
+    )
 }
 
 pub fn binary_vec_to_num(vec: &[usize]) -> usize {
     let as_str = vec.iter().map(|n| n.to_string()).join("");
-    usize::from_str_radix(&as_str, 2).unwrap()
+    usize::from_str_radix(&as_str, 2).unwrap_or(0)
 }
 
 pub fn get_gamma_vec(input: &[String]) -> Vec<usize> {
-    let row_length = input.iter().next().unwrap().len();
+    let row_length = input.first().map_or(0, |row| row.len());
     (0..row_length)
         .map(|pos| most_common_at_position(input, pos))
         .collect()
@@ -123,15 +161,7 @@ pub fn count_at_position(input: &[String], position: usize) -> (usize, usize) {
 
     let ones_count = input
         .iter()
-        .map(|s| {
-            let char = s.chars().nth(position).unwrap();
-
-            match char {
-                '1' => 1,
-                '0' => 0,
-                _ => panic!("Invalid char"),
-            }
-        })
+        .map(|s| usize::from(s.as_bytes()[position] == b'1'))
         .sum();
 
     let zeros_count = total - ones_count;
@@ -146,39 +176,9 @@ pub fn most_common_at_position(input: &[String], position: usize) -> usize {
 #[cfg(test)]
 mod tests {
     use crate::common::parse::test_utils::vec_of_strings;
-    use crate::utils::read_to_string;
 
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 3;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "3959450";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "7440311";
-        assert_eq!(result, expected.to_string());
-    }
-
     #[test]
     fn test_most_common_at_position() {
         // given
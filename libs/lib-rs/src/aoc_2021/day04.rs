@@ -1,38 +1,74 @@
 use std::collections::HashSet;
 
-use crate::common::parse::parse_numbers;
+use crate::common::parse::{parse_numbers, parse_value};
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-pub fn run_first(input: &str) -> String {
-    let (bingo_line, matrices) = parse(input);
-    first_bingo_winner(&bingo_line, matrices)
-}
+pub struct Day04;
+
+const EXAMPLE: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+impl Solution for Day04 {
+    type Input = (Vec<i64>, Vec<BingoBoard>);
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("4512").second("1924")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse(input)
+    }
 
-pub fn run_second(input: &str) -> String {
-    let (bingo_line, matrices) = parse(input);
-    last_bingo_winner(&bingo_line, matrices)
+    fn part_one((bingo_line, matrices): &Self::Input) -> Result<String, SolveError> {
+        first_bingo_winner(bingo_line, matrices.clone())
+    }
+
+    fn part_two((bingo_line, matrices): &Self::Input) -> Result<String, SolveError> {
+        last_bingo_winner(bingo_line, matrices.clone())
+    }
 }
 
-fn first_bingo_winner(bingo_line: &[i64], mut matrices: Vec<BingoBoard>) -> String {
-    let mut result = "".to_string();
+fn first_bingo_winner(
+    bingo_line: &[i64],
+    mut matrices: Vec<BingoBoard>,
+) -> Result<String, SolveError> {
     for num in bingo_line {
         for m in matrices.iter_mut() {
             m.mark(*num);
             if m.has_bingo() {
-                result = (m.sum_unmarked() * num).to_string();
-                return result;
+                return Ok((m.sum_unmarked() * num).to_string());
             }
         }
     }
-    result
+    Err(SolveError::NoSolution)
 }
 
-fn last_bingo_winner(bingo_line: &[i64], mut matrices: Vec<BingoBoard>) -> String {
+fn last_bingo_winner(
+    bingo_line: &[i64],
+    mut matrices: Vec<BingoBoard>,
+) -> Result<String, SolveError> {
     let mut num_winners = 0;
     let num_players = matrices.len();
     let mut solved: HashSet<i64> = HashSet::new();
 
-    let mut result = "".to_string();
-
     for num in bingo_line {
         for (idx, player_matrix) in matrices.iter_mut().enumerate() {
             player_matrix.mark(*num);
@@ -42,28 +78,27 @@ fn last_bingo_winner(bingo_line: &[i64], mut matrices: Vec<BingoBoard>) -> Strin
                 solved.insert(idx as i64);
 
                 if num_winners == num_players {
-                    result = (player_matrix.sum_unmarked() * num).to_string();
-                    return result;
+                    return Ok((player_matrix.sum_unmarked() * num).to_string());
                 }
             }
         }
     }
 
-    result
+    Err(SolveError::NoSolution)
 }
 
-fn parse(input: &str) -> (Vec<i64>, Vec<BingoBoard>) {
-    let bingo_line: Vec<i64> = input
-        .split("\n\n")
+fn parse(input: &str) -> Result<(Vec<i64>, Vec<BingoBoard>), SolveError> {
+    let mut sections = input.split("\n\n");
+    let bingo_line = sections
         .next()
-        .unwrap()
+        .unwrap_or_default()
         .split(',')
-        .map(|n| n.parse::<i64>().unwrap())
-        .collect();
+        .map(|n| parse_value(input, n.trim()))
+        .collect::<Result<Vec<i64>, _>>()?;
 
-    let matrices: Vec<BingoBoard> = input.split("\n\n").skip(1).map(parse_matrix).collect();
+    let matrices: Vec<BingoBoard> = sections.map(parse_matrix).collect();
 
-    (bingo_line, matrices)
+    Ok((bingo_line, matrices))
 }
 
 fn parse_matrix(input: &str) -> BingoBoard {
@@ -72,7 +107,7 @@ fn parse_matrix(input: &str) -> BingoBoard {
 }
 
 #[derive(Debug, Clone)]
-struct BingoBoard {
+pub struct BingoBoard {
     width: i64,
     height: i64,
     values: Vec<BingoNum>,
@@ -119,14 +154,12 @@ impl BingoBoard {
     fn mark(&mut self, number: i64) {
         match self.values.iter().position(|n| n.value == number) {
             None => {}
-            Some(index) => self.values.get_mut(index).unwrap().marked = true,
+            Some(index) => self.values[index].marked = true,
         };
     }
 
     fn any_column_has_bingo(&self) -> bool {
-        (0..self.width)
-            .map(|column| self.column_has_bingo(column))
-            .any(|bingo| bingo)
+        (0..self.width).any(|column| self.column_has_bingo(column))
     }
 
     fn column_has_bingo(&self, column: i64) -> bool {
@@ -136,9 +169,7 @@ impl BingoBoard {
     }
 
     fn any_row_has_bingo(&self) -> bool {
-        (0..self.height)
-            .map(|column| self.row_has_bingo(column))
-            .any(|bingo| bingo)
+        (0..self.height).any(|row| self.row_has_bingo(row))
     }
 
     fn row_has_bingo(&self, row: i64) -> bool {
@@ -161,39 +192,8 @@ impl BingoBoard {
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 4;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "58412";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "10030";
-        assert_eq!(result, expected.to_string());
-    }
-
     #[test]
     fn test_mark_marks_value_as_bingoed() {
         // given
@@ -1,23 +1,43 @@
+use crate::common::grid::Grid;
 use crate::common::parse::parse_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-pub fn run_first(input: &str) -> String {
-    let lines: Vec<String> = input
-        .split('\n')
-        .filter(|l| !l.is_empty())
-        .map(|l| l.to_string())
-        .collect();
+pub struct Day05;
 
-    count_overlapping(&lines).to_string()
-}
+const EXAMPLE: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
 
-pub fn run_second(input: &str) -> String {
-    let lines: Vec<String> = input
-        .split('\n')
-        .filter(|l| !l.is_empty())
-        .map(|l| l.to_string())
-        .collect();
+impl Solution for Day05 {
+    type Input = Vec<Line>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("5").second("12")];
 
-    count_overlapping_with_diagonal(&lines).to_string()
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_line(input, line))
+            .collect()
+    }
+
+    fn part_one(lines: &Self::Input) -> Result<String, SolveError> {
+        Ok(count_overlapping(lines).to_string())
+    }
+
+    fn part_two(lines: &Self::Input) -> Result<String, SolveError> {
+        Ok(count_overlapping_with_diagonal(lines).to_string())
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -58,80 +78,62 @@ impl Line {
     }
 }
 
-#[derive(Debug, Clone)]
-struct Grid {
-    width: i64,
-    values: Vec<i64>,
-}
-
-impl Grid {
-    pub fn new(width: i64, height: i64) -> Grid {
-        let values = vec![0; (width * height) as usize];
+fn draw_line(grid: &mut Grid<u32>, line: &Line) {
+    let x_delta = (line.delta_x()).signum();
+    let y_delta = (line.delta_y()).signum();
+    let len = line.length();
 
-        Grid { width, values }
-    }
-
-    fn get_index(&self, x: i64, y: i64) -> i64 {
-        y * self.width + x
-    }
-
-    fn inc_value(&mut self, row: i64, column: i64) {
-        let index = self.get_index(row, column);
-        self.values[index as usize] += 1
-    }
-
-    fn draw_lines(&mut self, lines: &[Line]) {
-        for line in lines {
-            self.draw_line(line)
-        }
-    }
-
-    fn draw_line(&mut self, line: &Line) {
-        let x_delta = (line.delta_x()).signum();
-        let y_delta = (line.delta_y()).signum();
-        let len = line.length();
-
-        for n in 0..=len {
-            let row = line.start_y + n * y_delta;
-            let column = line.start_x + n * x_delta;
-            self.inc_value(row, column);
-        }
+    for n in 0..=len {
+        let row = line.start_y + n * y_delta;
+        let column = line.start_x + n * x_delta;
+        grid[(row as usize, column as usize)] += 1;
     }
+}
 
-    fn count_overlapping(&self) -> i64 {
-        self.values.iter().filter(|&&v| v > 1).count() as i64
-    }
+fn count_overlapping_cells(grid: &Grid<u32>) -> i64 {
+    grid.values().filter(|&&v| v > 1).count() as i64
 }
 
-fn parse_line(input: &str) -> Line {
-    let values = parse_numbers(input);
-    Line {
-        start_x: values[0],
-        start_y: values[1],
-        end_x: values[2],
-        end_y: values[3],
+/// Lines have to be horizontal, vertical or at 45 degrees, `draw_line` walks no other angle.
+fn parse_line(input: &str, line: &str) -> Result<Line, SolveError> {
+    let parsed = match parse_numbers(line)[..] {
+        [start_x, start_y, end_x, end_y] => Line {
+            start_x,
+            start_y,
+            end_x,
+            end_y,
+        },
+        _ => return Err(SolveError::at(input, line)),
+    };
+    match parsed.is_horizontal()
+        || parsed.is_vertical()
+        || parsed.delta_x().abs() == parsed.delta_y().abs()
+    {
+        true => Ok(parsed),
+        false => Err(SolveError::at(input, line)),
     }
 }
 
-pub fn count_overlapping(input: &[String]) -> i64 {
-    let lines: Vec<_> = input
+pub fn count_overlapping(lines: &[Line]) -> i64 {
+    let lines: Vec<_> = lines
         .iter()
-        .map(|l| parse_line(l))
         .filter(|l| l.is_vertical() || l.is_horizontal())
+        .cloned()
         .collect();
     count_overlapping_points(&lines)
 }
 
 pub fn count_overlapping_points(lines: &[Line]) -> i64 {
     let (width, height) = max_coords(lines);
-    let mut grid = Grid::new(width + 1, height + 1);
-    grid.draw_lines(lines);
-    grid.count_overlapping()
+    let mut grid = Grid::new(width as usize + 1, height as usize + 1, 0);
+    for line in lines {
+        draw_line(&mut grid, line);
+    }
+    count_overlapping_cells(&grid)
 }
 
-pub fn count_overlapping_with_diagonal(input: &[String]) -> i64 {
-    let lines: Vec<_> = input.iter().map(|l| parse_line(l)).collect();
-    count_overlapping_points(&lines)
+pub fn count_overlapping_with_diagonal(lines: &[Line]) -> i64 {
+    count_overlapping_points(lines)
 }
 
 pub fn max_coords(lines: &[Line]) -> (i64, i64) {
@@ -148,74 +150,43 @@ pub fn max_coords(lines: &[Line]) -> (i64, i64) {
 
 #[cfg(test)]
 mod tests {
-    use crate::common::parse::test_utils::vec_of_strings;
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 5;
-
     #[test]
-    fn test_part_1() {
+    fn test_draw_line() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let line = Line {
+            start_x: 1,
+            start_y: 1,
+            end_x: 3,
+            end_y: 1,
+        };
+        let mut grid = Grid::new(4, 4, 0);
 
         // when
-        let result = run_first(&data);
+        draw_line(&mut grid, &line);
+        assert_eq!(count_overlapping_cells(&grid), 0);
 
-        // then
-        let expected = "6572";
-        assert_eq!(result, expected.to_string());
+        draw_line(&mut grid, &line);
+        assert_eq!(count_overlapping_cells(&grid), 3);
     }
 
     #[test]
-    fn test_part_2() {
+    fn test_parse_skewed_line() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let input = "0,9 -> 5,9\n0,0 -> 3,1\n";
 
         // when
-        let result = run_second(&data);
+        let result = Day05::parse(input);
 
         // then
-        let expected = "21466";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_draw_line() {
-        // given
-        let line = Line {
-            start_x: 1,
-            start_y: 1,
-            end_x: 3,
-            end_y: 1,
-        };
-        let mut grid = Grid::new(4, 4);
-
-        // when
-        grid.draw_line(&line);
-        assert_eq!(grid.count_overlapping(), 0);
-
-        grid.draw_line(&line);
-        assert_eq!(grid.count_overlapping(), 3);
+        assert_eq!(result.unwrap_err(), SolveError::parse(2, 1, "0,0 -> 3,1"));
     }
 
     #[test]
     fn test_count_overlapping() {
         // given
-        let lines = vec_of_strings![
-            "0,9 -> 5,9",
-            "8,0 -> 0,8",
-            "9,4 -> 3,4",
-            "2,2 -> 2,1",
-            "7,0 -> 7,4",
-            "6,4 -> 2,0",
-            "0,9 -> 2,9",
-            "3,4 -> 1,4",
-            "0,0 -> 8,8",
-            "5,5 -> 8,2"
-        ];
+        let lines = Day05::parse(EXAMPLE).unwrap();
 
         // when
         let result = count_overlapping(&lines);
@@ -227,18 +198,7 @@ mod tests {
     #[test]
     fn test_count_overlapping_with_diagonal() {
         // given
-        let lines = vec_of_strings![
-            "0,9 -> 5,9",
-            "8,0 -> 0,8",
-            "9,4 -> 3,4",
-            "2,2 -> 2,1",
-            "7,0 -> 7,4",
-            "6,4 -> 2,0",
-            "0,9 -> 2,9",
-            "3,4 -> 1,4",
-            "0,0 -> 8,8",
-            "5,5 -> 8,2"
-        ];
+        let lines = Day05::parse(EXAMPLE).unwrap();
 
         // when
         let result = count_overlapping_with_diagonal(&lines);
@@ -1,93 +1,109 @@
-use crate::common::parse::parse_numbers;
-
-pub fn run_first(input: &str) -> String {
-    let days = 80;
-    population_size(input, days).to_string()
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::params;
+use crate::solution::Solution;
+
+pub struct Day06;
+
+const EXAMPLE: &str = "3,4,3,1,2";
+
+params! {
+    pub struct Day06Params {
+        /// Days simulated in part one
+        first_days: i64 = 80,
+        /// Days simulated in part two
+        second_days: i64 = 256,
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    let days = 256;
-    population_size(input, days).to_string()
+impl Solution for Day06 {
+    type Input = Vec<i64>;
+    type Params = Day06Params;
+
+    const EXAMPLES: &'static [Example] =
+        &[Example::new(EXAMPLE).first("5934").second("26984457539")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .trim()
+            .split(',')
+            .map(|timer| match parse_value(input, timer.trim())? {
+                fish @ 0..=8 => Ok(fish),
+                _ => Err(SolveError::at(input, timer)),
+            })
+            .collect()
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Self::part_one_with(input, &Day06Params::default())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Self::part_two_with(input, &Day06Params::default())
+    }
+
+    fn part_one_with(input: &Self::Input, params: &Day06Params) -> Result<String, SolveError> {
+        Ok(population_size(input, params.first_days)?.to_string())
+    }
+
+    fn part_two_with(input: &Self::Input, params: &Day06Params) -> Result<String, SolveError> {
+        Ok(population_size(input, params.second_days)?.to_string())
+    }
 }
 
-fn population_size(input: &str, num_days: i64) -> i64 {
-    let fish = parse_numbers(input);
+fn population_size(fish: &[i64], num_days: i64) -> Result<i64, SolveError> {
     let mut groups: Vec<i64> = vec![0; 9];
 
-    for fish in fish {
+    for &fish in fish {
         groups[fish as usize] += 1;
     }
 
     for _ in 0..num_days {
-        groups[7] += groups[0];
+        groups[7] = groups[7]
+            .checked_add(groups[0])
+            .ok_or(SolveError::Overflow)?;
         groups.rotate_left(1);
     }
 
-    groups.iter().sum()
+    groups
+        .iter()
+        .try_fold(0_i64, |sum, &n| sum.checked_add(n))
+        .ok_or(SolveError::Overflow)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::read_to_string;
-
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 6;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "372300";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "1675781200288";
-        assert_eq!(result, expected.to_string());
-    }
 
     #[test]
     fn test_population_size_example_1() {
         // given
-        let input = "3,4,3,1,2";
+        let input = Day06::parse("3,4,3,1,2").unwrap();
         let num_days = 18;
 
         // when
-        let result = population_size(input, num_days);
+        let result = population_size(&input, num_days);
 
         // then
-        assert_eq!(result, 26)
+        assert_eq!(result, Ok(26))
     }
 
     #[test]
     fn test_population_size_v2_example_1() {
         // given
-        let input = "3,4,3,1,2";
+        let input = Day06::parse("3,4,3,1,2").unwrap();
 
         // then
-        assert_eq!(population_size(input, 1), 5);
-        assert_eq!(population_size(input, 2), 6);
-        assert_eq!(population_size(input, 3), 7);
-        assert_eq!(population_size(input, 4), 9);
-        assert_eq!(population_size(input, 5), 10);
-        assert_eq!(population_size(input, 6), 10);
-        assert_eq!(population_size(input, 7), 10);
-        assert_eq!(population_size(input, 8), 10);
-        assert_eq!(population_size(input, 9), 11);
-        assert_eq!(population_size(input, 18), 26);
+        assert_eq!(population_size(&input, 1), Ok(5));
+        assert_eq!(population_size(&input, 2), Ok(6));
+        assert_eq!(population_size(&input, 3), Ok(7));
+        assert_eq!(population_size(&input, 4), Ok(9));
+        assert_eq!(population_size(&input, 5), Ok(10));
+        assert_eq!(population_size(&input, 6), Ok(10));
+        assert_eq!(population_size(&input, 7), Ok(10));
+        assert_eq!(population_size(&input, 8), Ok(10));
+        assert_eq!(population_size(&input, 9), Ok(11));
+        assert_eq!(population_size(&input, 18), Ok(26));
     }
 }
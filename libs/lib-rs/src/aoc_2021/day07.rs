@@ -1,20 +1,41 @@
-use crate::common::parse::parse_numbers;
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-pub fn run_first(input: &str) -> String {
-    part_1(input).to_string()
-}
+pub struct Day07;
+
+const EXAMPLE: &str = "16,1,2,0,4,2,7,1,2,14";
+
+impl Solution for Day07 {
+    type Input = Vec<i64>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("37").second("168")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .trim()
+            .split(',')
+            .map(|crab| parse_value(input, crab.trim()))
+            .collect()
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(part_1(input).to_string())
+    }
 
-pub fn run_second(input: &str) -> String {
-    part_2(input).to_string()
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(part_2(input).to_string())
+    }
 }
 
-pub fn part_1(input: &str) -> i64 {
-    let crabs = parse_numbers(input);
-    let max_pos = crabs.iter().max().unwrap();
+pub fn part_1(crabs: &[i64]) -> i64 {
+    let max_pos = crabs.iter().max().unwrap_or(&0);
     let mut min_fuel = i64::MAX;
 
     for pos in 0..=*max_pos {
-        let fuel = total_fuel(pos, &crabs);
+        let fuel = total_fuel(pos, crabs);
         if fuel < min_fuel {
             min_fuel = fuel
         }
@@ -23,14 +44,13 @@ pub fn part_1(input: &str) -> i64 {
     min_fuel
 }
 
-pub fn part_2(input: &str) -> i64 {
-    let crabs = parse_numbers(input);
-    let max_pos = crabs.iter().max().unwrap();
+pub fn part_2(crabs: &[i64]) -> i64 {
+    let max_pos = crabs.iter().max().unwrap_or(&0);
 
     let mut min_fuel = i64::MAX;
 
     for pos in 0..=*max_pos {
-        let fuel = total_fuel_2(pos, &crabs);
+        let fuel = total_fuel_2(pos, crabs);
         if fuel < min_fuel {
             min_fuel = fuel
         }
@@ -53,46 +73,15 @@ fn gaussian(n: i64) -> i64 {
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 7;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "342641";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "93006301";
-        assert_eq!(result, expected.to_string());
-    }
-
     #[test]
     fn test_part_1_example() {
         // given
-        let input = "16,1,2,0,4,2,7,1,2,14";
+        let input = Day07::parse("16,1,2,0,4,2,7,1,2,14").unwrap();
 
         // when
-        let result = part_1(input);
+        let result = part_1(&input);
 
         // then
         let expected = 37_i64;
@@ -102,10 +91,10 @@ mod tests {
     #[test]
     fn test_part_2_example() {
         // given
-        let input = "16,1,2,0,4,2,7,1,2,14";
+        let input = Day07::parse("16,1,2,0,4,2,7,1,2,14").unwrap();
 
         // when
-        let result = part_2(input);
+        let result = part_2(&input);
 
         // then
         let expected = 168_i64;
@@ -2,16 +2,56 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-use crate::common::parse::to_non_empty_lines;
+use crate::common::parse::find_invalid;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day08;
+
+const EXAMPLE: &str =
+    "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+impl Solution for Day08 {
+    type Input = Vec<String>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("26").second("61229")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                if !line.contains('|') {
+                    return Err(SolveError::at(input, line));
+                }
+                match find_invalid(line, |c| matches!(c, 'a'..='g' | ' ' | '|')) {
+                    Some(invalid) => Err(SolveError::at(input, invalid)),
+                    None => Ok(line.to_string()),
+                }
+            })
+            .collect()
+    }
 
-pub fn run_first(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    part_1(&lines).to_string()
-}
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(part_1(input).to_string())
+    }
 
-pub fn run_second(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    part_2(&lines).to_string()
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        part_2(input)
+            .map(|sum| sum.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 }
 
 pub fn part_1(input: &[String]) -> usize {
@@ -29,49 +69,40 @@ pub fn part_1(input: &[String]) -> usize {
         .sum()
 }
 
-pub fn part_2(input: &[String]) -> usize {
+pub fn part_2(input: &[String]) -> Option<usize> {
     input.iter().map(|l| get_line_value(l)).sum()
 }
 
 pub fn from_digits(digits: &[usize]) -> usize {
-    digits
-        .iter()
-        .map(|d| d.to_string())
-        .join("")
-        .parse()
-        .unwrap()
+    digits.iter().fold(0, |number, d| number * 10 + d)
 }
 
-pub fn get_line_value(input: &str) -> usize {
-    let tokens: Vec<Vec<String>> = input
-        .splitn(2, '|')
-        .map(|s| s.trim().split(' ').map(|s| s.to_string()).collect())
-        .collect();
+pub fn get_line_value(input: &str) -> Option<usize> {
+    let (segments, values) = input.split_once('|')?;
+    let segments: Vec<String> = segments.split_whitespace().map(|s| s.to_string()).collect();
+    let values: Vec<String> = values.split_whitespace().map(|s| s.to_string()).collect();
 
-    let segments = tokens.get(0).unwrap();
-    let values = tokens.get(1).unwrap();
-    let combination = find_encoding(segments);
-    decode(values, &combination)
+    let combination = find_encoding(&segments)?;
+    decode(&values, &combination)
 }
 
-pub fn decode(segments: &[String], combination: &str) -> usize {
-    let digits: Vec<_> = segments
+pub fn decode(segments: &[String], combination: &str) -> Option<usize> {
+    let digits = segments
         .iter()
         .map(|s| find_value(s, combination))
-        .collect();
+        .collect::<Option<Vec<_>>>()?;
 
-    from_digits(&digits)
+    Some(from_digits(&digits))
 }
 
-pub fn find_value(segment: &str, combination: &str) -> usize {
+pub fn find_value(segment: &str, combination: &str) -> Option<usize> {
     let valid_segments = vec![
         "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
     ];
 
-    return valid_segments
+    valid_segments
         .iter()
         .position(|&s| same_chars(segment, &map_segment(s, combination)))
-        .unwrap();
 }
 
 fn same_chars(a: &str, b: &str) -> bool {
@@ -83,18 +114,13 @@ fn same_chars(a: &str, b: &str) -> bool {
     b.chars().all(|c| set.contains(&c))
 }
 
-pub fn find_encoding(segments: &[String]) -> String {
+pub fn find_encoding(segments: &[String]) -> Option<String> {
     let signals = "abcdefg".to_string();
-    for combination in signals
+    signals
         .chars()
         .permutations(signals.len())
         .map(|s| s.iter().collect::<String>())
-    {
-        if all_segments_match(segments, &combination) {
-            return combination;
-        }
-    }
-    panic!("Did not found combination")
+        .find(|combination| all_segments_match(segments, combination))
 }
 
 pub fn all_segments_match(segments: &[String], combination: &str) -> bool {
@@ -110,7 +136,7 @@ pub fn all_segments_match(segments: &[String], combination: &str) -> bool {
 pub fn segment_matches(valid_segment: &str, combination: &str, signals: &[String]) -> bool {
     let mapped = map_segment(valid_segment, combination);
 
-    return signals.iter().any(|s| same_chars(s, &mapped));
+    signals.iter().any(|s| same_chars(s, &mapped))
 }
 
 pub fn map_segment(valid_segment: &str, combination: &str) -> String {
@@ -128,39 +154,9 @@ fn map_signal(c: char, combination: &str) -> char {
 #[cfg(test)]
 mod tests {
     use crate::common::parse::test_utils::vec_of_strings;
-    use crate::utils::read_to_string;
 
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 8;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "412";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "978171";
-        assert_eq!(result, expected.to_string());
-    }
-
     #[test]
     fn test_map_segment_1() {
         // given
@@ -199,7 +195,7 @@ mod tests {
         let result = all_segments_match(&segments, combination);
 
         // then
-        assert_eq!(result, true);
+        assert!(result);
     }
 
     #[test]
@@ -213,7 +209,7 @@ mod tests {
         let result = find_encoding(&segments);
 
         // then
-        assert_eq!(result, "deafgbc");
+        assert_eq!(result, Some("deafgbc".to_string()));
     }
 
     #[test]
@@ -226,7 +222,7 @@ mod tests {
         let result = decode(&segments, combination);
 
         // then
-        assert_eq!(result, 5353);
+        assert_eq!(result, Some(5353));
     }
 
     #[test]
@@ -239,6 +235,6 @@ mod tests {
         let result = get_line_value(line);
 
         // then
-        assert_eq!(result, 5353);
+        assert_eq!(result, Some(5353));
     }
 }
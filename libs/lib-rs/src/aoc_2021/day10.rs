@@ -1,26 +1,62 @@
 use itertools::Itertools;
 
-use crate::common::parse::to_non_empty_lines;
+use crate::common::parse::find_invalid;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day10;
+
+const EXAMPLE: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+impl Solution for Day10 {
+    type Input = Vec<String>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("26397").second("288957")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(
+                |line| match find_invalid(line, |c| is_open_char(c) || is_close_char(c)) {
+                    Some(invalid) => Err(SolveError::at(input, invalid)),
+                    None => Ok(line.to_string()),
+                },
+            )
+            .collect()
+    }
 
-pub fn run_first(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    syntax_error_score(&lines).to_string()
-}
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(syntax_error_score(input).to_string())
+    }
 
-pub fn run_second(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    line_completion_score(&lines).to_string()
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        line_completion_score(input)
+            .map(|score| score.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 }
 
-pub fn line_completion_score(input: &[String]) -> i64 {
+pub fn line_completion_score(input: &[String]) -> Option<i64> {
     let scores: Vec<_> = only_incomplete_lines(input)
         .iter()
         .map(|l| score_line_completion(l))
         .sorted()
         .collect();
 
-    let middle_index = (scores.len() - 1) / 2;
-    *scores.get(middle_index).unwrap()
+    let middle_index = scores.len().checked_sub(1)? / 2;
+    scores.get(middle_index).copied()
 }
 
 pub fn syntax_error_score(input: &[String]) -> i64 {
@@ -161,36 +197,6 @@ pub fn closes(open: char, close: char) -> bool {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::read_to_string;
-
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 10;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "399153";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "2995077699";
-        assert_eq!(result, expected.to_string());
-    }
 
     #[test]
     fn test_chunks_1() {
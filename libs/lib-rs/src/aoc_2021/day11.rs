@@ -1,86 +1,73 @@
 use std::collections::HashSet;
 
-use itertools::Itertools;
+use crate::common::grid::{Grid, Pos};
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-use crate::common::parse::to_non_empty_lines;
+pub struct Day11;
 
-pub fn run_first(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    flash_count(&lines).to_string()
-}
+const EXAMPLE: &str = "5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
 
-pub fn run_second(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    first_simultaneous(&lines).to_string()
-}
+impl Solution for Day11 {
+    type Input = OctopodesMap;
+    type Params = ();
 
-struct OctopodesMap {
-    width: i32,
-    height: i32,
-    values: Vec<i32>,
-}
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("1656").second("195")];
 
-impl OctopodesMap {
-    pub fn new(values: Vec<i32>, width: i32, height: i32) -> OctopodesMap {
-        OctopodesMap {
-            width,
-            height,
-            values,
-        }
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        Ok(OctopodesMap {
+            grid: Grid::parse_digits(input)?,
+        })
     }
 
-    fn get_index(&self, row: i32, column: i32) -> usize {
-        (row * self.width + column) as usize
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input.clone().count_flashes(100).to_string())
     }
 
-    fn get_value(&self, row: i32, column: i32) -> i32 {
-        self.values[self.get_index(row, column)]
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input.clone().first_simultaneous().to_string())
     }
+}
 
-    fn increase_energy(&mut self, value: i32) {
-        for v in self.values.iter_mut() {
+#[derive(Debug, Clone)]
+pub struct OctopodesMap {
+    grid: Grid<u8>,
+}
+
+impl OctopodesMap {
+    fn increase_energy(&mut self, value: u8) {
+        for v in self.grid.values_mut() {
             *v += value
         }
     }
 
-    fn increase_for_points(&mut self, points: &[(i32, i32)], value: usize) {
-        for &(r, c) in points.iter() {
-            let index = self.get_index(r, c);
-            *self.values.get_mut(index).unwrap() += value as i32
+    fn increase_for_points(&mut self, points: &[Pos], value: u8) {
+        for &pos in points.iter() {
+            self.grid[pos] += value
         }
     }
 
-    fn reset_points(&mut self, points: &HashSet<(i32, i32)>) {
-        for &(r, c) in points.iter() {
-            let index = self.get_index(r, c);
-            *self.values.get_mut(index).unwrap() = 0
+    fn reset_points(&mut self, points: &HashSet<Pos>) {
+        for &pos in points.iter() {
+            self.grid[pos] = 0
         }
     }
 
-    fn get_flashing(&self, flashed: &HashSet<(i32, i32)>) -> HashSet<(i32, i32)> {
-        (0..self.height)
-            .cartesian_product(0..self.width)
-            .filter(|&(row, column)| {
-                self.get_value(row, column) > 9 && !flashed.contains(&(row, column))
-            })
-            .collect()
-    }
-
-    fn get_adjacent(&self, row: i32, column: i32) -> Vec<(i32, i32)> {
-        let coords = vec![
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ];
-
-        coords
+    fn get_flashing(&self, flashed: &HashSet<Pos>) -> HashSet<Pos> {
+        self.grid
             .iter()
-            .map(|(dr, dc)| (row + dr, column + dc))
+            .filter(|&(pos, &value)| value > 9 && !flashed.contains(&pos))
+            .map(|(pos, _)| pos)
             .collect()
     }
 
@@ -93,7 +80,7 @@ impl OctopodesMap {
         loop {
             loop_count += 1;
             let step_count = self.step();
-            if step_count == self.values.len() {
+            if step_count == self.grid.len() {
                 return loop_count;
             }
         }
@@ -102,16 +89,15 @@ impl OctopodesMap {
     fn step(&mut self) -> usize {
         // increase total energy by 1
         self.increase_energy(1);
-        let mut all_flashed: HashSet<(i32, i32)> = HashSet::new();
+        let mut all_flashed: HashSet<Pos> = HashSet::new();
         loop {
             let flashed = self.get_flashing(&all_flashed);
             if flashed.is_empty() {
                 break;
             }
             all_flashed.extend(flashed.iter());
-            // get all valid adjacent points adjacent points that are not
-            // out of bounds, and not already flashed. Some points will be duplicated
-            // when they are adjacent to multiple flashing
+            // get all adjacent points that are not already flashed. Some points will be
+            // duplicated when they are adjacent to multiple flashing
             let adjacent = self.get_all_valid_adjacent(&flashed, &all_flashed);
             // increase value for adjacent
             self.increase_for_points(&adjacent, 1);
@@ -120,96 +106,24 @@ impl OctopodesMap {
         all_flashed.len()
     }
 
-    fn get_all_valid_adjacent(
-        &self,
-        points: &HashSet<(i32, i32)>,
-        flashed: &HashSet<(i32, i32)>,
-    ) -> Vec<(i32, i32)> {
+    fn get_all_valid_adjacent(&self, points: &HashSet<Pos>, flashed: &HashSet<Pos>) -> Vec<Pos> {
         points
             .iter()
-            .flat_map(|&(r, c)| self.get_adjacent(r, c))
-            .filter(|p| !self.out_of_bounds(p.0, p.1) && !flashed.contains(p))
+            .flat_map(|&pos| self.grid.neighbours8(pos))
+            .filter(|p| !flashed.contains(p))
             .collect()
     }
-
-    fn out_of_bounds(&self, row: i32, column: i32) -> bool {
-        if row < 0 || row >= self.height {
-            return true;
-        }
-        if column < 0 || column >= self.width {
-            return true;
-        }
-        false
-    }
-}
-
-fn parse_octopodes(input: &[String]) -> OctopodesMap {
-    let width = parse_line(&input[0]).len() as i32;
-    let height = input.len() as i32;
-    let values: Vec<i32> = input.iter().flat_map(|l| parse_line(l)).collect();
-
-    OctopodesMap::new(values, width, height)
-}
-
-fn parse_line(line: &str) -> Vec<i32> {
-    line.split("")
-        .filter(|&x| !x.is_empty())
-        .map(|n| n.parse().unwrap())
-        .collect()
-}
-
-pub fn flash_count(input: &[String]) -> usize {
-    let mut opd = parse_octopodes(input);
-    opd.count_flashes(100)
-}
-
-pub fn first_simultaneous(input: &[String]) -> usize {
-    let mut opd = parse_octopodes(input);
-    opd.first_simultaneous()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::common::parse::test_utils::vec_of_strings;
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 11;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "1686";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "360";
-        assert_eq!(result, expected.to_string());
-    }
-
     #[test]
     fn test_get_flashing() {
         // given
-        let input = vec_of_strings!["11111", "19991", "19191", "19991", "11111"];
-        let mut opd = parse_octopodes(&input);
-        let flashed: HashSet<(i32, i32)> = HashSet::new();
+        let mut opd = Day11::parse("11111\n19991\n19191\n19991\n11111").unwrap();
+        let flashed: HashSet<Pos> = HashSet::new();
 
         // when
         opd.increase_energy(1);
@@ -233,8 +147,7 @@ mod tests {
     #[test]
     fn test_step_1() {
         // given
-        let input = vec_of_strings!["11111", "19991", "19191", "19991", "11111"];
-        let mut opd = parse_octopodes(&input);
+        let mut opd = Day11::parse("11111\n19991\n19191\n19991\n11111").unwrap();
 
         // when
         let count = opd.step();
@@ -246,8 +159,7 @@ mod tests {
     #[test]
     fn test_step_2() {
         // given
-        let input = vec_of_strings!["11111", "19991", "19191", "19991", "11111"];
-        let mut opd = parse_octopodes(&input);
+        let mut opd = Day11::parse("11111\n19991\n19191\n19991\n11111").unwrap();
 
         // when
         let first_count = opd.step();
@@ -261,19 +173,7 @@ mod tests {
     #[test]
     fn test_count_flashes_1() {
         // given
-        let input = vec_of_strings![
-            "5483143223",
-            "2745854711",
-            "5264556173",
-            "6141336146",
-            "6357385478",
-            "4167524645",
-            "2176841721",
-            "6882881134",
-            "4846848554",
-            "5283751526"
-        ];
-        let mut opd = parse_octopodes(&input);
+        let mut opd = Day11::parse(EXAMPLE).unwrap();
 
         // when
         let flashes = opd.count_flashes(1);
@@ -285,19 +185,7 @@ mod tests {
     #[test]
     fn test_count_flashes_2() {
         // given
-        let input = vec_of_strings![
-            "5483143223",
-            "2745854711",
-            "5264556173",
-            "6141336146",
-            "6357385478",
-            "4167524645",
-            "2176841721",
-            "6882881134",
-            "4846848554",
-            "5283751526"
-        ];
-        let mut opd = parse_octopodes(&input);
+        let mut opd = Day11::parse(EXAMPLE).unwrap();
 
         // when
         let flashes = opd.count_flashes(10);
@@ -309,19 +197,7 @@ mod tests {
     #[test]
     fn test_count_flashes_3() {
         // given
-        let input = vec_of_strings![
-            "5483143223",
-            "2745854711",
-            "5264556173",
-            "6141336146",
-            "6357385478",
-            "4167524645",
-            "2176841721",
-            "6882881134",
-            "4846848554",
-            "5283751526"
-        ];
-        let mut opd = parse_octopodes(&input);
+        let mut opd = Day11::parse(EXAMPLE).unwrap();
 
         // when
         let flashes = opd.count_flashes(100);
@@ -333,19 +209,7 @@ mod tests {
     #[test]
     fn test_first_sim() {
         // given
-        let input = vec_of_strings![
-            "5483143223",
-            "2745854711",
-            "5264556173",
-            "6141336146",
-            "6357385478",
-            "4167524645",
-            "2176841721",
-            "6882881134",
-            "4846848554",
-            "5283751526"
-        ];
-        let mut opd = parse_octopodes(&input);
+        let mut opd = Day11::parse(EXAMPLE).unwrap();
 
         // when
         let flashes = opd.first_simultaneous();
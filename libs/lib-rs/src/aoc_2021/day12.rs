@@ -1,14 +1,50 @@
+use crate::common::graph::{Graph, Interner};
 use crate::common::parse::to_non_empty_lines;
-use std::collections::HashMap;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day12;
+
+const EXAMPLE: &str = "start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end";
+
+const LARGER_EXAMPLE: &str = "dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+impl Solution for Day12 {
+    type Input = CaveMatrix;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(EXAMPLE).first("10").second("36"),
+        Example::new(LARGER_EXAMPLE).first("19").second("103"),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_cave(&to_non_empty_lines(input))
+    }
 
-pub fn run_first(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    count_paths(&lines).to_string()
-}
+    fn part_one(cave: &Self::Input) -> Result<String, SolveError> {
+        Ok(cave.count_paths().to_string())
+    }
 
-pub fn run_second(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    count_paths_2(&lines).to_string()
+    fn part_two(cave: &Self::Input) -> Result<String, SolveError> {
+        Ok(cave.count_paths_2().to_string())
+    }
 }
 
 #[derive(Debug)]
@@ -19,137 +55,104 @@ pub struct Distance {
 
 #[derive(Debug)]
 pub struct CaveMatrix {
-    neighbours: HashMap<String, Vec<String>>,
+    caves: Interner<String>,
+    tunnels: Graph<usize>,
+    small: Vec<bool>,
 }
 
 #[derive(Debug, Clone)]
 pub struct CavePath {
-    caves: Vec<String>,
+    visits: Vec<usize>,
     small_twice_visited: bool,
-    visits: HashMap<String, usize>,
 }
 
 impl CavePath {
-    fn new() -> CavePath {
+    fn new(caves: usize) -> CavePath {
         CavePath {
+            visits: vec![0; caves],
             small_twice_visited: false,
-            caves: vec![],
-            visits: HashMap::new(),
-        }
-    }
-
-    fn contains(&self, cave: &str) -> bool {
-        self.caves.contains(&cave.to_string())
-    }
-
-    fn push(&mut self, cave: &str) {
-        self.caves.push(cave.to_string());
-        self.visits.insert(
-            cave.to_string(),
-            1 + if self.visits.contains_key(cave) {
-                self.visits[cave]
-            } else {
-                0
-            },
-        );
-        if !self.small_twice_visited && is_small_cave(cave) {
-            let count = self.count_visits(cave);
-            if count == 2 {
-                self.small_twice_visited = true;
-            }
         }
     }
 
-    fn count_visits(&self, cave: &str) -> usize {
-        *self.visits.get(cave).unwrap_or(&0)
-    }
-
-    fn can_push(&self, cave: &str) -> bool {
-        if !is_small_cave(cave) {
-            return true;
-        }
-
-        let cave_visits = self.count_visits(cave);
-        match cave_visits {
-            0 => true,
-            1 => {
-                if cave == "start" || cave == "end" {
-                    return false;
-                }
-
-                !self.small_twice_visited
-            }
-            _ => false,
+    fn push(&mut self, cave: usize, small: bool) {
+        self.visits[cave] += 1;
+        if small && self.visits[cave] == 2 {
+            self.small_twice_visited = true;
         }
     }
 }
 
 impl CaveMatrix {
     fn new(distances: Vec<Distance>) -> CaveMatrix {
-        let mut neighbours: HashMap<String, Vec<String>> = HashMap::new();
+        let mut caves = Interner::new();
+        let mut tunnels = Graph::new(0);
 
         for distance in distances {
-            let from = distance.from.clone();
-            let to = distance.to.clone();
-            if neighbours.contains_key(&from) {
-                let cave = neighbours.get_mut(&from).unwrap();
-                cave.push(to.clone());
-            } else {
-                neighbours.insert(from.clone(), vec![to.clone()]);
-            }
-            if neighbours.contains_key(&to) {
-                let cave = neighbours.get_mut(&to).unwrap();
-                cave.push(from.clone());
-            } else {
-                neighbours.insert(distance.to.clone(), vec![distance.from.clone()]);
-            }
+            let from = caves.intern(distance.from);
+            let to = caves.intern(distance.to);
+            tunnels.add_undirected(from, to, 1);
+        }
+        let small = caves
+            .names()
+            .iter()
+            .map(|cave| is_small_cave(cave))
+            .collect();
+
+        CaveMatrix {
+            caves,
+            tunnels,
+            small,
         }
-
-        CaveMatrix { neighbours }
     }
 
     fn count_paths(&self) -> usize {
-        let mut curr_path = CavePath::new();
-        let mut all_paths: Vec<CavePath> = vec![];
-        let start = "start";
-        self.dfs(start, &mut curr_path, &mut all_paths);
-        all_paths.len()
+        self.count_from_start(false)
     }
 
     fn count_paths_2(&self) -> usize {
-        let mut curr_path = CavePath::new();
-        let mut all_paths: Vec<CavePath> = vec![];
-        let start = "start";
-        self.dfs_2(start, &mut curr_path, &mut all_paths);
-        all_paths.len()
+        self.count_from_start(true)
     }
 
-    fn dfs(&self, current: &str, path: &mut CavePath, all_paths: &mut Vec<CavePath>) {
-        path.push(current);
-
-        if current == "end" {
-            all_paths.push(path.clone());
-        }
-
-        for cave in self.neighbours.get(current).unwrap().iter() {
-            if !(is_small_cave(cave) && path.contains(cave)) {
-                self.dfs(cave, &mut path.clone(), all_paths)
-            }
-        }
+    fn count_from_start(&self, small_twice: bool) -> usize {
+        let (Some(start), Some(end)) = (self.caves.id("start"), self.caves.id("end")) else {
+            return 0;
+        };
+        let mut path = CavePath::new(self.caves.len());
+        path.push(start, self.small[start]);
+        self.dfs(start, &path, small_twice, [start, end])
     }
 
-    fn dfs_2(&self, current: &str, path: &mut CavePath, all_paths: &mut Vec<CavePath>) {
-        path.push(current);
-
-        if current == "end" {
-            all_paths.push(path.clone());
+    /// Paths to the end from `current`, `path` already visited it.
+    fn dfs(
+        &self,
+        current: usize,
+        path: &CavePath,
+        small_twice: bool,
+        [start, end]: [usize; 2],
+    ) -> usize {
+        if current == end {
+            return 1;
         }
 
-        for cave in self.neighbours.get(current).unwrap().iter() {
-            if path.can_push(cave) {
-                self.dfs_2(cave, &mut path.clone(), all_paths)
-            }
-        }
+        self.tunnels
+            .neighbours(current)
+            .iter()
+            .filter(|&&(cave, _)| {
+                // A single small cave other than the start and the end may be visited twice
+                let twice = small_twice && cave != start && cave != end;
+                let allowed_visits = if twice && !path.small_twice_visited {
+                    2
+                } else {
+                    1
+                };
+                !self.small[cave] || path.visits[cave] < allowed_visits
+            })
+            .map(|&(cave, _)| {
+                let mut path = path.clone();
+                path.push(cave, self.small[cave]);
+                self.dfs(cave, &path, small_twice, [start, end])
+            })
+            .sum()
     }
 }
 
@@ -157,71 +160,35 @@ fn is_small_cave(cave: &str) -> bool {
     cave.chars().any(|c| c.is_lowercase())
 }
 
-fn parse_input(input: &[String]) -> Vec<Distance> {
+fn parse_input(input: &[String]) -> Result<Vec<Distance>, SolveError> {
     input
         .iter()
-        .map(|line| {
-            let tokens: Vec<&str> = line.split('-').collect();
-            let from = tokens[0].to_string();
-            let to = tokens[1].to_string();
-            Distance { from, to }
+        .enumerate()
+        .map(|(idx, line)| {
+            let (from, to) = line
+                .split_once('-')
+                .ok_or(SolveError::parse(idx + 1, 1, line))?;
+            Ok(Distance {
+                from: from.to_string(),
+                to: to.to_string(),
+            })
         })
         .collect()
 }
 
-fn parse_cave(input: &[String]) -> CaveMatrix {
-    CaveMatrix::new(parse_input(input))
-}
-
-fn count_paths(input: &[String]) -> usize {
-    let cave = parse_cave(input);
-    cave.count_paths()
-}
-
-fn count_paths_2(input: &[String]) -> usize {
-    let cave = parse_cave(input);
-    cave.count_paths_2()
+fn parse_cave(input: &[String]) -> Result<CaveMatrix, SolveError> {
+    Ok(CaveMatrix::new(parse_input(input)?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::common::parse::test_utils::vec_of_strings;
-    use crate::utils::read_to_string;
-
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 12;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "5212";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "134862";
-        assert_eq!(result, expected.to_string());
-    }
 
     #[test]
     fn test_count_paths_1() {
         let input = vec_of_strings!["start-A", "start-b", "A-c", "A-b", "b-d", "A-end", "b-end"];
-        let cave = parse_cave(&input);
+        let cave = parse_cave(&input).unwrap();
         assert_eq!(cave.count_paths(), 10)
     }
 
@@ -231,14 +198,14 @@ mod tests {
             "dc-end", "HN-start", "start-kj", "dc-start", "dc-HN", "LN-dc", "HN-end", "kj-sa",
             "kj-HN", "kj-dc"
         ];
-        let cave = parse_cave(&input);
+        let cave = parse_cave(&input).unwrap();
         assert_eq!(cave.count_paths(), 19)
     }
 
     #[test]
     fn test_count_paths_2_1() {
         let input = vec_of_strings!["start-A", "start-b", "A-c", "A-b", "b-d", "A-end", "b-end"];
-        let cave = parse_cave(&input);
+        let cave = parse_cave(&input).unwrap();
         assert_eq!(cave.count_paths_2(), 36)
     }
 }
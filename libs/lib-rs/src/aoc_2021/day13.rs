@@ -3,33 +3,79 @@ use std::fmt;
 use std::fmt::Formatter;
 use std::iter::FromIterator;
 
-pub fn run_first(input: &str) -> String {
-    first_fold(input).to_string()
-}
+use crate::common::geom::Point2;
+use crate::common::grid::Grid;
+use crate::common::ocr::read_letters;
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day13;
+
+const EXAMPLE: &str = "6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5";
+
+impl Solution for Day13 {
+    type Input = (Paper, Vec<Fold>);
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE)
+        .first("17")
+        .second("##########\n##      ##\n##      ##\n##      ##\n##########\n")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_one((paper, folds): &Self::Input) -> Result<String, SolveError> {
+        if folds.is_empty() {
+            return Err(SolveError::NoSolution);
+        }
+        Ok(first_fold(paper, folds).to_string())
+    }
+
+    fn part_two((paper, folds): &Self::Input) -> Result<String, SolveError> {
+        Ok(code(paper, folds).0)
+    }
 
-pub fn run_second(input: &str) -> String {
-    code(input)
+    fn part_two_drawn(
+        (paper, folds): &Self::Input,
+        _params: &(),
+    ) -> Result<(String, Option<String>), SolveError> {
+        let (letters, drawing) = code(paper, folds);
+        Ok((letters, Some(drawing)))
+    }
 }
 
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-struct Point {
-    row: usize,
-    column: usize,
+/// `x` is the column and `y` the row.
+type Point = Point2<usize>;
+
+fn fold_x(point: &Point, value: usize) -> Point {
+    Point::new(point.x - 2 * (point.x - value), point.y)
 }
 
-impl Point {
-    fn fold_x(&self, value: usize) -> Point {
-        Point {
-            row: self.row,
-            column: self.column - 2 * (self.column - value),
-        }
-    }
-    fn fold_y(&self, value: usize) -> Point {
-        Point {
-            row: self.row - 2 * (self.row - value),
-            column: self.column,
-        }
-    }
+fn fold_y(point: &Point, value: usize) -> Point {
+    Point::new(point.x, point.y - 2 * (point.y - value))
 }
 
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
@@ -38,12 +84,13 @@ enum Axis {
     Y = 1,
 }
 
-struct Fold {
+pub struct Fold {
     axis: Axis,
     value: usize,
 }
 
-struct Paper {
+#[derive(Clone)]
+pub struct Paper {
     points: HashSet<Point>,
 }
 
@@ -67,9 +114,9 @@ impl Paper {
             .points
             .iter()
             .cloned()
-            .filter(|p| p.column > value)
+            .filter(|p| p.x > value)
             .collect();
-        let folded: Vec<_> = points_to_fold.iter().map(|p| p.fold_x(value)).collect();
+        let folded: Vec<_> = points_to_fold.iter().map(|p| fold_x(p, value)).collect();
 
         self.remove_points(points_to_fold);
 
@@ -94,9 +141,9 @@ impl Paper {
             .points
             .iter()
             .cloned()
-            .filter(|p| p.row > value)
+            .filter(|p| p.y > value)
             .collect();
-        let folded: Vec<_> = points_to_fold.iter().map(|p| p.fold_y(value)).collect();
+        let folded: Vec<_> = points_to_fold.iter().map(|p| fold_y(p, value)).collect();
 
         self.remove_points(points_to_fold);
         self.add_points(folded);
@@ -107,11 +154,19 @@ impl Paper {
     }
 
     fn max_row(&self) -> usize {
-        self.points.iter().map(|p| p.row).max().unwrap()
+        self.points.iter().map(|p| p.y).max().unwrap_or(0)
     }
 
     fn max_column(&self) -> usize {
-        self.points.iter().map(|p| p.column).max().unwrap()
+        self.points.iter().map(|p| p.x).max().unwrap_or(0)
+    }
+
+    fn to_grid(&self) -> Grid<bool> {
+        let mut grid = Grid::new(self.max_column() + 1, self.max_row() + 1, false);
+        for p in &self.points {
+            grid[(p.y, p.x)] = true;
+        }
+        grid
     }
 }
 
@@ -121,7 +176,7 @@ impl fmt::Display for Paper {
         for row in 0..=self.max_row() {
             let mut line = "".to_string();
             for column in 0..=self.max_column() {
-                if self.points.contains(&Point { row, column }) {
+                if self.points.contains(&Point::new(column, row)) {
                     line += "##"
                 } else {
                     line += "  "
@@ -134,65 +189,82 @@ impl fmt::Display for Paper {
     }
 }
 
-fn first_fold(input: &str) -> usize {
-    let (mut paper, folds) = parse_input(input);
-    paper.apply_fold(&folds[0]);
+fn first_fold(paper: &Paper, folds: &[Fold]) -> usize {
+    let mut paper = paper.clone();
+    for fold in folds.iter().take(1) {
+        paper.apply_fold(fold);
+    }
     paper.count_dots()
 }
 
-fn code(input: &str) -> String {
-    let (mut paper, folds) = parse_input(input);
-    for fold in &folds {
+fn fold_all(paper: &Paper, folds: &[Fold]) -> Paper {
+    let mut paper = paper.clone();
+    for fold in folds {
         paper.apply_fold(fold);
     }
-    println!("{paper}");
-    paper.to_string()
+    paper
+}
+
+/// Letters left on the paper after every fold and the drawing they are read from, the
+/// drawing stands in for the letters when they can't be read.
+fn code(paper: &Paper, folds: &[Fold]) -> (String, String) {
+    let paper = fold_all(paper, folds);
+    let drawing = paper.to_string();
+    let letters = read_letters(&paper.to_grid()).unwrap_or_else(|| drawing.clone());
+    (letters, drawing)
 }
 
-fn parse_input(input: &str) -> (Paper, Vec<Fold>) {
+fn parse_input(input: &str) -> Result<(Paper, Vec<Fold>), SolveError> {
     let mut parts = input.split("\n\n");
 
-    let points: Vec<Point> = parts
+    let points = parts
         .next()
-        .unwrap()
+        .unwrap_or_default()
         .split('\n')
         .filter(|l| !l.is_empty())
         .map(|l| {
-            let nums: Vec<usize> = l.split(',').map(|n| n.trim().parse().unwrap()).collect();
-            Point {
-                column: nums[0],
-                row: nums[1],
-            }
+            let (column, row) = l.split_once(',').ok_or(SolveError::at(input, l))?;
+            Ok(Point::new(
+                parse_value(input, column.trim())?,
+                parse_value(input, row.trim())?,
+            ))
         })
-        .collect();
+        .collect::<Result<Vec<Point>, SolveError>>()?;
 
     let paper = Paper::new(&points);
 
     let folds = parts
         .next()
-        .unwrap()
+        .unwrap_or_default()
         .split('\n')
+        .map(|l| l.trim())
         .filter(|l| !l.is_empty())
         .map(|l| {
-            let fold_token = l.split(' ').filter(|l| !l.is_empty()).nth(2).unwrap();
-            let parts: Vec<_> = fold_token.split('=').collect();
-            let axis = match parts[0] {
+            let fold_token = l
+                .strip_prefix("fold along ")
+                .ok_or(SolveError::at(input, l))?;
+            let (axis, value) = fold_token
+                .split_once('=')
+                .ok_or(SolveError::at(input, fold_token))?;
+            let axis = match axis {
                 "y" => Axis::Y,
                 "x" => Axis::X,
-                &_ => unreachable!(),
+                _ => return Err(SolveError::at(input, axis)),
             };
 
-            let value: usize = parts[1].parse().unwrap();
-            Fold { axis, value }
+            let value: usize = parse_value(input, value)?;
+            Ok(Fold { axis, value })
         })
-        .collect();
+        .collect::<Result<Vec<Fold>, SolveError>>()?;
 
-    (paper, folds)
+    Ok((paper, folds))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
+    use crate::answers::test_utils::real_cases;
+    use crate::common::ocr::read_drawing;
+    use crate::Part;
 
     use super::*;
 
@@ -200,16 +272,22 @@ mod tests {
     const DAY: u8 = 13;
 
     #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "701";
-        assert_eq!(result, expected.to_string());
+    fn test_drawing_reported() {
+        for case in real_cases(YEAR, DAY, Part::Second) {
+            // when
+            let second = Day13::solve(&case.input).second.unwrap();
+
+            // then
+            let drawing = second.drawing.unwrap();
+            assert_eq!(drawing.lines().count(), 6, "profile {}", case.profile);
+            // Every dot is drawn two characters wide
+            let narrow: String = drawing
+                .lines()
+                .map(|line| line.chars().step_by(2).collect::<String>() + "\n")
+                .collect();
+            assert_eq!(read_drawing(&narrow), second.answer);
+            assert_eq!(second.answer, Some(case.expected));
+        }
     }
 
     #[test]
@@ -239,7 +317,8 @@ mod tests {
             fold along x=5";
 
         // when
-        let count = first_fold(input);
+        let (paper, folds) = Day13::parse(input).unwrap();
+        let count = first_fold(&paper, &folds);
 
         // then
         assert_eq!(count, 17);
@@ -1,11 +1,73 @@
 use std::collections::BTreeMap;
 
-pub fn run_first(input: &str) -> String {
-    min_max_diff(input, 10).to_string()
+use crate::common::parse::find_invalid;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::params;
+use crate::solution::Solution;
+
+pub struct Day14;
+
+const EXAMPLE: &str = "NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C";
+
+params! {
+    pub struct Day14Params {
+        /// Insertion steps in part one
+        first_steps: i64 = 10,
+        /// Insertion steps in part two
+        second_steps: i64 = 40,
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    min_max_diff(input, 40).to_string()
+impl Solution for Day14 {
+    type Input = (String, BTreeMap<String, String>);
+    type Params = Day14Params;
+
+    const EXAMPLES: &'static [Example] =
+        &[Example::new(EXAMPLE).first("1588").second("2188189693529")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Self::part_one_with(input, &Day14Params::default())
+    }
+
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Self::part_two_with(input, &Day14Params::default())
+    }
+
+    fn part_one_with(
+        (polymer, insertions): &Self::Input,
+        params: &Day14Params,
+    ) -> Result<String, SolveError> {
+        Ok(min_max_diff(polymer, insertions, params.first_steps)?.to_string())
+    }
+
+    fn part_two_with(
+        (polymer, insertions): &Self::Input,
+        params: &Day14Params,
+    ) -> Result<String, SolveError> {
+        Ok(min_max_diff(polymer, insertions, params.second_steps)?.to_string())
+    }
 }
 
 fn char_windows(src: &str, win_size: i64) -> impl Iterator<Item = &str> {
@@ -17,9 +79,11 @@ fn char_windows(src: &str, win_size: i64) -> impl Iterator<Item = &str> {
     })
 }
 
-pub fn min_max_diff(input: &str, steps: i64) -> i64 {
-    let (polymer, insertions) = parse_input(input);
-
+pub fn min_max_diff(
+    polymer: &str,
+    insertions: &BTreeMap<String, String>,
+    steps: i64,
+) -> Result<i64, SolveError> {
     let mut pairs_count: BTreeMap<String, i64> = BTreeMap::new();
     let mut element_count: BTreeMap<char, i64> = BTreeMap::new();
 
@@ -27,7 +91,7 @@ pub fn min_max_diff(input: &str, steps: i64) -> i64 {
         *element_count.entry(c).or_insert(0) += 1
     }
 
-    for pair in char_windows(&polymer, 2) {
+    for pair in char_windows(polymer, 2) {
         *pairs_count.entry(pair.to_string()).or_insert(0) += 1;
     }
 
@@ -35,16 +99,20 @@ pub fn min_max_diff(input: &str, steps: i64) -> i64 {
         let mut to_add: Vec<(String, i64)> = vec![];
         let mut to_remove: Vec<(String, i64)> = vec![];
 
-        for (pair, insert) in &insertions {
+        for (pair, insert) in insertions {
             let pair_str = pair.to_string();
             let curr_pair_count = *pairs_count.get(&pair_str).unwrap_or(&0);
 
             if curr_pair_count > 0 {
-                let left_insert = format!("{}{}", pair_str.chars().next().unwrap(), insert);
-                let right_insert = format!("{}{}", insert, pair_str.chars().nth(1).unwrap());
+                let left_insert = format!("{}{}", &pair_str[..1], insert);
+                let right_insert = format!("{}{}", insert, &pair_str[1..]);
 
-                let inserted_char = insertions.get(&pair_str).unwrap().chars().next().unwrap();
-                *element_count.entry(inserted_char).or_insert(0) += curr_pair_count;
+                for inserted_char in insert.chars() {
+                    let count = element_count.entry(inserted_char).or_insert(0);
+                    *count = count
+                        .checked_add(curr_pair_count)
+                        .ok_or(SolveError::Overflow)?;
+                }
 
                 to_add.push((left_insert, curr_pair_count));
                 to_add.push((right_insert, curr_pair_count));
@@ -54,7 +122,8 @@ pub fn min_max_diff(input: &str, steps: i64) -> i64 {
 
         for (key, value) in to_add {
             // Add all new created pairs
-            *pairs_count.entry(key.to_string()).or_insert(0) += value;
+            let count = pairs_count.entry(key.to_string()).or_insert(0);
+            *count = count.checked_add(value).ok_or(SolveError::Overflow)?;
         }
 
         for (key, value) in to_remove {
@@ -62,73 +131,44 @@ pub fn min_max_diff(input: &str, steps: i64) -> i64 {
             *pairs_count.entry(key.to_string()).or_insert(0) -= value;
         }
     }
-    let max = *element_count
-        .iter()
-        .max_by_key(|&(_, count)| count)
-        .unwrap()
-        .1;
-    let min = *element_count
-        .iter()
-        .min_by_key(|&(_, count)| count)
-        .unwrap()
-        .1;
-    max - min
+    let max = element_count.values().max().ok_or(SolveError::NoSolution)?;
+    let min = element_count.values().min().ok_or(SolveError::NoSolution)?;
+    Ok(max - min)
 }
 
-fn parse_input(input: &str) -> (String, BTreeMap<String, String>) {
+fn parse_input(input: &str) -> Result<(String, BTreeMap<String, String>), SolveError> {
     let mut parts = input.split("\n\n").filter(|l| !l.is_empty());
 
-    let polymer: String = parts.next().unwrap().to_string();
+    let polymer = parts.next().unwrap_or_default().trim();
+    if let Some(invalid) = find_invalid(polymer, |c| c.is_ascii_uppercase()) {
+        return Err(SolveError::at(input, invalid));
+    }
+    let polymer = polymer.to_string();
 
-    let insertions: BTreeMap<_, _> = parts
+    let insertions = parts
         .next()
-        .unwrap()
+        .unwrap_or_default()
         .split('\n')
         .filter(|l| !l.is_empty())
-        .map(|l| {
-            let insert_tokens: Vec<_> = l.trim().split(" -> ").filter(|l| !l.is_empty()).collect();
-            (insert_tokens[0].to_string(), insert_tokens[1].to_string())
+        .map(|l| match l.trim().split_once(" -> ") {
+            Some((pair, insert)) if is_element_pair(pair) && !insert.is_empty() => {
+                Ok((pair.to_string(), insert.to_string()))
+            }
+            _ => Err(SolveError::at(input, l)),
         })
-        .collect();
+        .collect::<Result<BTreeMap<_, _>, SolveError>>()?;
+
+    Ok((polymer, insertions))
+}
 
-    (polymer, insertions)
+fn is_element_pair(pair: &str) -> bool {
+    pair.len() == 2 && pair.bytes().all(|b| b.is_ascii_uppercase())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 14;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "2068";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "2158894777814";
-        assert_eq!(result, expected.to_string());
-    }
-
     #[test]
     fn test_min_max_diff() {
         let input = "NNCB
@@ -150,6 +190,7 @@ BC -> B
 CC -> N
 CN -> C";
 
-        assert_eq!(min_max_diff(&input, 10), 1588);
+        let (polymer, insertions) = Day14::parse(input).unwrap();
+        assert_eq!(min_max_diff(&polymer, &insertions, 10), Ok(1588));
     }
 }
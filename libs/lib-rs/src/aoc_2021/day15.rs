@@ -1,220 +1,99 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-
-use crate::common::parse::to_non_empty_lines;
-
-pub fn run_first(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    let matrix = parse_input(&lines);
-    matrix.tl_br_risk().to_string()
-}
-
-pub fn run_second(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    let matrix = parse_extended(&lines);
-    matrix.tl_br_risk().to_string()
-}
-
-#[derive(Debug)]
-struct RiskMatrix {
-    risk_values: Vec<i32>,
-    width: i32,
-    height: i32,
-}
-
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-struct Point {
-    row: i32,
-    column: i32,
-}
-
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-pub struct RiskPoint {
-    coords: Point,
-    risk: i32,
-}
-
-impl PartialOrd<Self> for RiskPoint {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for RiskPoint {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.risk.cmp(&self.risk)
-    }
-}
-
-impl RiskMatrix {
-    fn get_index(&self, row: i32, column: i32) -> usize {
-        (row * self.width + column) as usize
-    }
-
-    fn get_risk(&self, row: i32, column: i32) -> i32 {
-        self.risk_values[self.get_index(row, column)]
-    }
-
-    fn get_adjacent(&self, point: &RiskPoint) -> Vec<RiskPoint> {
-        let row = point.coords.row;
-        let col = point.coords.column;
-        let coords = vec![(0, 1), (0, -1), (-1, 0), (1, 0)];
-
-        coords
-            .into_iter()
-            .filter(|(dr, dc)| {
-                let r = row + dr;
-                let c = col + dc;
-                let oob = self.out_of_bounds(r, c);
-                !oob
-            })
-            .map(|(dr, dc)| {
-                let new_row = row + dr;
-                let new_col = col + dc;
-                let risk = self.get_risk(new_row, new_col);
-                RiskPoint {
-                    coords: Point {
-                        row: new_row,
-                        column: new_col,
-                    },
-                    risk,
-                }
-            })
-            .collect()
-    }
-
-    fn out_of_bounds(&self, row: i32, column: i32) -> bool {
-        if row < 0 || row >= self.height {
-            return true;
-        }
-        if column < 0 || column >= self.width {
-            return true;
-        }
-        false
-    }
-
-    fn find_risk(&self, start: Point, end: Point) -> i32 {
-        let mut came_from: HashMap<Point, Point> = HashMap::new();
-        let mut risk_so_far: HashMap<Point, i32> = HashMap::new();
-        let mut priority_queue: BinaryHeap<RiskPoint> = BinaryHeap::new();
-
-        let current_point = start;
-
-        let mut current = RiskPoint {
-            risk: 0,
-            coords: current_point,
-        };
-
-        priority_queue.push(current);
-        *came_from.entry(current_point).or_insert(current_point) = current_point;
-        *risk_so_far.entry(current_point).or_insert(0) = 0;
-
-        while !priority_queue.is_empty() {
-            current = priority_queue.pop().unwrap();
-            if current.coords == end {
-                break;
-            }
-
-            for adjacent in self.get_adjacent(&current) {
-                // set new risk of adjacent to the cost of path so far + adjacent risk
-                let new_risk = risk_so_far.get(&current.coords).unwrap() + adjacent.risk;
-
-                if !risk_so_far.contains_key(&adjacent.coords)
-                    || new_risk < *risk_so_far.get(&adjacent.coords).unwrap()
-                {
-                    *risk_so_far.entry(adjacent.coords).or_insert(new_risk) = new_risk;
-
-                    let priority = new_risk;
-                    priority_queue.push(RiskPoint {
-                        coords: adjacent.coords,
-                        risk: priority,
-                    });
-                    *came_from.entry(adjacent.coords).or_insert(current.coords) = current.coords;
-                }
-            }
-        }
-
-        *risk_so_far.get(&end).unwrap()
+use crate::common::grid::{Grid, Pos};
+use crate::common::search::{astar, Path};
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day15;
+
+const EXAMPLE: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+impl Solution for Day15 {
+    type Input = RiskMatrix;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("40").second("315")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        Ok(RiskMatrix {
+            risk_values: Grid::parse_digits(input)?,
+        })
     }
 
-    fn tl_br_risk(&self) -> i32 {
-        let top_left: Point = Point { row: 0, column: 0 };
-        let bottom_right: Point = Point {
-            row: self.height - 1,
-            column: self.width - 1,
-        };
-        self.find_risk(top_left, bottom_right)
+    fn part_one(matrix: &Self::Input) -> Result<String, SolveError> {
+        matrix
+            .tl_br_risk()
+            .map(|path| path.cost.to_string())
+            .ok_or(SolveError::NoSolution)
     }
-}
-
-fn parse_input(input: &[String]) -> RiskMatrix {
-    let height = input.len() as i32;
-    let width = input
-        .iter()
-        .next()
-        .unwrap()
-        .split("")
-        .filter(|n| !n.is_empty())
-        .count() as i32;
 
-    let values = parse_values(input).iter().flatten().cloned().collect();
-
-    RiskMatrix {
-        risk_values: values,
-        height,
-        width,
+    fn part_two(matrix: &Self::Input) -> Result<String, SolveError> {
+        matrix
+            .extended()
+            .tl_br_risk()
+            .map(|path| path.cost.to_string())
+            .ok_or(SolveError::NoSolution)
     }
 }
 
-fn parse_values(input: &[String]) -> Vec<Vec<i32>> {
-    input
-        .iter()
-        .map(|l| {
-            l.trim()
-                .split("")
-                .filter(|n| !n.is_empty())
-                .map(|n| n.parse().unwrap())
-                .collect()
-        })
-        .collect()
-}
-
-fn parse_extended(input: &[String]) -> RiskMatrix {
-    let values = parse_and_extend(input);
-    let height = values.len() as i32;
-    let width = values[0].len() as i32;
-    let flat = values.iter().flatten().cloned().collect();
-    RiskMatrix {
-        risk_values: flat,
-        height,
-        width,
-    }
+#[derive(Debug)]
+pub struct RiskMatrix {
+    risk_values: Grid<u8>,
 }
 
-fn parse_and_extend(input: &[String]) -> Vec<Vec<i32>> {
-    let initial = parse_values(input);
-
-    let mut lines: Vec<Vec<i32>> = vec![];
-
-    for idx in 0..5 {
-        let shifted: Vec<Vec<i32>> = initial
-            .iter()
-            .map(|n| n.iter().map(|n| conv(*n, idx)).collect())
-            .collect();
-
-        for row in shifted.iter() {
-            let mut result: Vec<i32> = vec![];
-            for i in 0..5 {
-                result.extend(row.iter().map(|n| conv(*n, i)))
-            }
-            lines.push(result)
+impl RiskMatrix {
+    fn get_adjacent(&self, pos: Pos) -> impl Iterator<Item = (Pos, u32)> + '_ {
+        self.risk_values
+            .neighbours4(pos)
+            .map(|adjacent| (adjacent, self.risk_values[adjacent] as u32))
+    }
+
+    /// Least risky route, every position entered adds its risk.
+    ///
+    /// Every step costs at least 1 so the manhattan distance to `end` guides the search.
+    fn find_risk(&self, start: Pos, end: Pos) -> Option<Path<Pos, u32>> {
+        let manhattan =
+            |&(row, column): &Pos| (row.abs_diff(end.0) + column.abs_diff(end.1)) as u32;
+        astar(
+            [start],
+            |&pos| self.get_adjacent(pos),
+            manhattan,
+            |&pos| pos == end,
+        )
+    }
+
+    fn tl_br_risk(&self) -> Option<Path<Pos, u32>> {
+        let bottom_right = (
+            self.risk_values.height().checked_sub(1)?,
+            self.risk_values.width().checked_sub(1)?,
+        );
+        self.find_risk((0, 0), bottom_right)
+    }
+
+    /// The full map, the tile repeated five times in both directions with increasing risk.
+    fn extended(&self) -> RiskMatrix {
+        let (width, height) = (self.risk_values.width(), self.risk_values.height());
+        let mut extended = Grid::new(width * 5, height * 5, 0);
+        for (row, column) in extended.positions().collect::<Vec<_>>() {
+            let tile = (row / height + column / width) as u8;
+            extended[(row, column)] = conv(self.risk_values[(row % height, column % width)], tile);
+        }
+        RiskMatrix {
+            risk_values: extended,
         }
     }
-
-    lines
 }
 
-fn conv(n: i32, idx: i32) -> i32 {
+fn conv(n: u8, idx: u8) -> u8 {
     let shifted = n + idx;
     if shifted == 9 {
         return shifted;
@@ -224,68 +103,37 @@ fn conv(n: i32, idx: i32) -> i32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::common::parse::test_utils::vec_of_strings;
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 15;
-
     #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "595";
-        assert_eq!(result, expected.to_string());
+    fn test_risk_1() {
+        let mat = Day15::parse(EXAMPLE).unwrap();
+        let risk = mat.tl_br_risk().map(|path| path.cost);
+        assert_eq!(risk, Some(40));
     }
 
     #[test]
-    fn test_part_2() {
+    fn test_risk_route() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let mat = Day15::parse(EXAMPLE).unwrap();
 
         // when
-        let result = run_second(&data);
+        let path = mat.tl_br_risk().unwrap();
 
         // then
-        let expected = "2914";
-        assert_eq!(result, expected.to_string());
-    }
-
-    fn mock_input() -> Vec<String> {
-        vec_of_strings![
-            "1163751742",
-            "1381373672",
-            "2136511328",
-            "3694931569",
-            "7463417111",
-            "1319128137",
-            "1359912421",
-            "3125421639",
-            "1293138521",
-            "2311944581"
-        ]
-    }
-
-    #[test]
-    fn test_risk_1() {
-        let input = mock_input();
-        let mat = parse_input(&input);
-        let risk = mat.tl_br_risk();
-        assert_eq!(risk, 40);
+        assert_eq!(path.start(), Some(&(0, 0)));
+        assert_eq!(path.goal(), Some(&(9, 9)));
+        let entered_risk: u32 = path.nodes[1..]
+            .iter()
+            .map(|&pos| mat.risk_values[pos] as u32)
+            .sum();
+        assert_eq!(entered_risk, path.cost);
     }
 
     #[test]
     fn test_risk_extended() {
-        let input = mock_input();
-        let mat = parse_extended(&input);
-        let risk = mat.tl_br_risk();
-        assert_eq!(risk, 315);
+        let mat = Day15::parse(EXAMPLE).unwrap().extended();
+        let risk = mat.tl_br_risk().map(|path| path.cost);
+        assert_eq!(risk, Some(315));
     }
 }
@@ -1,267 +1,445 @@
-pub fn run_first(input: &str) -> String {
-    let trimmed = input.trim();
-    version_sum(trimmed).to_string()
-}
+use std::str::FromStr;
+
+use crate::common::bits::{hex_to_bytes, BitReader, BitWriter};
+use crate::common::parse::find_invalid;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Packet;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[
+        Example::new("8A004A801A8002F478").first("16"),
+        Example::new("620080001611562C8802118E34").first("12"),
+        Example::new("C0015000016115A2E0802F182340").first("23"),
+        Example::new("A0016C880162017C3686B18A3D4780").first("31"),
+        Example::new("C200B40A82").second("3"),
+        Example::new("04005AC33890").second("54"),
+        Example::new("880086C3E88112").second("7"),
+        Example::new("CE00C43D881120").second("9"),
+        Example::new("D8005AC2A8F0").second("1"),
+        Example::new("F600BC2D8F").second("0"),
+        Example::new("9C005AC2F8F0").second("0"),
+        Example::new("9C0141080250320F1802104A08").second("1"),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        let hex = input.trim();
+        if let Some(invalid) = find_invalid(hex, |c| c.is_ascii_hexdigit()) {
+            return Err(SolveError::at(input, invalid));
+        }
+        let bytes = hex_to_bytes(hex).ok_or_else(|| SolveError::at(input, hex))?;
+        let mut decoder = Decoder {
+            input,
+            hex,
+            bits: BitReader::with_len(&bytes, hex.len() * 4),
+        };
+        decoder.packet()
+    }
 
-pub fn run_second(input: &str) -> String {
-    let trimmed = input.trim();
-    eval_packets(trimmed).to_string()
-}
+    fn part_one(packet: &Self::Input) -> Result<String, SolveError> {
+        Ok(packet.version_sum().to_string())
+    }
 
-#[derive(Eq, PartialEq, Debug)]
-pub enum PacketType {
-    LiteralValue = 0,
-    Operator = 1,
+    fn part_two(packet: &Self::Input) -> Result<String, SolveError> {
+        packet.value().map(|value| value.to_string())
+    }
 }
 
-#[derive(Eq, PartialEq, Debug)]
-pub enum LengthType {
-    SubPacketsTotalLength = 0,
-    NumSubPackets = 1,
-}
+const LITERAL_TYPE_ID: u64 = 4;
 
-#[derive(Clone, Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Packet {
     version: u8,
-    type_id: u8,
-    subpackets: Vec<Packet>,
-    value: i64,
+    kind: PacketKind,
 }
 
-fn parse_packet(mut offset: i64, binary: &str) -> (Packet, i64) {
-    let version = version(offset, binary);
-    offset += 3;
-    let type_id = type_id(offset, binary);
-    offset += 3;
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketKind {
+    Literal(u64),
+    Operator(Operator, Vec<Packet>),
+}
 
-    match packet_type(type_id) {
-        PacketType::LiteralValue => parse_literal(offset, version, type_id, binary),
-        PacketType::Operator => parse_operator(offset, version, type_id, binary),
-    }
+/// What an operator packet computes from the values of its subpackets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Sum,
+    Product,
+    Min,
+    Max,
+    /// `1` when the first subpacket is greater than the second, else `0`.
+    Gt,
+    /// `1` when the first subpacket is less than the second, else `0`.
+    Lt,
+    /// `1` when both subpackets are equal, else `0`.
+    Eq,
 }
 
-fn parse_literal(mut offset: i64, version: u8, type_id: u8, binary: &str) -> (Packet, i64) {
-    let mut literal_str = "".to_string();
+impl Operator {
+    fn from_type_id(type_id: u64) -> Option<Self> {
+        match type_id {
+            0 => Some(Operator::Sum),
+            1 => Some(Operator::Product),
+            2 => Some(Operator::Min),
+            3 => Some(Operator::Max),
+            5 => Some(Operator::Gt),
+            6 => Some(Operator::Lt),
+            7 => Some(Operator::Eq),
+            _ => None,
+        }
+    }
 
-    while binary.chars().nth(offset as usize).unwrap() == '1' {
-        let start = offset + 1;
-        let end = offset + 5;
-        literal_str += &binary[start as usize..end as usize];
-        offset += 5;
+    fn type_id(self) -> u64 {
+        match self {
+            Operator::Sum => 0,
+            Operator::Product => 1,
+            Operator::Min => 2,
+            Operator::Max => 3,
+            Operator::Gt => 5,
+            Operator::Lt => 6,
+            Operator::Eq => 7,
+        }
     }
 
-    let start = offset + 1;
-    let end = offset + 5;
-    literal_str += &binary[start as usize..end as usize];
-
-    offset += 5;
-    let value = i64::from_str_radix(&literal_str, 2).unwrap();
-    let packet = Packet {
-        version,
-        type_id,
-        value,
-        subpackets: vec![],
-    };
-    (packet, offset)
+    /// Whether the operator applies to `count` subpackets, comparisons take exactly two and
+    /// the others at least one.
+    fn accepts(self, count: usize) -> bool {
+        match self {
+            Operator::Gt | Operator::Lt | Operator::Eq => count == 2,
+            _ => count >= 1,
+        }
+    }
 }
 
-fn parse_operator(mut offset: i64, version: u8, type_id: u8, binary: &str) -> (Packet, i64) {
-    let mut subpackets: Vec<Packet> = vec![];
-    let len_type = length_type(offset, binary);
-    offset += 1;
-
-    match len_type {
-        LengthType::SubPacketsTotalLength => {
-            let len = subpackets_length(offset, binary);
-            offset += 15;
-            let target_offset = offset + len;
-            while offset < target_offset {
-                let (child_packet, new_offset) = parse_packet(offset, binary);
-                subpackets.push(child_packet);
-                offset = new_offset;
-            }
-        }
-        LengthType::NumSubPackets => {
-            let num_subpackets = subpackets_num(offset, binary);
-            offset += 11;
-            for _ in 0..num_subpackets {
-                let (child_packet, new_offset) = parse_packet(offset, binary);
-                subpackets.push(child_packet);
-                offset = new_offset
-            }
+impl Packet {
+    fn subpackets(&self) -> &[Packet] {
+        match &self.kind {
+            PacketKind::Literal(_) => &[],
+            PacketKind::Operator(_, subpackets) => subpackets,
         }
     }
 
-    (
-        Packet {
-            version,
-            type_id,
-            subpackets,
-            value: 0,
-        },
-        offset,
-    )
-}
-
-fn eval_packet(p: Packet) -> i64 {
-    let children: Vec<i64> = p.subpackets.into_iter().map(eval_packet).collect();
-    match p.type_id {
-        0 => children.into_iter().sum(),
-        1 => children.into_iter().product(),
-        2 => children.into_iter().min().unwrap(),
-        3 => children.into_iter().max().unwrap(),
-        4 => p.value,
-        5 => (children[0] > children[1]).into(),
-        6 => (children[0] < children[1]).into(),
-        7 => (children[0] == children[1]).into(),
-        _ => unreachable!(),
+    fn version_sum(&self) -> u64 {
+        self.version as u64
+            + self
+                .subpackets()
+                .iter()
+                .map(Packet::version_sum)
+                .sum::<u64>()
     }
-}
 
-fn sum_version(p: Packet) -> i64 {
-    p.version as i64 + p.subpackets.into_iter().map(sum_version).sum::<i64>()
-}
+    fn value(&self) -> Result<u64, SolveError> {
+        let (operator, subpackets) = match &self.kind {
+            PacketKind::Literal(value) => return Ok(*value),
+            PacketKind::Operator(operator, subpackets) => (operator, subpackets),
+        };
+        let values = subpackets
+            .iter()
+            .map(Packet::value)
+            .collect::<Result<Vec<_>, _>>()?;
+        let value = match (operator, values.as_slice()) {
+            (Operator::Sum, _) => values.iter().try_fold(0u64, |acc, &v| acc.checked_add(v)),
+            (Operator::Product, _) => values.iter().try_fold(1u64, |acc, &v| acc.checked_mul(v)),
+            (Operator::Min, _) => values.iter().copied().min(),
+            (Operator::Max, _) => values.iter().copied().max(),
+            (Operator::Gt, [left, right]) => Some((left > right).into()),
+            (Operator::Lt, [left, right]) => Some((left < right).into()),
+            (Operator::Eq, [left, right]) => Some((left == right).into()),
+            _ => return Err(SolveError::NoSolution),
+        };
+        value.ok_or(SolveError::Overflow)
+    }
 
-fn version_sum(input: &str) -> i64 {
-    let binary = hex_to_bin(input);
-    let (p, _) = parse_packet(0, &binary);
-    sum_version(p)
-}
+    /// The transmission of the packet, padded with zeros to the last hex digit.
+    /// [`SolveError::Overflow`] when an operator has too many subpackets for either length type.
+    pub fn to_hex(&self) -> Result<String, SolveError> {
+        let mut bits = BitWriter::new();
+        self.encode(&mut bits)?;
+        Ok(bits.to_hex())
+    }
 
-fn eval_packets(input: &str) -> i64 {
-    let binary = hex_to_bin(input);
-    let (p, _) = parse_packet(0, &binary);
-    eval_packet(p)
+    fn encode(&self, bits: &mut BitWriter) -> Result<(), SolveError> {
+        bits.write(self.version as u64, 3);
+        match &self.kind {
+            PacketKind::Literal(value) => {
+                bits.write(LITERAL_TYPE_ID, 3);
+                let groups = (64 - value.leading_zeros() as usize).div_ceil(4).max(1);
+                for group in (0..groups).rev() {
+                    bits.write_bool(group > 0);
+                    bits.write(value >> (group * 4), 4);
+                }
+            }
+            PacketKind::Operator(operator, subpackets) => {
+                bits.write(operator.type_id(), 3);
+                let mut encoded = BitWriter::new();
+                for subpacket in subpackets {
+                    subpacket.encode(&mut encoded)?;
+                }
+                // Length in bits when it fits, else the number of subpackets
+                if encoded.len() < 1 << 15 {
+                    bits.write_bool(false);
+                    bits.write(encoded.len() as u64, 15);
+                } else if subpackets.len() < 1 << 11 {
+                    bits.write_bool(true);
+                    bits.write(subpackets.len() as u64, 11);
+                } else {
+                    return Err(SolveError::Overflow);
+                }
+                bits.append(&encoded);
+            }
+        }
+        Ok(())
+    }
 }
 
-pub fn version(offset: i64, binary: &str) -> u8 {
-    let version_str = &binary[offset as usize..(offset + 3) as usize];
-    u8::from_str_radix(version_str, 2).unwrap()
-}
+impl FromStr for Packet {
+    type Err = SolveError;
 
-pub fn subpackets_length(offset: i64, binary: &str) -> i64 {
-    let length_str = &binary[offset as usize..(offset + 15) as usize];
-    i64::from_str_radix(length_str, 2).unwrap()
+    fn from_str(hex: &str) -> Result<Self, Self::Err> {
+        Day16::parse(hex)
+    }
 }
 
-pub fn subpackets_num(offset: i64, binary: &str) -> i64 {
-    let num_str = &binary[offset as usize..(offset + 11) as usize];
-    i64::from_str_radix(num_str, 2).unwrap()
+/// Reads packets from the bits of `hex`, errors point at the hex digit where the offending
+/// field or packet starts.
+struct Decoder<'a> {
+    input: &'a str,
+    hex: &'a str,
+    bits: BitReader<'a>,
 }
 
-fn hex_to_bin(hex: &str) -> String {
-    hex.chars().map(to_binary).collect()
-}
+impl Decoder<'_> {
+    fn packet(&mut self) -> Result<Packet, SolveError> {
+        let start = self.bits.position();
+        let version = self.read(3)? as u8;
+        let kind = match self.read(3)? {
+            LITERAL_TYPE_ID => PacketKind::Literal(self.literal()?),
+            type_id => {
+                let operator = Operator::from_type_id(type_id).ok_or_else(|| self.error(start))?;
+                let subpackets = self.subpackets(start)?;
+                if !operator.accepts(subpackets.len()) {
+                    return Err(self.error(start));
+                }
+                PacketKind::Operator(operator, subpackets)
+            }
+        };
+        Ok(Packet { version, kind })
+    }
 
-fn to_binary(c: char) -> &'static str {
-    match c {
-        '0' => "0000",
-        '1' => "0001",
-        '2' => "0010",
-        '3' => "0011",
-        '4' => "0100",
-        '5' => "0101",
-        '6' => "0110",
-        '7' => "0111",
-        '8' => "1000",
-        '9' => "1001",
-        'A' => "1010",
-        'B' => "1011",
-        'C' => "1100",
-        'D' => "1101",
-        'E' => "1110",
-        'F' => "1111",
-        _ => "",
+    /// Groups of 4 bits, each preceded by a bit telling whether another group follows.
+    fn literal(&mut self) -> Result<u64, SolveError> {
+        let mut value: u64 = 0;
+        loop {
+            let more = self.read(1)? == 1;
+            if value.leading_zeros() < 4 {
+                return Err(SolveError::Overflow);
+            }
+            value = value << 4 | self.read(4)?;
+            if !more {
+                return Ok(value);
+            }
+        }
     }
-}
 
-fn type_id(offset: i64, binary: &str) -> u8 {
-    let version_str = &binary[offset as usize..(offset + 3) as usize];
-    u8::from_str_radix(version_str, 2).unwrap()
-}
+    /// Subpackets of the operator packet starting at bit `start`, counted either in bits or in
+    /// packets.
+    fn subpackets(&mut self, start: usize) -> Result<Vec<Packet>, SolveError> {
+        let mut subpackets = vec![];
+        if self.read(1)? == 0 {
+            let len = self.read(15)? as usize;
+            let end = self.bits.position() + len;
+            while self.bits.position() < end {
+                subpackets.push(self.packet()?);
+            }
+            // The last subpacket must end exactly at the announced length
+            if self.bits.position() != end {
+                return Err(self.error(start));
+            }
+        } else {
+            let count = self.read(11)?;
+            for _ in 0..count {
+                subpackets.push(self.packet()?);
+            }
+        }
+        Ok(subpackets)
+    }
 
-pub fn packet_type(type_id: u8) -> PacketType {
-    match type_id {
-        4 => PacketType::LiteralValue,
-        _ => PacketType::Operator,
+    fn read(&mut self, bits: usize) -> Result<u64, SolveError> {
+        let position = self.bits.position();
+        self.bits.read(bits).ok_or_else(|| self.error(position))
     }
-}
 
-fn length_type(offset: i64, binary: &str) -> LengthType {
-    match binary.chars().nth(offset as usize).unwrap() {
-        '0' => LengthType::SubPacketsTotalLength,
-        '1' => LengthType::NumSubPackets,
-        _ => unreachable!(),
+    /// Error pointing at the hex digits from the one holding bit `position` on.
+    fn error(&self, position: usize) -> SolveError {
+        let digit = (position / 4).min(self.hex.len());
+        SolveError::at(self.input, &self.hex[digit..])
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 16;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "925";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "342997120375";
-        assert_eq!(result, expected.to_string());
-    }
-
     #[test]
     fn test_version_sum_1() {
         let input = "D2FE28";
-        let sum = version_sum(input);
+        let sum = Day16::parse(input).unwrap().version_sum();
         assert_eq!(sum, 6);
     }
 
     #[test]
     fn test_version_sum_2() {
         let input = "8A004A801A8002F478";
-        let sum = version_sum(input);
+        let sum = Day16::parse(input).unwrap().version_sum();
         assert_eq!(sum, 16);
     }
 
     #[test]
     fn test_version_sum_3() {
         let input = "620080001611562C8802118E34";
-        let sum = version_sum(input);
+        let sum = Day16::parse(input).unwrap().version_sum();
         assert_eq!(sum, 12);
     }
 
     #[test]
     fn test_version_sum_4() {
         let input = "C0015000016115A2E0802F182340";
-        let sum = version_sum(input);
+        let sum = Day16::parse(input).unwrap().version_sum();
         assert_eq!(sum, 23);
     }
 
     #[test]
     fn test_version_sum_5() {
         let input = "A0016C880162017C3686B18A3D4780";
-        let sum = version_sum(input);
+        let sum = Day16::parse(input).unwrap().version_sum();
         assert_eq!(sum, 31);
     }
+
+    #[test]
+    fn test_parse_literal() {
+        // when
+        let packet = Day16::parse("D2FE28").unwrap();
+
+        // then
+        assert_eq!(
+            packet,
+            Packet {
+                version: 6,
+                kind: PacketKind::Literal(2021)
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_operators() {
+        // given
+        let literal = |version, value| Packet {
+            version,
+            kind: PacketKind::Literal(value),
+        };
+
+        // when
+        let by_length = Day16::parse("38006F45291200").unwrap();
+        let by_count = Day16::parse("EE00D40C823060").unwrap();
+
+        // then
+        assert_eq!(
+            by_length,
+            Packet {
+                version: 1,
+                kind: PacketKind::Operator(Operator::Lt, vec![literal(6, 10), literal(2, 20)])
+            }
+        );
+        assert_eq!(
+            by_count,
+            Packet {
+                version: 7,
+                kind: PacketKind::Operator(
+                    Operator::Max,
+                    vec![literal(2, 1), literal(4, 2), literal(1, 3)]
+                )
+            }
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for example in Day16::EXAMPLES.iter().chain([&Example::new("D2FE28")]) {
+            // given
+            let packet = Day16::parse(example.input).unwrap();
+
+            // when
+            let hex = packet.to_hex().unwrap();
+
+            // then
+            assert_eq!(Day16::parse(&hex), Ok(packet), "example {}", example.input);
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // given
+        let three_way_compare = Packet {
+            version: 0,
+            kind: PacketKind::Operator(
+                Operator::Gt,
+                vec![
+                    Packet {
+                        version: 0,
+                        kind: PacketKind::Literal(1),
+                    };
+                    3
+                ],
+            ),
+        };
+
+        // then
+        // The last group of the literal starts at bit 16 but only 4 bits are left
+        assert_eq!(Day16::parse("D2FE2"), Err(SolveError::parse(1, 5, "2")));
+        assert_eq!(Day16::parse("38006F"), Err(SolveError::parse(1, 6, "F")));
+        assert_eq!(
+            Day16::parse(&three_way_compare.to_hex().unwrap()),
+            Err(SolveError::parse(1, 1, "14008440881102"))
+        );
+        assert_eq!(Day16::parse("D2XE28"), Err(SolveError::parse(1, 3, "X")));
+    }
+
+    #[test]
+    fn test_value_overflow() {
+        // given
+        let huge = Packet {
+            version: 0,
+            kind: PacketKind::Literal(u64::MAX),
+        };
+        let product = Packet {
+            version: 0,
+            kind: PacketKind::Operator(Operator::Product, vec![huge.clone(), huge.clone()]),
+        };
+
+        // then
+        assert_eq!(Day16::parse(&huge.to_hex().unwrap()), Ok(huge));
+        assert_eq!(product.value(), Err(SolveError::Overflow));
+    }
+
+    #[test]
+    fn test_to_hex_oversize() {
+        // given
+        let huge = Packet {
+            version: 0,
+            kind: PacketKind::Literal(u64::MAX),
+        };
+        // 2048 literals of 86 bits fit neither 15 bits of length nor 11 bits of count
+        let sum = |count| Packet {
+            version: 0,
+            kind: PacketKind::Operator(Operator::Sum, vec![huge.clone(); count]),
+        };
+
+        // then
+        assert!(sum(2047).to_hex().is_ok());
+        assert_eq!(sum(2048).to_hex(), Err(SolveError::Overflow));
+    }
 }
@@ -2,17 +2,33 @@ use std::cmp::Ordering;
 
 use itertools::Itertools;
 
-pub fn run_first(input: &str) -> String {
-    let trimmed = input.trim();
-    let area = parse_area(trimmed);
-    let res = find_max_vel(&area);
-    res.1.to_string()
-}
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Area;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new("target area: x=20..30, y=-10..-5")
+        .first("45")
+        .second("112")];
 
-pub fn run_second(input: &str) -> String {
-    let trimmed = input.trim();
-    let area = parse_area(trimmed);
-    count_reachable(&area).to_string()
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_area(input.trim())
+    }
+
+    fn part_one(area: &Self::Input) -> Result<String, SolveError> {
+        let res = find_max_vel(area);
+        Ok(res.1.to_string())
+    }
+
+    fn part_two(area: &Self::Input) -> Result<String, SolveError> {
+        Ok(count_reachable(area).to_string())
+    }
 }
 
 pub struct Position {
@@ -129,61 +145,34 @@ fn in_range(num: i32, from: i32, to: i32) -> bool {
     num >= from && num <= to
 }
 
-fn parse_area(input: &str) -> Area {
-    let stripped: Vec<_> = input[13..].split(", ").collect();
-    let x_tokens: Vec<i32> = stripped[0]
-        .replace("x=", "")
-        .split("..")
-        .map(|t| t.parse().unwrap())
-        .collect();
-
-    let y_tokens: Vec<i32> = stripped[1]
-        .replace("y=", "")
-        .split("..")
-        .map(|t| t.parse().unwrap())
-        .collect();
-
-    Area {
-        x_start: x_tokens[0],
-        x_end: x_tokens[1],
-        y_start: y_tokens[1],
-        y_end: y_tokens[0],
-    }
+fn parse_area(input: &str) -> Result<Area, SolveError> {
+    let (x_range, y_range) = input
+        .strip_prefix("target area: ")
+        .and_then(|ranges| ranges.split_once(", "))
+        .ok_or(SolveError::at(input, input))?;
+
+    let (x_start, x_end) = parse_range(input, x_range, "x=")?;
+    let (y_end, y_start) = parse_range(input, y_range, "y=")?;
+
+    Ok(Area {
+        x_start,
+        x_end,
+        y_start,
+        y_end,
+    })
+}
+
+fn parse_range(input: &str, range: &str, prefix: &str) -> Result<(i32, i32), SolveError> {
+    let (from, to) = range
+        .strip_prefix(prefix)
+        .and_then(|range| range.split_once(".."))
+        .ok_or(SolveError::at(input, range))?;
+    Ok((parse_value(input, from)?, parse_value(input, to)?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::utils::read_to_string;
-
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 17;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "5671";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "4556";
-        assert_eq!(result, expected.to_string());
-    }
 
     #[test]
     fn test_max_y() {
@@ -1,527 +1,388 @@
-use itertools::Itertools;
-
-use crate::common::parse::to_non_empty_lines;
-
-pub fn run_first(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    part_1(&lines)
-}
-
-pub fn run_second(input: &str) -> String {
-    let lines = to_non_empty_lines(input);
-    part_2(&lines)
-}
+use std::fmt;
+use std::iter::Sum;
+use std::ops::Add;
+use std::str::FromStr;
+
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day18;
+
+const EXAMPLE: &str = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
+[[[5,[2,8]],4],[5,[[9,9],0]]]
+[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
+[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]
+[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]
+[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]
+[[[[5,4],[7,7]],8],[[8,3],8]]
+[[9,3],[[9,9],[6,[4,9]]]]
+[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
+[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]";
+
+impl Solution for Day18 {
+    type Input = Vec<SnailfishNumber>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("4140").second("3993")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_value(input, line))
+            .collect()
+    }
+
+    fn part_one(numbers: &Self::Input) -> Result<String, SolveError> {
+        if numbers.is_empty() {
+            return Err(SolveError::NoSolution);
+        }
+        Ok(numbers
+            .iter()
+            .sum::<SnailfishNumber>()
+            .magnitude()
+            .to_string())
+    }
 
-fn part_1(input: &[String]) -> String {
-    let final_sum = add_all(input);
-    magnitude(&final_sum).to_string()
+    fn part_two(numbers: &Self::Input) -> Result<String, SolveError> {
+        max_magnitude(numbers)
+            .map(|max| max.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 }
 
-fn part_2(input: &[String]) -> String {
-    input
+/// Largest magnitude of the sum of two different numbers, in either order.
+fn max_magnitude(numbers: &[SnailfishNumber]) -> Option<u32> {
+    numbers
         .iter()
-        .permutations(2)
-        .map(|p| {
-            let left = p[0].to_string();
-            let right = p[1].to_string();
-            magnitude(&add_and_process(&left, &right))
+        .enumerate()
+        .flat_map(|(i, left)| {
+            numbers
+                .iter()
+                .enumerate()
+                .filter(move |&(j, _)| i != j)
+                .map(move |(_, right)| (left.clone() + right.clone()).magnitude())
         })
         .max()
-        .unwrap()
-        .to_string()
-}
-
-fn pair_values(num: &str, index: usize) -> (i32, i32) {
-    let pair_str: String = num
-        .chars()
-        .skip(index + 1)
-        .take_while(|&c| c != ']')
-        .collect();
-
-    let values: Vec<_> = pair_str
-        .split(',')
-        .map(|p| p.parse::<i32>().unwrap())
-        .collect();
-
-    (values[0], values[1])
-}
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub struct PairInfo {
-    left: i32,
-    right: i32,
-    index: usize,
-}
-
-impl PairInfo {
-    fn str_rep(&self) -> String {
-        format!("[{},{}]", self.left, self.right)
-    }
 }
 
-fn leftmost_pair(num: &str, level: usize) -> Option<PairInfo> {
-    leftmost_lvl_index(num, level).map(|index| {
-        let (left, right) = pair_values(num, index);
-        PairInfo { left, right, index }
-    })
+/// Pairs explode once they are nested inside this many pairs.
+const MAX_DEPTH: u8 = 4;
+
+/// A snailfish number, stored as its regular numbers from left to right along with the number
+/// of pairs around each of them.
+///
+/// Both numbers of a pair that holds no other pair are next to each other at the same depth,
+/// which is all reducing needs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnailfishNumber {
+    values: Vec<(u32, u8)>,
 }
 
-fn can_explode(num: &str) -> bool {
-    leftmost_pair(num, 4).is_some()
-}
-
-fn explode(num: &str) -> String {
-    match leftmost_pair(num, 4) {
-        None => num.to_string(),
-        Some(pair) => {
-            let start = pair.index;
-            let end = start + num.chars().skip(start).take_while(|&c| c != ']').count();
-            let can_right = can_explode_right(num, end);
-            let can_left = can_explode_left(num, start);
-
-            if can_right && can_left {
-                let (left_part, after_left) = left_part(num, pair, start);
-                let (right_part, after_right) = right_part(num, pair, end);
-                let to_replace = format!("{left_part}[{},{}]{right_part}", pair.left, pair.right);
-                let replace_with = format!("{after_left}0{after_right}");
-
-                return num.replacen(&to_replace, &replace_with, 1);
-            }
-
-            if can_right {
-                explode_right(num, pair, end)
-            } else {
-                explode_left(num, pair, start)
+impl SnailfishNumber {
+    /// Explodes and splits until neither applies.
+    pub fn reduce(&mut self) {
+        while self.explode() || self.split() {}
+    }
+
+    pub fn magnitude(&self) -> u32 {
+        let mut stack: Vec<(u32, u8)> = Vec::new();
+        for &value in &self.values {
+            stack.push(value);
+            // Both halves of a pair are complete once they sit at the same depth
+            while let [.., (left, depth), (right, right_depth)] = stack[..] {
+                if depth != right_depth {
+                    break;
+                }
+                stack.truncate(stack.len() - 2);
+                stack.push((3 * left + 2 * right, depth.saturating_sub(1)));
             }
         }
-    }
-}
-
-fn left_part(num: &str, pair: PairInfo, start: usize) -> (String, String) {
-    let part = num
-        .chars()
-        .rev()
-        .skip(num.len() - start)
-        .take_while(|c| !c.is_ascii_digit())
-        .collect::<String>();
-
-    let num_str = num
-        .chars()
-        .rev()
-        .skip(num.len() - start + part.len())
-        .take_while(|c| c.is_ascii_digit())
-        .collect::<String>();
-
-    let rev_part: String = part.chars().rev().collect();
-    let rev_num_str: String = num_str.chars().rev().collect();
-
-    let left_num = rev_num_str.parse::<i32>().unwrap();
-    let new_num = left_num + pair.left;
-
-    // first new number, then old part
-    let before = format!("{rev_num_str}{rev_part}");
-    let after = format!("{new_num}{rev_part}");
-
-    (before, after)
-}
-
-fn right_part(num: &str, pair: PairInfo, end: usize) -> (String, String) {
-    let part = num
-        .chars()
-        .skip(end)
-        .take_while(|c| !c.is_ascii_digit())
-        .collect::<String>();
-    let num_str = num
-        .chars()
-        .skip(end + part.len())
-        .take_while(|c| c.is_ascii_digit())
-        .collect::<String>();
-    let num: i32 = num_str.parse().unwrap();
-    let after = format!("{}{}", &part[1..], num + pair.right);
-    (format!("{}{}", &part[1..], num_str), after)
-}
-
-fn split(num: &str) -> String {
-    split_num(num, find_split(num).unwrap())
-}
-
-fn split_num(num: &str, split: i32) -> String {
-    let half = (split as f64) / 2.0;
-    let left = half.floor() as i32;
-    let right = half.ceil() as i32;
-    let to_replace = format!("{split}");
-    let replace_with = format!("[{left},{right}]");
-    num.replacen(&to_replace, &replace_with, 1)
-}
-
-fn add(left: &str, right: &str) -> String {
-    format!("[{left},{right}]")
-}
-
-fn add_all(pairs: &[String]) -> String {
-    let mut curr = pairs.iter().next().unwrap().clone();
-    for pair in pairs.iter().skip(1) {
-        curr = add_and_process(&curr, pair)
-    }
-    curr
-}
-
-fn add_and_process(left: &str, right: &str) -> String {
-    process(&add(left, right))
-}
-
-fn process(num: &str) -> String {
-    let mut curr_num = num.to_string();
-    loop {
-        if can_explode(&curr_num) {
-            curr_num = explode(&curr_num);
-            continue;
+        stack.first().map_or(0, |&(magnitude, _)| magnitude)
+    }
+
+    /// Explodes the leftmost pair nested too deep, `false` when there is none.
+    fn explode(&mut self) -> bool {
+        let Some(idx) = self
+            .values
+            .windows(2)
+            .position(|pair| pair[0].1 > MAX_DEPTH && pair[0].1 == pair[1].1)
+        else {
+            return false;
+        };
+        let (left, depth) = self.values[idx];
+        let (right, _) = self.values[idx + 1];
+        if let Some(previous) = idx.checked_sub(1) {
+            self.values[previous].0 += left;
         }
-
-        if can_split(&curr_num) {
-            curr_num = split(&curr_num);
-            continue;
+        if let Some(next) = self.values.get_mut(idx + 2) {
+            next.0 += right;
+        }
+        self.values.splice(idx..idx + 2, [(0, depth - 1)]);
+        true
+    }
+
+    /// Splits the leftmost number of 10 or more, `false` when there is none.
+    fn split(&mut self) -> bool {
+        let Some(idx) = self.values.iter().position(|&(value, _)| value >= 10) else {
+            return false;
+        };
+        let (value, depth) = self.values[idx];
+        self.values.splice(
+            idx..idx + 1,
+            [(value / 2, depth + 1), (value - value / 2, depth + 1)],
+        );
+        true
+    }
+
+    /// Writes the part of the number starting at `values[*idx]` at `depth`.
+    fn write_from(&self, f: &mut fmt::Formatter<'_>, idx: &mut usize, depth: u8) -> fmt::Result {
+        let Some(&(value, value_depth)) = self.values.get(*idx) else {
+            return Err(fmt::Error);
+        };
+        if value_depth == depth {
+            *idx += 1;
+            return write!(f, "{value}");
         }
-        break;
+        write!(f, "[")?;
+        self.write_from(f, idx, depth + 1)?;
+        write!(f, ",")?;
+        self.write_from(f, idx, depth + 1)?;
+        write!(f, "]")
     }
-    curr_num
 }
 
-fn can_split(num: &str) -> bool {
-    find_split(num).is_some()
-}
+impl FromStr for SnailfishNumber {
+    type Err = SolveError;
 
-fn find_split(num: &str) -> Option<i32> {
-    let mut digits = "".to_string();
-    for c in num.chars() {
-        if c.is_ascii_digit() {
-            digits.push_str(&c.to_string())
-        } else {
-            digits = "".to_string();
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match validate_number(text.as_bytes()) {
+            Some([]) => {}
+            Some(rest) => return Err(SolveError::at(text, &text[text.len() - rest.len()..])),
+            None => return Err(SolveError::at(text, text)),
         }
 
-        if digits.len() > 1 {
-            return Some(digits.parse().unwrap());
+        let mut values = Vec::new();
+        let mut depth = 0;
+        for token in text.split_inclusive(['[', ',', ']']) {
+            let digits = token.trim_end_matches(['[', ',', ']']);
+            if !digits.is_empty() {
+                let value = digits.parse().map_err(|_| SolveError::at(text, digits))?;
+                values.push((value, depth));
+            }
+            match token.chars().last() {
+                Some('[') => depth += 1,
+                Some(']') => depth -= 1,
+                _ => {}
+            }
         }
+        Ok(SnailfishNumber { values })
     }
-
-    None
-}
-
-fn can_explode_right(num: &str, pair_end: usize) -> bool {
-    num.chars().skip(pair_end).any(|c| c != ']')
-}
-
-fn can_explode_left(num: &str, pair_start: usize) -> bool {
-    num.chars()
-        .rev()
-        .skip(num.len() - (pair_start - 1))
-        .any(|c| c != '[')
-}
-
-fn explode_left(num: &str, pair: PairInfo, start: usize) -> String {
-    let left_num_str = num
-        .chars()
-        .rev()
-        .skip(num.len() - (start - 1))
-        .take_while(|&c| c != '[')
-        .collect::<String>();
-
-    let rev = left_num_str.chars().rev().collect::<String>();
-    let left_num = rev.parse::<i32>().unwrap();
-
-    let new_left = left_num + pair.left;
-    let to_replace = format!("[{left_num},[{},{}]]", pair.left, pair.right);
-    let replace_with = format!("[{new_left},0]");
-    num.replacen(&to_replace, &replace_with, 1)
-}
-
-fn explode_right(num: &str, pair: PairInfo, end: usize) -> String {
-    let (before, after) = right_part(num, pair, end);
-    let to_replace = format!("{}{}", pair.str_rep(), before);
-    let replace_with = format!("0{after}");
-    num.replacen(&to_replace, &replace_with, 1)
 }
 
-fn leftmost_lvl_index(num: &str, level: usize) -> Option<usize> {
-    let mut open_count = 0;
-    for (idx, c) in num.chars().enumerate() {
-        match c {
-            '[' => open_count += 1,
-            ']' => open_count -= 1,
-            _ => {}
-        }
-        if open_count == level + 1 {
-            return Some(idx);
+/// Checks a single `[left,right]` pair or regular number of any number of digits, returning the
+/// unparsed rest.
+fn validate_number(num: &[u8]) -> Option<&[u8]> {
+    match num {
+        [b'[', rest @ ..] => match validate_number(rest)? {
+            [b',', rest @ ..] => match validate_number(rest)? {
+                [b']', rest @ ..] => Some(rest),
+                _ => None,
+            },
+            _ => None,
+        },
+        [b'0'..=b'9', ..] => {
+            let digits = num.iter().take_while(|c| c.is_ascii_digit()).count();
+            Some(&num[digits..])
         }
+        _ => None,
     }
-
-    None
 }
 
-fn find_pivot_index(num: &str) -> usize {
-    let mut open_count = num.chars().take_while(|&c| c == '[').count();
-
-    if open_count == 0 {
-        return 0;
+impl fmt::Display for SnailfishNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.values.is_empty() {
+            return Ok(());
+        }
+        self.write_from(f, &mut 0, 0)
     }
+}
 
-    let mut close_count = 0;
+/// Pairs both numbers and reduces the result, an empty number is left out.
+impl Add for SnailfishNumber {
+    type Output = SnailfishNumber;
 
-    for (idx, c) in num.chars().enumerate().skip(open_count) {
-        if close_count == open_count - 1 && c == ',' {
-            return idx;
+    fn add(mut self, other: SnailfishNumber) -> SnailfishNumber {
+        if self.values.is_empty() {
+            return other;
         }
-
-        if idx != 0 {
-            match c {
-                '[' => open_count += 1,
-                ']' => close_count += 1,
-                _ => {}
-            }
+        if other.values.is_empty() {
+            return self;
+        }
+        self.values.extend(other.values);
+        for (_, depth) in &mut self.values {
+            *depth += 1;
         }
+        self.reduce();
+        self
     }
-
-    0
 }
 
-fn magnitude(num: &str) -> i32 {
-    if num.is_empty() {
-        return 0;
+/// Adds the numbers in order, the sum of no numbers is empty.
+impl Sum for SnailfishNumber {
+    fn sum<I: Iterator<Item = SnailfishNumber>>(iter: I) -> Self {
+        iter.fold(SnailfishNumber::default(), Add::add)
     }
-    let pivot_index = find_pivot_index(num);
-    let mut stripped = "";
+}
 
-    if pivot_index != 0 {
-        stripped = &num[1..num.len() - 1];
+impl<'a> Sum<&'a SnailfishNumber> for SnailfishNumber {
+    fn sum<I: Iterator<Item = &'a SnailfishNumber>>(iter: I) -> Self {
+        iter.cloned().sum()
     }
-
-    let (left_str, mut right_str) = stripped.split_at(pivot_index - 1);
-
-    right_str = &right_str[1..];
-    let left = left_str.parse::<i32>();
-    let right = right_str.parse::<i32>();
-
-    let left_res = match left {
-        Ok(res) => 3 * res,
-        Err(_) => 3 * magnitude(left_str),
-    };
-
-    let right_res = match right {
-        Ok(res) => 2 * res,
-        Err(_) => 2 * magnitude(right_str),
-    };
-
-    left_res + right_res
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::common::parse::test_utils::vec_of_strings;
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2021;
-    const DAY: u8 = 18;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "4132";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "4685";
-        assert_eq!(result, expected.to_string());
+    fn number(text: &str) -> SnailfishNumber {
+        text.parse().unwrap()
     }
 
-    #[test]
-    fn test_lvl_index() {
-        let input = "[[[[[9,8],1],2],3],4]";
-        let idx = leftmost_lvl_index(input, 4).unwrap();
-        pair_values(input, idx);
-        assert_eq!(idx, 4)
+    fn exploded(text: &str) -> String {
+        let mut number = number(text);
+        number.explode();
+        number.to_string()
     }
 
-    #[test]
-    fn test_pair_values_1() {
-        let input = "[[[[[9,8],1],2],3],4]";
-        let values = pair_values(input, 4);
-        assert_eq!((9, 8), values)
+    fn sum(texts: &[&str]) -> String {
+        texts
+            .iter()
+            .map(|text| number(text))
+            .sum::<SnailfishNumber>()
+            .to_string()
     }
 
     #[test]
-    fn test_leftmost_level_1() {
-        let input = "[[[[[9,8],1],2],3],4]";
-        let values = leftmost_pair(input, 4).unwrap();
-        assert_eq!(
-            PairInfo {
-                left: 9,
-                right: 8,
-                index: 4,
-            },
-            values
-        )
-    }
+    fn test_parse_display() {
+        // given
+        let text = "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]";
 
-    #[test]
-    fn test_leftmost_level_2() {
-        let input = "[7,[6,[5,[4,[3,2]]]]]";
-        let values = leftmost_pair(input, 4).unwrap();
+        // then
+        assert_eq!(number(text).to_string(), text);
+        assert_eq!(number("[5,8]").values, vec![(5, 1), (8, 1)]);
+        assert_eq!(number("[[15,0],3]").to_string(), "[[15,0],3]");
         assert_eq!(
-            PairInfo {
-                left: 3,
-                right: 2,
-                index: 12,
-            },
-            values
-        )
-    }
-
-    #[test]
-    fn test_leftmost_level_3() {
-        let input = "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]";
-        let values = leftmost_pair(input, 4).unwrap();
-        assert_eq!(
-            PairInfo {
-                left: 7,
-                right: 3,
-                index: 10,
-            },
-            values
-        )
-    }
-
-    #[test]
-    fn test_can_explode_right() {
-        assert_eq!(can_explode_right("[[6,[5,[4,[3,2]]]],1]", 14), true);
-        assert_eq!(can_explode_right("[7,[6,[5,[4,[3,2]]]]]", 16), false);
-    }
-
-    #[test]
-    fn test_can_explode_left() {
-        assert_eq!(can_explode_left("[[[[[9,8],1],2],3],4]", 4), false);
-        assert_eq!(can_explode_left("[[6,[5,[4,[3,2]]]],1]", 11), true);
+            "[1,2]]".parse::<SnailfishNumber>(),
+            Err(SolveError::parse(1, 6, "]"))
+        );
+        assert!("[1,x]".parse::<SnailfishNumber>().is_err());
     }
 
     #[test]
     fn test_explode_pair_right() {
-        let input = "[[[[[9,8],1],2],3],4]";
-        let exploded = explode(input);
-        assert_eq!(exploded, "[[[[0,9],2],3],4]")
+        assert_eq!(exploded("[[[[[9,8],1],2],3],4]"), "[[[[0,9],2],3],4]")
     }
 
     #[test]
     fn test_explode_pair_right_2() {
-        let input = "[[6,[5,[4,[3,2]]]],1]";
-        let exploded = explode(input);
-        assert_eq!(exploded, "[[6,[5,[7,0]]],3]")
+        assert_eq!(exploded("[[6,[5,[4,[3,2]]]],1]"), "[[6,[5,[7,0]]],3]")
     }
 
     #[test]
     fn test_explode_pair_left() {
-        let input = "[7,[6,[5,[4,[3,2]]]]]";
-        let exploded = explode(input);
-        assert_eq!(exploded, "[7,[6,[5,[7,0]]]]")
+        assert_eq!(exploded("[7,[6,[5,[4,[3,2]]]]]"), "[7,[6,[5,[7,0]]]]")
     }
 
     #[test]
     fn test_explode_multiple_lvl_4() {
-        let input = "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]";
-        let exploded = explode(input);
-        assert_eq!(exploded, "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]")
+        assert_eq!(
+            exploded("[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]"),
+            "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"
+        )
     }
 
     #[test]
     fn test_explode_multiple_2_explodes() {
-        let input = "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]";
-        let exploded = explode(input);
-        assert_eq!(exploded, "[[3,[2,[8,0]]],[9,[5,[7,0]]]]")
+        assert_eq!(
+            exploded("[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]"),
+            "[[3,[2,[8,0]]],[9,[5,[7,0]]]]"
+        )
     }
 
     #[test]
     fn test_explode_2_deep_4_me() {
-        let input = "[[[[0,7],4],[7,[[8,4],9]]],[1,1]]";
-        let exploded = explode(input);
-        assert_eq!(exploded, "[[[[0,7],4],[15,[0,13]]],[1,1]]")
+        assert_eq!(
+            exploded("[[[[0,7],4],[7,[[8,4],9]]],[1,1]]"),
+            "[[[[0,7],4],[15,[0,13]]],[1,1]]"
+        )
     }
 
     #[test]
     fn test_explode_addition() {
-        let input = "[[[[[1,1],[2,2]],[3,3]],[4,4]],[5,5]]";
-        let exploded = explode(input);
-        assert_eq!(exploded, "[[[[0,[3,2]],[3,3]],[4,4]],[5,5]]")
-    }
-
-    #[test]
-    fn test_find_split() {
-        let input = "[[3,[2,[10,11]]],[9,[5,[4,[3,2]]]]]";
-        let exploded = find_split(input).unwrap();
-        assert_eq!(exploded, 10)
+        assert_eq!(
+            exploded("[[[[[1,1],[2,2]],[3,3]],[4,4]],[5,5]]"),
+            "[[[[0,[3,2]],[3,3]],[4,4]],[5,5]]"
+        )
     }
 
     #[test]
     fn test_split() {
-        let input = "[11,0]";
-        let exploded = split_num(input, 11);
-        assert_eq!(exploded, "[[5,6],0]")
-    }
+        // given
+        let mut number = SnailfishNumber {
+            values: vec![(11, 1), (0, 1)],
+        };
 
-    #[test]
-    fn test_can_split() {
-        let input = "[[[[0,7],4],[15,[0,13]]],[1,1]]";
-        assert_eq!(can_split(input), true);
-    }
+        // when
+        let split = number.split();
 
-    #[test]
-    fn test_process() {
-        let input = "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]";
-        assert_eq!(process(input), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+        // then
+        assert!(split);
+        assert_eq!(number.to_string(), "[[5,6],0]");
+        assert!(!number.split());
     }
 
     #[test]
-    fn test_end_pr() {
-        let input = "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]";
-        assert_eq!(process(input), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+    fn test_reduce() {
+        // given
+        let mut number = number("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
+
+        // when
+        number.reduce();
+
+        // then
+        assert_eq!(number.to_string(), "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
     }
 
     #[test]
     fn test_add_all_1() {
-        let input = vec_of_strings!["[1,1]", "[2,2]", "[3,3]", "[4,4]"];
-        assert_eq!(add_all(&input), "[[[[1,1],[2,2]],[3,3]],[4,4]]")
+        assert_eq!(
+            sum(&["[1,1]", "[2,2]", "[3,3]", "[4,4]"]),
+            "[[[[1,1],[2,2]],[3,3]],[4,4]]"
+        )
     }
 
     #[test]
     fn test_add_all_2() {
-        let input = vec_of_strings!["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]"];
-        assert_eq!(add_all(&input), "[[[[3,0],[5,3]],[4,4]],[5,5]]")
+        assert_eq!(
+            sum(&["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]"]),
+            "[[[[3,0],[5,3]],[4,4]],[5,5]]"
+        )
     }
 
     #[test]
     fn test_add_all_3() {
-        let input = vec_of_strings!["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"];
-        assert_eq!(add_all(&input), "[[[[5,0],[7,4]],[5,5]],[6,6]]")
+        assert_eq!(
+            sum(&["[1,1]", "[2,2]", "[3,3]", "[4,4]", "[5,5]", "[6,6]"]),
+            "[[[[5,0],[7,4]],[5,5]],[6,6]]"
+        )
     }
 
     #[test]
     fn test_add_all_4() {
-        let input = vec_of_strings![
+        let input = [
             "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]",
             "[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]",
             "[[2,[[0,8],[3,4]]],[[[6,7],1],[7,[1,6]]]]",
@@ -531,67 +392,40 @@ mod tests {
             "[2,9]",
             "[1,[[[9,3],9],[[9,0],[0,7]]]]",
             "[[[5,[7,4]],7],1]",
-            "[[[[4,2],2],6],[8,7]]"
+            "[[[[4,2],2],6],[8,7]]",
         ];
         assert_eq!(
-            add_all(&input),
+            sum(&input),
             "[[[[8,7],[7,7]],[[8,6],[7,7]]],[[[0,7],[6,6]],[8,7]]]"
         )
     }
 
     #[test]
     fn test_add_all_5() {
-        let input = vec_of_strings![
-            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]",
-            "[[[5,[2,8]],4],[5,[[9,9],0]]]",
-            "[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]",
-            "[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]",
-            "[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]",
-            "[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]",
-            "[[[[5,4],[7,7]],8],[[8,3],8]]",
-            "[[9,3],[[9,9],[6,[4,9]]]]",
-            "[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]",
-            "[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"
-        ];
+        let input = Day18::parse(EXAMPLE).unwrap();
         assert_eq!(
-            add_all(&input),
+            input.iter().sum::<SnailfishNumber>().to_string(),
             "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
         )
     }
 
     #[test]
     fn test_magnitude() {
-        let res = magnitude("[5,8]");
-        assert_eq!(res, 31);
+        assert_eq!(number("[5,8]").magnitude(), 31);
+        assert_eq!(number("[[9,1],[1,9]]").magnitude(), 129);
+        assert_eq!(number("[[1,2],[[3,4],5]]").magnitude(), 143);
+        assert_eq!(SnailfishNumber::default().magnitude(), 0);
     }
 
     #[test]
-    fn test_magnitude_2() {
-        let res = magnitude("[[9,1],[1,9]]");
-        assert_eq!(res, 129);
-    }
+    fn test_max_magnitude() {
+        // given
+        let input = Day18::parse(EXAMPLE).unwrap();
 
-    #[test]
-    fn test_magnitude_3() {
-        let res = magnitude("[[1,2],[[3,4],5]]");
-        assert_eq!(res, 143);
-    }
+        // when
+        let max = max_magnitude(&input);
 
-    #[test]
-    fn test_max_magnitude() {
-        let input = vec_of_strings![
-            "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]",
-            "[[[5,[2,8]],4],[5,[[9,9],0]]]",
-            "[6,[[[6,2],[5,6]],[[7,6],[4,7]]]]",
-            "[[[6,[0,7]],[0,9]],[4,[9,[9,0]]]]",
-            "[[[7,[6,4]],[3,[1,3]]],[[[5,5],1],9]]",
-            "[[6,[[7,3],[3,2]]],[[[3,8],[5,7]],4]]",
-            "[[[[5,4],[7,7]],8],[[8,3],8]]",
-            "[[9,3],[[9,9],[6,[4,9]]]]",
-            "[[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]",
-            "[[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]"
-        ];
-        let res = part_2(&input);
-        assert_eq!(res, "3993".to_string());
+        // then
+        assert_eq!(max, Some(3993));
     }
 }
@@ -1,34 +1,58 @@
 use itertools::Itertools;
-pub fn run_first(input: &str) -> String {
-    let as_lines: Vec<String> = input.split('\n').map(|s| s.to_string()).collect();
 
-    top_n_sum(&as_lines, 1).to_string()
-}
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-pub fn run_second(input: &str) -> String {
-    let as_lines: Vec<String> = input.split('\n').map(|s| s.to_string()).collect();
+pub struct Day01;
 
-    top_n_sum(&as_lines, 3).to_string()
-}
+const EXAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+impl Solution for Day01 {
+    type Input = Vec<i64>;
+    type Params = ();
 
-pub fn get_calories(input: &[String]) -> Vec<i64> {
-    let mut res: Vec<i64> = vec![];
-    let mut curr = 0;
-    for line in input {
-        if line.is_empty() {
-            res.push(curr);
-            curr = 0;
-        } else {
-            curr += line.parse::<i64>().unwrap();
-        }
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("24000").second("45000")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        get_calories(input)
+    }
+
+    fn part_one(calories: &Self::Input) -> Result<String, SolveError> {
+        Ok(top_n_sum(calories, 1).to_string())
     }
-    res
+
+    fn part_two(calories: &Self::Input) -> Result<String, SolveError> {
+        Ok(top_n_sum(calories, 3).to_string())
+    }
+}
+
+pub fn get_calories(input: &str) -> Result<Vec<i64>, SolveError> {
+    input
+        .split("\n\n")
+        .map(|elf| {
+            elf.split('\n')
+                .filter(|line| !line.is_empty())
+                .map(|line| parse_value::<i64>(input, line))
+                .sum()
+        })
+        .collect()
 }
 
-pub fn top_n_sum(input: &[String], n: usize) -> i64 {
-    get_calories(input)
-        .iter()
-        .sorted_by(|a, b| b.cmp(a))
-        .take(n)
-        .sum()
+pub fn top_n_sum(calories: &[i64], n: usize) -> i64 {
+    calories.iter().sorted_by(|a, b| b.cmp(a)).take(n).sum()
 }
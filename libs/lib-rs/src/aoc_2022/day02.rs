@@ -1,118 +1,69 @@
-pub fn run_first(input: &str) -> String {
-    input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let tokens: Vec<&str> = line.split(' ').collect();
-            let opponent_move = &tokens[0];
-            let your_move = &tokens[1];
-
-            get_move_value(your_move) + get_result(opponent_move, your_move)
-        })
-        .sum::<i64>()
-        .to_string()
-}
-
-pub fn get_move_value(m: &str) -> i64 {
-    match m {
-        "A" => 1,
-        "B" => 2,
-        "C" => 3,
-        "X" => 1,
-        "Y" => 2,
-        "Z" => 3,
-        _ => panic!("Invalid"),
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day02;
+
+const EXAMPLE: &str = "A Y
+B X
+C Z";
+
+impl Solution for Day02 {
+    /// Pairs of (opponent, you), with rock, paper and scissors mapped to 0, 1 and 2.
+    type Input = Vec<(i64, i64)>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("15").second("12")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| match line.as_bytes() {
+                [opponent @ b'A'..=b'C', b' ', you @ b'X'..=b'Z'] => {
+                    Ok(((opponent - b'A') as i64, (you - b'X') as i64))
+                }
+                _ => Err(SolveError::at(input, line)),
+            })
+            .collect()
     }
-}
-
-pub fn get_result(opponent_move: &str, your_move: &str) -> i64 {
-    match (opponent_move, your_move) {
-        ("A", "X") => 3,
-        ("A", "Y") => 6,
-        ("A", "Z") => 0,
-        ("B", "X") => 0,
-        ("B", "Y") => 3,
-        ("B", "Z") => 6,
-        ("C", "X") => 6,
-        ("C", "Y") => 0,
-        ("C", "Z") => 3,
 
-        (_, _) => panic!("Invalid input {opponent_move} {your_move}"),
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input
+            .iter()
+            .map(|&(opponent_move, your_move)| {
+                get_move_value(your_move) + get_result(opponent_move, your_move)
+            })
+            .sum::<i64>()
+            .to_string())
     }
-}
-
-pub fn get_move(opponent: &str, strategy: &str) -> String {
-    match (opponent, strategy) {
-        ("A", "X") => "Z".to_string(),
-        ("A", "Y") => "X".to_string(),
-        ("A", "Z") => "Y".to_string(),
-        ("B", "X") => "X".to_string(),
-        ("B", "Y") => "Y".to_string(),
-        ("B", "Z") => "Z".to_string(),
-        ("C", "X") => "Y".to_string(),
-        ("C", "Y") => "Z".to_string(),
-        ("C", "Z") => "X".to_string(),
 
-        (_, _) => panic!("Invalid input {opponent} {strategy}"),
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input
+            .iter()
+            .map(|&(opponent_move, outcome)| {
+                let your_move = get_move(opponent_move, outcome);
+                get_res_value(outcome) + get_move_value(your_move)
+            })
+            .sum::<i64>()
+            .to_string())
     }
 }
 
-pub fn get_res_value(res: &str) -> i64 {
-    match res {
-        "X" => 0,
-        "Y" => 3,
-        "Z" => 6,
-        _ => panic!("Invalid"),
-    }
+pub fn get_move_value(m: i64) -> i64 {
+    m + 1
 }
 
-pub fn run_second(input: &str) -> String {
-    input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let tokens: Vec<&str> = line.split(' ').collect();
-            let opponent_move = &tokens[0];
-            let strategy = &tokens[1];
-            let your_move = get_move(opponent_move, strategy);
-
-            get_res_value(strategy) + get_move_value(&your_move)
-        })
-        .sum::<i64>()
-        .to_string()
+pub fn get_result(opponent_move: i64, your_move: i64) -> i64 {
+    let outcome = (your_move - opponent_move + 4) % 3;
+    get_res_value(outcome)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::utils::read_to_string;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 2;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "13682";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
+/// Move giving the `outcome` (0 for a loss, 1 for a draw, 2 for a win).
+pub fn get_move(opponent_move: i64, outcome: i64) -> i64 {
+    (opponent_move + outcome + 2) % 3
+}
 
-        // then
-        let expected = "12881";
-        assert_eq!(result, expected.to_string());
-    }
+pub fn get_res_value(outcome: i64) -> i64 {
+    outcome * 3
 }
@@ -2,85 +2,84 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
-pub fn run_first(input: &str) -> String {
-    input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .map(|line| {
-            let half = line.len() / 2;
-            let first: HashSet<_> = line[..half].to_string().chars().collect();
-            let second: HashSet<_> = line[half..].to_string().chars().collect();
-            first
-                .intersection(&second)
-                .map(|&c| get_char_value(c))
-                .sum::<i64>()
-        })
-        .sum::<i64>()
-        .to_string()
-}
-
-pub fn run_second(input: &str) -> String {
-    input
-        .split('\n')
-        .filter(|line| !line.is_empty())
-        .chunks(3)
-        .into_iter()
-        .map(|mut chunk| {
-            let first: HashSet<_> = chunk.next().unwrap().chars().collect();
-            let second: HashSet<_> = chunk.next().unwrap().chars().collect();
-            let third: HashSet<_> = chunk.next().unwrap().chars().collect();
-
-            first
-                .iter()
-                .filter(|c| second.contains(c))
-                .filter(|c| third.contains(c))
-                .map(|&c| get_char_value(c))
-                .sum::<i64>()
-        })
-        .sum::<i64>()
-        .to_string()
-}
-
-fn get_char_value(c: char) -> i64 {
-    match c {
-        'a'..='z' => c as i64 - 96,
-        'A'..='Z' => c as i64 - 65 + 27,
-        _ => unreachable!("Unexpected char: {}", c),
-    }
-}
+use crate::common::parse::find_invalid;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
+pub struct Day03;
 
-    use super::*;
+const EXAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw";
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 3;
+impl Solution for Day03 {
+    type Input = Vec<String>;
+    type Params = ();
 
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("157").second("70")];
 
-        // when
-        let result = run_first(&data);
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(
+                |line| match find_invalid(line, |c| c.is_ascii_alphabetic()) {
+                    Some(invalid) => Err(SolveError::at(input, invalid)),
+                    None => Ok(line.to_string()),
+                },
+            )
+            .collect()
+    }
 
-        // then
-        let expected = "8401";
-        assert_eq!(result, expected.to_string());
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input
+            .iter()
+            .map(|line| {
+                let half = line.len() / 2;
+                let first: HashSet<_> = line[..half].to_string().chars().collect();
+                let second: HashSet<_> = line[half..].to_string().chars().collect();
+                first
+                    .intersection(&second)
+                    .map(|&c| get_char_value(c))
+                    .sum::<i64>()
+            })
+            .sum::<i64>()
+            .to_string())
     }
 
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input
+            .iter()
+            .chunks(3)
+            .into_iter()
+            .map(|chunk| match chunk.collect_vec()[..] {
+                [first, second, third] => {
+                    let first: HashSet<_> = first.chars().collect();
+                    let second: HashSet<_> = second.chars().collect();
+                    let third: HashSet<_> = third.chars().collect();
 
-        // when
-        let result = run_second(&data);
+                    Ok(first
+                        .iter()
+                        .filter(|c| second.contains(c))
+                        .filter(|c| third.contains(c))
+                        .map(|&c| get_char_value(c))
+                        .sum::<i64>())
+                }
+                _ => Err(SolveError::NoSolution),
+            })
+            .sum::<Result<i64, _>>()?
+            .to_string())
+    }
+}
 
-        // then
-        let expected = "2641";
-        assert_eq!(result, expected.to_string());
+fn get_char_value(c: char) -> i64 {
+    match c {
+        'a'..='z' => c as i64 - 96,
+        'A'..='Z' => c as i64 - 65 + 27,
+        _ => unreachable!("Unexpected char: {}", c),
     }
 }
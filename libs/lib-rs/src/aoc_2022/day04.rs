@@ -1,92 +1,61 @@
-use crate::common::parse::{parse_numbers, to_non_empty_lines};
-
-#[derive(Copy, Clone)]
-struct Assignment {
-    pub from: i64,
-    pub to: i64,
-}
-
-impl Assignment {
-    fn contains(&self, other: &Assignment) -> bool {
-        self.from <= other.from && self.to >= other.to
+use crate::common::interval::Interval;
+use crate::common::parse::parse_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day04;
+
+const EXAMPLE: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8";
+
+impl Solution for Day04 {
+    type Input = Vec<(Interval<i64>, Interval<i64>)>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("2").second("4")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_assignments(input, line))
+            .collect()
     }
 
-    fn overlaps(&self, other: &Assignment) -> bool {
-        self.from <= other.from && self.to >= other.from
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input
+            .iter()
+            .filter(|(first, second)| {
+                first.contains_interval(second) || second.contains_interval(first)
+            })
+            .count()
+            .to_string())
     }
-}
-
-pub fn run_first(input: &str) -> String {
-    to_non_empty_lines(input)
-        .iter()
-        .filter(|line| {
-            let assignments = parse_assignments(line);
-            let first = assignments[0];
-            let second = assignments[1];
-            first.contains(&second) || second.contains(&first)
-        })
-        .count()
-        .to_string()
-}
-
-pub fn run_second(input: &str) -> String {
-    to_non_empty_lines(input)
-        .iter()
-        .filter(|line| {
-            let assignments = parse_assignments(line);
-            let first = assignments[0];
-            let second = assignments[1];
 
-            first.overlaps(&second) || second.overlaps(&first)
-        })
-        .count()
-        .to_string()
-}
-
-fn parse_assignments(line: &str) -> Vec<Assignment> {
-    line.split(',')
-        .map(|part| {
-            let nums = parse_numbers(part);
-            Assignment {
-                from: nums[0],
-                to: nums[1],
-            }
-        })
-        .collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 4;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "532";
-        assert_eq!(result, expected.to_string());
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        Ok(input
+            .iter()
+            .filter(|(first, second)| first.overlaps(second))
+            .count()
+            .to_string())
     }
+}
 
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "854";
-        assert_eq!(result, expected.to_string());
+/// The two sections assigned to a pair of elves, both ends included.
+fn parse_assignments(
+    input: &str,
+    line: &str,
+) -> Result<(Interval<i64>, Interval<i64>), SolveError> {
+    match parse_numbers(line)[..] {
+        [first_from, first_to, second_from, second_to] => Ok((
+            Interval::closed(first_from, first_to),
+            Interval::closed(second_from, second_to),
+        )),
+        _ => Err(SolveError::at(input, line)),
     }
 }
@@ -1,15 +1,51 @@
-use crate::common::parse::{parse_numbers, to_non_empty_lines};
+use crate::common::parse::parse_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 use itertools::Itertools;
 
-pub fn run_first(input: &str) -> String {
-    let (mut stacks, commands) = parse_stacks(input);
+pub struct Day05;
+
+const EXAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+impl Solution for Day05 {
+    type Input = (Vec<CrateStack>, Vec<Vec<i64>>);
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("CMZ").second("MCD")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_stacks(input)
+    }
+
+    fn part_one((stacks, commands): &Self::Input) -> Result<String, SolveError> {
+        move_one_by_one(stacks.clone(), commands)
+    }
+
+    fn part_two((stacks, commands): &Self::Input) -> Result<String, SolveError> {
+        move_multiple(stacks.clone(), commands)
+    }
+}
+
+fn move_one_by_one(
+    mut stacks: Vec<CrateStack>,
+    commands: &[Vec<i64>],
+) -> Result<String, SolveError> {
     for cmd in commands {
         let amount = cmd[0];
         let from = cmd[1] as usize;
         let to = cmd[2] as usize;
         for _ in 0..amount {
-            let cr = stacks.get_mut(from - 1).unwrap().pop_crate();
-            stacks.get_mut(to - 1).unwrap().push_crate(&cr);
+            let cr = stacks[from - 1].pop_crate().ok_or(SolveError::NoSolution)?;
+            stacks[to - 1].push_crate(&cr);
         }
     }
     let top_crates = stacks
@@ -17,29 +53,34 @@ pub fn run_first(input: &str) -> String {
         .map(|s| s.get_top())
         .collect::<Vec<String>>()
         .join("");
-    top_crates
+    Ok(top_crates)
 }
 
-pub fn run_second(input: &str) -> String {
-    let (mut stacks, commands) = parse_stacks(input);
+fn move_multiple(mut stacks: Vec<CrateStack>, commands: &[Vec<i64>]) -> Result<String, SolveError> {
     for cmd in commands {
         let amount = cmd[0] as usize;
         let from = cmd[1] as usize;
         let to = cmd[2] as usize;
-        let cr = stacks.get_mut(from - 1).unwrap().pop_multiple(amount);
-        stacks.get_mut(to - 1).unwrap().push_multiple(&cr);
+        let cr = stacks[from - 1]
+            .pop_multiple(amount)
+            .ok_or(SolveError::NoSolution)?;
+        stacks[to - 1].push_multiple(&cr);
     }
     let top_crates = stacks
         .iter()
         .map(|s| s.get_top())
         .collect::<Vec<String>>()
         .join("");
-    top_crates
+    Ok(top_crates)
 }
 
-fn parse_stacks(input: &str) -> (Vec<CrateStack>, Vec<Vec<i64>>) {
-    let lines = to_non_empty_lines(input);
-    let (stack_lines, cmd_lines) = lines.split_at(8);
+fn parse_stacks(input: &str) -> Result<(Vec<CrateStack>, Vec<Vec<i64>>), SolveError> {
+    let (drawing, cmd_lines) = input
+        .split_once("\n\n")
+        .ok_or_else(|| SolveError::at(input, input.lines().last().unwrap_or_default()))?;
+    // The last line of the drawing only numbers the stacks
+    let stack_lines = drawing.lines().collect_vec();
+    let stack_lines = &stack_lines[..stack_lines.len().saturating_sub(1)];
     let max_len = stack_lines.iter().map(|l| l.len()).max().unwrap_or(0);
 
     let mut stacks: Vec<_> = (0..(max_len + 1) / 4)
@@ -47,25 +88,31 @@ fn parse_stacks(input: &str) -> (Vec<CrateStack>, Vec<Vec<i64>>) {
         .collect();
 
     for line in stack_lines {
-        for idx in 0..stacks.len() {
+        for (idx, stack) in stacks.iter_mut().enumerate() {
             match get_crate_for_stack(idx + 1, line) {
-                None => {}
-                Some(c) => {
-                    if !c.is_empty() {
-                        stacks.get_mut(idx).unwrap().push_crate(&c);
-                    }
+                Some(c) if !c.is_empty() => {
+                    stack.push_crate(&c);
                 }
+                _ => {}
             }
         }
     }
 
-    for idx in 0..stacks.len() {
-        stacks.get_mut(idx).unwrap().reverse();
+    for stack in stacks.iter_mut() {
+        stack.reverse();
     }
 
-    let cmds: Vec<_> = cmd_lines.iter().skip(1).map(|l| parse_numbers(l)).collect();
-
-    (stacks, cmds)
+    let is_stack = |number: i64| number >= 1 && number as usize <= stacks.len();
+    let cmds = cmd_lines
+        .split('\n')
+        .filter(|l| !l.is_empty())
+        .map(|l| match parse_numbers(l)[..] {
+            [amount, from, to] if is_stack(from) && is_stack(to) => Ok(vec![amount, from, to]),
+            _ => Err(SolveError::at(input, l)),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((stacks, cmds))
 }
 
 pub fn get_crate_for_stack(idx: usize, line: &str) -> Option<String> {
@@ -74,8 +121,8 @@ pub fn get_crate_for_stack(idx: usize, line: &str) -> Option<String> {
         .map(|c| c.to_string().trim_end().to_string())
 }
 
-#[derive(Debug)]
-struct CrateStack {
+#[derive(Debug, Clone)]
+pub struct CrateStack {
     pub crates: Vec<String>,
 }
 
@@ -84,12 +131,12 @@ impl CrateStack {
         self.crates.push(cr.to_string());
     }
 
-    fn pop_crate(&mut self) -> String {
-        self.crates.pop().unwrap()
+    fn pop_crate(&mut self) -> Option<String> {
+        self.crates.pop()
     }
 
-    fn pop_multiple(&mut self, count: usize) -> String {
-        (0..count).map(|_| self.crates.pop().unwrap()).join("")
+    fn pop_multiple(&mut self, count: usize) -> Option<String> {
+        (0..count).map(|_| self.crates.pop()).collect()
     }
 
     fn push_multiple(&mut self, crates: &str) {
@@ -99,46 +146,10 @@ impl CrateStack {
     }
 
     fn get_top(&self) -> String {
-        self.crates.last().unwrap().clone()
+        self.crates.last().cloned().unwrap_or_default()
     }
 
     fn reverse(&mut self) {
         self.crates = self.crates.clone().into_iter().rev().collect();
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 5;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "GFTNRBZPF";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "VRQWPDSGP";
-        assert_eq!(result, expected.to_string());
-    }
-}
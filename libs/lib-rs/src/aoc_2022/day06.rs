@@ -1,61 +1,56 @@
 use std::collections::HashSet;
 use std::iter::FromIterator;
 
-pub fn run_first(input: &str) -> String {
-    first_uniq(input, 4).to_string()
-}
-
-pub fn run_second(input: &str) -> String {
-    first_uniq(input, 14).to_string()
-}
-
-pub fn first_uniq(input: &str, size: usize) -> usize {
-    let mut char_count = 0;
-    let chars = input.chars().collect::<Vec<char>>();
-
-    for (idx, window) in chars.windows(size).enumerate() {
-        let uniq: HashSet<&char> = HashSet::from_iter(window.iter());
-        if uniq.len() == size {
-            char_count = idx + size;
-            break;
-        }
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = String;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[
+        Example::new("mjqjpqmgbljsphdztnvjfqwrcgsmlb")
+            .first("7")
+            .second("19"),
+        Example::new("bvwbjplbgvbhsrlpgdmjqwftvncz")
+            .first("5")
+            .second("23"),
+        Example::new("nppdvjthqldpwncqszvftbrmjlhg")
+            .first("6")
+            .second("23"),
+        Example::new("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")
+            .first("10")
+            .second("29"),
+        Example::new("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")
+            .first("11")
+            .second("26"),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        Ok(input.to_string())
     }
 
-    char_count
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 6;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "1042";
-        assert_eq!(result, expected.to_string());
+    fn part_one(input: &Self::Input) -> Result<String, SolveError> {
+        first_uniq(input, 4)
+            .map(|count| count.to_string())
+            .ok_or(SolveError::NoSolution)
     }
 
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
+    fn part_two(input: &Self::Input) -> Result<String, SolveError> {
+        first_uniq(input, 14)
+            .map(|count| count.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
+}
 
-        // when
-        let result = run_second(&data);
+pub fn first_uniq(input: &str, size: usize) -> Option<usize> {
+    let chars = input.chars().collect::<Vec<char>>();
 
-        // then
-        let expected = "2980";
-        assert_eq!(result, expected.to_string());
-    }
+    chars.windows(size).enumerate().find_map(|(idx, window)| {
+        let uniq: HashSet<&char> = HashSet::from_iter(window.iter());
+        (uniq.len() == size).then_some(idx + size)
+    })
 }
@@ -1,30 +1,67 @@
-use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
-use crate::common::parse::to_non_empty_lines;
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day07;
+
+const EXAMPLE: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k";
+
+impl Solution for Day07 {
+    type Input = HashMap<String, i64>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("95437").second("24933642")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        dir_size_lookup(input)
+    }
 
-pub fn run_first(input: &str) -> String {
-    let lookup = dir_size_lookup(input);
-    lookup
-        .values()
-        .filter(|&&dir_size| dir_size <= 100_000)
-        .sum::<i64>()
-        .to_string()
-}
+    fn part_one(lookup: &Self::Input) -> Result<String, SolveError> {
+        Ok(lookup
+            .values()
+            .filter(|&&dir_size| dir_size <= 100_000)
+            .sum::<i64>()
+            .to_string())
+    }
 
-pub fn run_second(input: &str) -> String {
-    let lookup = dir_size_lookup(input);
-    let root_size = *lookup.get("").unwrap();
-    lookup
-        .values()
-        .filter(|&&dir_size| dir_size >= root_size - 40_000_000)
-        .min()
-        .unwrap()
-        .to_string()
+    fn part_two(lookup: &Self::Input) -> Result<String, SolveError> {
+        let root_size = *lookup.get("").ok_or(SolveError::NoSolution)?;
+        lookup
+            .values()
+            .filter(|&&dir_size| dir_size >= root_size - 40_000_000)
+            .min()
+            .map(|dir_size| dir_size.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 }
 
-fn dir_size_lookup(input: &str) -> HashMap<String, i64> {
-    let lines = to_non_empty_lines(input);
+fn dir_size_lookup(input: &str) -> Result<HashMap<String, i64>, SolveError> {
+    let lines = input.split('\n').filter(|line| !line.is_empty());
 
     let mut path: Vec<String> = vec!["".to_string()];
     let mut size_lookup: HashMap<String, i64> = HashMap::new();
@@ -48,55 +85,14 @@ fn dir_size_lookup(input: &str) -> HashMap<String, i64> {
                 path.push(dir.to_string());
             }
             [size_str, _] => {
-                let size = size_str.parse::<i64>().unwrap();
+                let size = parse_value::<i64>(input, size_str)?;
                 for i in 1..=path.len() {
-                    let subpath = path[0..i].join("/");
-                    if let Entry::Vacant(e) = size_lookup.entry(subpath.clone()) {
-                        e.insert(size);
-                    } else {
-                        *size_lookup.get_mut(&subpath).unwrap() += size;
-                    }
+                    *size_lookup.entry(path[0..i].join("/")).or_insert(0) += size;
                 }
             }
-            _ => unreachable!(""),
+            _ => return Err(SolveError::at(input, line)),
         }
     }
 
-    size_lookup
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 7;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "1427048";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "2940614";
-        assert_eq!(result, expected.to_string());
-    }
+    Ok(size_lookup)
 }
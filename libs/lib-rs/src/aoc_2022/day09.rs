@@ -1,30 +1,65 @@
 use std::collections::HashSet;
 
-use crate::common::parse::to_non_empty_lines;
+use crate::common::geom::{Direction, Point2};
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-type Position = (i64, i64);
+type Position = Point2<i64>;
 
-pub fn run_first(input: &str) -> String {
-    rope(input, 2)
-}
+pub struct Day09;
+
+const EXAMPLE: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2";
+
+const LARGER_EXAMPLE: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20";
+
+impl Solution for Day09 {
+    type Input = Vec<(Direction, i64)>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(EXAMPLE).first("13").second("1"),
+        Example::new(LARGER_EXAMPLE).second("36"),
+    ];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_commands(input)
+    }
 
-pub fn run_second(input: &str) -> String {
-    rope(input, 10)
+    fn part_one(cmds: &Self::Input) -> Result<String, SolveError> {
+        Ok(rope(cmds, 2))
+    }
+
+    fn part_two(cmds: &Self::Input) -> Result<String, SolveError> {
+        Ok(rope(cmds, 10))
+    }
 }
 
-pub fn rope(input: &str, length: usize) -> String {
-    let cmds = parse_commands(input);
-    let mut rope: Vec<Position> = (0..length).map(|_| (0, 0)).collect();
+pub fn rope(cmds: &[(Direction, i64)], length: usize) -> String {
+    let mut rope: Vec<Position> = vec![Position::default(); length];
     let mut visited: HashSet<Position> = HashSet::new();
 
-    for cmd in cmds {
-        let (direction, steps) = cmd;
-        let (x_delta, y_delta) = step_delta(&direction);
+    for &(direction, steps) in cmds {
         for _step in 0..steps {
-            rope[0] = (rope[0].0 + x_delta, rope[0].1 + y_delta);
+            rope[0] += direction.offset();
             for idx in 1..rope.len() {
                 let delta = move_delta(&rope[idx - 1], &rope[idx]);
-                rope[idx] = (rope[idx].0 + delta.0, rope[idx].1 + delta.1);
+                rope[idx] += delta;
             }
             visited.insert(rope[rope.len() - 1]);
         }
@@ -33,91 +68,23 @@ pub fn rope(input: &str, length: usize) -> String {
 }
 
 fn move_delta(head: &Position, tail: &Position) -> Position {
-    let delta_x = head.0 - tail.0;
-    let delta_y = head.1 - tail.1;
-    if (-1..=1).contains(&delta_x) && (-1..=1).contains(&delta_y) {
-        (0, 0)
+    if head.chebyshev(tail) <= 1 {
+        Position::default()
     } else {
-        (delta_x.clamp(-1, 1), delta_y.clamp(-1, 1))
-    }
-}
-
-pub fn step_delta(direction: &str) -> (i64, i64) {
-    match direction {
-        "U" => (0, 1),
-        "D" => (0, -1),
-        "R" => (1, 0),
-        "L" => (-1, 0),
-        _ => unreachable!("Invalid command: {:?}", direction),
+        (*head - *tail).signum()
     }
 }
 
-pub fn parse_commands(input: &str) -> Vec<(String, i64)> {
-    to_non_empty_lines(input)
-        .iter()
-        .map(|l| {
-            let tokens: Vec<_> = l.split(' ').filter(|t| !t.is_empty()).collect();
-            (
-                tokens.first().unwrap().to_string(),
-                tokens.last().unwrap().parse::<i64>().unwrap(),
-            )
+pub fn parse_commands(input: &str) -> Result<Vec<(Direction, i64)>, SolveError> {
+    input
+        .split('\n')
+        .filter(|l| !l.is_empty())
+        .map(|l| match l.split_once(' ') {
+            Some((direction @ ("U" | "D" | "R" | "L"), steps)) => Ok((
+                Direction::parse(input, direction)?,
+                parse_value(input, steps.trim())?,
+            )),
+            _ => Err(SolveError::at(input, l)),
         })
         .collect()
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 9;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "5930";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "2443";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_example_part_1() {
-        // given
-
-        let data = "R 4
-U 4
-L 3
-D 1
-R 4
-D 1
-L 5
-R 2";
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "13";
-        assert_eq!(result, expected.to_string());
-    }
-}
@@ -1,21 +1,205 @@
 use std::collections::HashSet;
 
-use crate::common::parse::to_non_empty_lines;
+use crate::common::ocr::read_drawing;
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
 type Instruction = (String, i64);
 
-pub fn run_first(input: &str) -> String {
-    let instructions = parse_instructions(input);
-    let cycles = vec![20, 60, 100, 140, 180, 220].into_iter().collect();
-    let mut program = Program::default();
-    program.sum_cycles(&instructions, &cycles).to_string()
+pub struct Day10;
+
+const EXAMPLE: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop";
+
+impl Solution for Day10 {
+    type Input = Vec<Instruction>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("13140").second(concat!(
+        "##  ##  ##  ##  ##  ##  ##  ##  ##  ##  \n",
+        "###   ###   ###   ###   ###   ###   ### \n",
+        "####    ####    ####    ####    ####    \n",
+        "#####     #####     #####     #####     \n",
+        "######      ######      ######      ####\n",
+        "#######       #######       #######     \n",
+    ))];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_instructions(input)
+    }
+
+    fn part_one(instructions: &Self::Input) -> Result<String, SolveError> {
+        let cycles = [20, 60, 100, 140, 180, 220].into_iter().collect();
+        let mut program = Program::default();
+        Ok(program.sum_cycles(instructions, &cycles).to_string())
+    }
+
+    fn part_two(instructions: &Self::Input) -> Result<String, SolveError> {
+        Self::part_two_drawn(instructions, &()).map(|(answer, _)| answer)
+    }
+
+    fn part_two_drawn(
+        instructions: &Self::Input,
+        _params: &(),
+    ) -> Result<(String, Option<String>), SolveError> {
+        let drawing = draw(instructions);
+        // The example draws no letters, its answer is the drawing itself
+        let answer = read_drawing(&drawing).unwrap_or_else(|| drawing.clone());
+        Ok((answer, Some(drawing)))
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    let instructions = parse_instructions(input);
-    let cycles = vec![20, 60, 100, 140, 180, 220].into_iter().collect();
+/// Screen lit by the sprite while running `instructions`.
+fn draw(instructions: &[Instruction]) -> String {
+    let cycles = [20, 60, 100, 140, 180, 220].into_iter().collect();
     let mut program = Program::default();
-    program.compute_sprite(&instructions, &cycles)
+    program.compute_sprite(instructions, &cycles)
 }
 
 struct Program {
@@ -90,8 +274,8 @@ impl Program {
     }
 
     fn sprite_tick(&mut self, cycles: &HashSet<i64>) {
-        let sprite = vec![self.result - 1, self.result, self.result + 1];
-        let c = if sprite.iter().any(|&y| y == self.cycle % 40) {
+        let sprite = [self.result - 1, self.result, self.result + 1];
+        let c = if sprite.contains(&(self.cycle % 40)) {
             '#'
         } else {
             ' '
@@ -107,28 +291,26 @@ impl Program {
     }
 }
 
-pub fn parse_instructions(input: &str) -> Vec<(String, i64)> {
-    to_non_empty_lines(input)
-        .iter()
+pub fn parse_instructions(input: &str) -> Result<Vec<(String, i64)>, SolveError> {
+    input
+        .split('\n')
+        .filter(|l| !l.is_empty())
         .map(|l| {
             let tokens: Vec<_> = l.split(' ').filter(|t| !t.is_empty()).collect();
 
-            (
-                tokens.first().unwrap().to_string(),
-                tokens
-                    .get(1)
-                    .unwrap_or(&"0")
-                    .to_string()
-                    .parse::<i64>()
-                    .unwrap(),
-            )
+            match tokens[..] {
+                ["noop"] => Ok(("noop".to_string(), 0)),
+                ["addx", value] => Ok(("addx".to_string(), parse_value(input, value)?)),
+                _ => Err(SolveError::at(input, l)),
+            }
         })
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
+    use crate::answers::test_utils::real_cases;
+    use crate::Part;
 
     use super::*;
 
@@ -136,15 +318,17 @@ mod tests {
     const DAY: u8 = 10;
 
     #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
+    fn test_drawing_reported() {
+        for case in real_cases(YEAR, DAY, Part::Second) {
+            // when
+            let second = Day10::solve(&case.input).second.unwrap();
 
-        // then
-        let expected = "14920";
-        assert_eq!(result, expected.to_string());
+            // then
+            let drawing = second.drawing.unwrap();
+            assert_eq!(drawing.lines().count(), 6, "profile {}", case.profile);
+            assert!(drawing.lines().all(|line| line.len() == 40));
+            assert_eq!(read_drawing(&drawing), second.answer);
+            assert_eq!(second.answer, Some(case.expected));
+        }
     }
 }
@@ -2,27 +2,96 @@ use std::collections::VecDeque;
 
 use itertools::Itertools;
 
-use crate::common::parse::{parse_numbers, to_non_empty_lines};
+use crate::common::math::{lcm_of, mul_mod, rem};
+use crate::common::parse::{captures, comma_list, non_empty_lines, parse_value, sections};
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::params;
+use crate::solution::Solution;
 
-pub fn run_first(input: &str) -> String {
-    let mut monkeys = parse_monkeys(input);
-    run_rounds(&mut monkeys, 20, None).to_string()
+pub struct Day11;
+
+const EXAMPLE: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1";
+
+params! {
+    pub struct Day11Params {
+        /// Rounds played in part one
+        first_rounds: i64 = 20,
+        /// Rounds played in part two
+        second_rounds: i64 = 10_000,
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    let mut monkeys = parse_monkeys(input);
-    let product: i64 = monkeys.iter().map(|m| m.test).product();
-    run_rounds(&mut monkeys, 10_000, Some(product)).to_string()
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Params = Day11Params;
+
+    const EXAMPLES: &'static [Example] =
+        &[Example::new(EXAMPLE).first("10605").second("2713310158")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_monkeys(input)
+    }
+
+    fn part_one(monkeys: &Self::Input) -> Result<String, SolveError> {
+        Self::part_one_with(monkeys, &Day11Params::default())
+    }
+
+    fn part_two(monkeys: &Self::Input) -> Result<String, SolveError> {
+        Self::part_two_with(monkeys, &Day11Params::default())
+    }
+
+    fn part_one_with(monkeys: &Self::Input, params: &Day11Params) -> Result<String, SolveError> {
+        Ok(run_rounds(&mut monkeys.clone(), params.first_rounds, true)?.to_string())
+    }
+
+    fn part_two_with(monkeys: &Self::Input, params: &Day11Params) -> Result<String, SolveError> {
+        Ok(run_rounds(&mut monkeys.clone(), params.second_rounds, false)?.to_string())
+    }
 }
 
-fn run_rounds(monkeys: &mut Vec<Monkey>, rounds: i64, product: Option<i64>) -> i64 {
+/// Plays `rounds` and returns the monkey business, worry levels are divided by 3 after every
+/// inspection when `relieved`.
+fn run_rounds(monkeys: &mut [Monkey], rounds: i64, relieved: bool) -> Result<i64, SolveError> {
+    // Without relief worry levels only matter through the divisibility tests, which all divide
+    // this. With relief the division by 3 needs the real level and no modulus is used.
+    let modulus = match relieved {
+        true => None,
+        false => Some(lcm_of(monkeys.iter().map(|m| m.test)).ok_or(SolveError::Overflow)?),
+    };
     for _ in 0..rounds {
         for m_idx in 0..monkeys.len() {
             let m = &mut monkeys[m_idx];
             let mut items: Vec<(i64, i64)> = vec![];
 
-            while m.has_items() {
-                items.push(m.inspect_next(product))
+            while let Some(item) = m.inspect_next(modulus)? {
+                items.push(item)
             }
 
             for (item, idx) in items {
@@ -31,61 +100,75 @@ fn run_rounds(monkeys: &mut Vec<Monkey>, rounds: i64, product: Option<i64>) -> i
         }
     }
 
-    monkeys
+    Ok(monkeys
         .iter()
         .map(|m| m.inspected_count)
         .sorted()
         .rev()
         .take(2)
-        .product()
+        .product())
 }
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Debug, Clone)]
+pub struct Monkey {
     items: VecDeque<i64>,
-    operation: String,
+    operation: Operation,
     test: i64,
     if_true: i64,
     if_false: i64,
     inspected_count: i64,
 }
 
-impl Monkey {
-    fn has_items(&self) -> bool {
-        !self.items.is_empty()
+/// `new = left * right` or `new = left + right`, a `None` operand is the old worry level.
+#[derive(Debug, Clone)]
+struct Operation {
+    left: Option<i64>,
+    multiply: bool,
+    right: Option<i64>,
+}
+
+impl Operation {
+    /// New worry level, `None` on overflow.
+    fn apply(&self, old: i64) -> Option<i64> {
+        let left = self.left.unwrap_or(old);
+        let right = self.right.unwrap_or(old);
+        if self.multiply {
+            left.checked_mul(right)
+        } else {
+            left.checked_add(right)
+        }
     }
 
-    fn inspect_next(&mut self, product: Option<i64>) -> (i64, i64) {
-        let item = self.items.pop_front().unwrap();
-        let tokens: Vec<_> = self.operation.split(' ').collect();
-        let a = self.compute_value(tokens[3], item);
-        let op = tokens[4];
-        let b = self.compute_value(tokens[5], item);
-
-        let op_result = match op {
-            "*" => a * b,
-            "+" => a + b,
-            _ => unreachable!("Invalid op: {}", op),
-        };
+    /// New worry level modulo `modulus`.
+    fn apply_mod(&self, old: i64, modulus: i64) -> i64 {
+        let left = self.left.unwrap_or(old);
+        let right = self.right.unwrap_or(old);
+        if self.multiply {
+            mul_mod(left, right, modulus)
+        } else {
+            rem(left + right, modulus)
+        }
+    }
+}
 
-        let rounded = match product {
-            None => op_result / 3,
-            Some(pr) => op_result % pr,
+impl Monkey {
+    /// Inspects the next item, returning its new worry level and the monkey it is thrown to.
+    /// Worry levels are kept below `modulus`, or divided by 3 without one.
+    fn inspect_next(&mut self, modulus: Option<i64>) -> Result<Option<(i64, i64)>, SolveError> {
+        let Some(item) = self.items.pop_front() else {
+            return Ok(None);
+        };
+        let rounded = match modulus {
+            None => self.operation.apply(item).ok_or(SolveError::Overflow)? / 3,
+            Some(modulus) => self.operation.apply_mod(item, modulus),
         };
         let is_divisible = rounded % self.test == 0;
         self.inspected_count += 1;
 
         if is_divisible {
-            (rounded, self.if_true)
+            Ok(Some((rounded, self.if_true)))
         } else {
-            (rounded, self.if_false)
-        }
-    }
-
-    fn compute_value(&self, op_token: &str, item: i64) -> i64 {
-        match op_token {
-            "old" => item,
-            _ => op_token.parse().unwrap(),
+            Ok(Some((rounded, self.if_false)))
         }
     }
 
@@ -94,60 +177,111 @@ impl Monkey {
     }
 }
 
-fn parse_monkeys(input: &str) -> Vec<Monkey> {
-    to_non_empty_lines(input)
-        .chunks(6)
-        .map(|chunk| {
-            let items = parse_numbers(&chunk[1]).into_iter().collect();
-            let operation = &chunk[2];
-            let divisible = parse_numbers(&chunk[3]);
-            let if_true = parse_numbers(&chunk[4]);
-            let if_false = parse_numbers(&chunk[5]);
-
-            Monkey {
-                items,
-                operation: operation.trim().to_string(),
-                test: divisible[0],
-                if_true: if_true[0],
-                if_false: if_false[0],
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, SolveError> {
+    let sections: Vec<_> = sections(input).collect();
+    let monkey_count = sections.len() as i64;
+    let target = |line, template| {
+        let [idx] = captures(input, line, template)?;
+        match parse_value(input, idx)? {
+            idx if idx < monkey_count => Ok(idx),
+            _ => Err(SolveError::at(input, idx)),
+        }
+    };
+
+    sections
+        .iter()
+        .map(|&section| {
+            let lines: Vec<_> = non_empty_lines(section).collect();
+            let [header, items, operation, divisible, if_true, if_false] = lines[..] else {
+                return Err(SolveError::at(input, section));
+            };
+            captures::<1>(input, header, "Monkey {}:")?;
+            let [items] = captures(input, items, "Starting items: {}")?;
+            let [left, operator, right] = captures(input, operation, "Operation: new = {} {} {}")?;
+            let [test] = captures(input, divisible, "Test: divisible by {}")?;
+            let test = match parse_value(input, test)? {
+                test if test > 0 => test,
+                _ => return Err(SolveError::at(input, test)),
+            };
+
+            Ok(Monkey {
+                items: comma_list(input, items)?.into(),
+                operation: Operation {
+                    left: parse_operand(input, left)?,
+                    multiply: match operator {
+                        "*" => true,
+                        "+" => false,
+                        _ => return Err(SolveError::at(input, operator)),
+                    },
+                    right: parse_operand(input, right)?,
+                },
+                test,
+                if_true: target(if_true, "If true: throw to monkey {}")?,
+                if_false: target(if_false, "If false: throw to monkey {}")?,
                 inspected_count: 0,
-            }
+            })
         })
         .collect()
 }
 
+/// `None` for the old worry level.
+fn parse_operand(input: &str, token: &str) -> Result<Option<i64>, SolveError> {
+    match token {
+        "old" => Ok(None),
+        _ => parse_value(input, token).map(Some),
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 11;
-
     #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
+    fn test_parse_monkeys() {
         // when
-        let result = run_first(&data);
+        let monkeys = parse_monkeys(EXAMPLE).unwrap();
 
         // then
-        let expected = "121450";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(monkeys.len(), 4);
+        assert_eq!(monkeys[1].items, [54, 65, 75, 74]);
+        assert_eq!(monkeys[2].operation.apply(7), Some(49));
+        assert_eq!((monkeys[3].if_true, monkeys[3].if_false), (0, 1));
     }
 
     #[test]
-    fn test_part_2() {
+    fn test_parse_errors() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let bad_operator = EXAMPLE.replace("old + 6", "old - 6");
+        let unknown_monkey = EXAMPLE.replace("throw to monkey 3\n\n", "throw to monkey 4\n\n");
+        let zero_test = EXAMPLE.replace("divisible by 19", "divisible by 0");
 
-        // when
-        let result = run_second(&data);
+        // then
+        assert_eq!(
+            parse_monkeys(&bad_operator).unwrap_err(),
+            SolveError::parse(10, 24, "-")
+        );
+        assert_eq!(
+            parse_monkeys(&unknown_monkey).unwrap_err(),
+            SolveError::parse(6, 31, "4")
+        );
+        assert_eq!(
+            parse_monkeys(&zero_test).unwrap_err(),
+            SolveError::parse(11, 22, "0")
+        );
+    }
+
+    #[test]
+    fn test_relief_needs_no_modulus() {
+        // given
+        let huge_tests = EXAMPLE
+            .replace("divisible by 23", "divisible by 4000000007")
+            .replace("divisible by 19", "divisible by 4000000009");
 
         // then
-        let expected = "28244037010";
-        assert_eq!(result, expected.to_string());
+        assert!(Day11::run_first(&huge_tests).is_ok());
+        assert_eq!(
+            Day11::run_second(&huge_tests).unwrap_err(),
+            SolveError::Overflow
+        );
     }
 }
@@ -1,240 +1,128 @@
-use std::cmp::Ordering;
-use std::collections::{BinaryHeap, HashMap};
-
-use crate::common::parse::to_non_empty_lines;
+use crate::common::grid::{Grid, Pos};
+use crate::common::search::{bfs, Path};
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day12;
+
+const EXAMPLE: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi";
+
+impl Solution for Day12 {
+    type Input = (HillMatrix, Pos, Pos);
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("31").second("29")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        let mut matrix = parse_matrix(input)?;
+        let start = matrix.update_start().ok_or(SolveError::NoSolution)?;
+        let end = matrix.update_end().ok_or(SolveError::NoSolution)?;
+        Ok((matrix, start, end))
+    }
 
-pub fn run_first(input: &str) -> String {
-    let mut matrix = parse_matrix(input);
-    let start = matrix.update_start();
-    let end = matrix.update_end();
-    let len = matrix.find_shortest_path_len(start, end);
-    len.to_string()
-}
+    fn part_one((matrix, start, end): &Self::Input) -> Result<String, SolveError> {
+        matrix
+            .find_shortest_path(&[*start], *end)
+            .map(|path| path.cost.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 
-pub fn run_second(input: &str) -> String {
-    let mut matrix = parse_matrix(input);
-    let _start = matrix.update_start();
-    let end = matrix.update_end();
-    let all_starts = matrix.get_all_starts('a');
-    all_starts
-        .iter()
-        .map(|p| matrix.find_shortest_path_len(*p, end))
-        .min()
-        .unwrap()
-        .to_string()
+    fn part_two((matrix, _start, end): &Self::Input) -> Result<String, SolveError> {
+        matrix
+            .find_shortest_path(&matrix.get_all_starts('a'), *end)
+            .map(|path| path.cost.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 }
 
 #[derive(Debug)]
 pub struct HillMatrix {
-    cost_values: Vec<i32>,
-    width: i32,
-    height: i32,
-}
-
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-pub struct Point {
-    row: i32,
-    column: i32,
-}
-
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-pub struct HillPoint {
-    coords: Point,
-    cost: i32,
-}
-
-impl PartialOrd<Self> for HillPoint {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-impl Ord for HillPoint {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
-    }
+    cost_values: Grid<u8>,
 }
 
 impl HillMatrix {
-    fn get_first_of_value(&self, val: char) -> Point {
-        let idx = self
-            .cost_values
-            .iter()
-            .position(|&p| p == val as i32)
-            .unwrap() as i32;
-        let col = idx % self.width;
-        let row = (idx - col) / self.width;
-        Point { row, column: col }
-    }
-
-    fn get_all_starts(&self, val: char) -> Vec<Point> {
+    fn get_all_starts(&self, val: char) -> Vec<Pos> {
         self.cost_values
             .iter()
-            .enumerate()
-            .filter(|(_idx, &v)| v == val as i32)
-            .map(|(idx, _v)| {
-                let col = idx as i32 % self.width;
-                let row = (idx as i32 - col) / self.width;
-                Point { row, column: col }
-            })
+            .filter(|&(_pos, &v)| v == val as u8)
+            .map(|(pos, _v)| pos)
             .collect()
     }
 
-    fn update_start(&mut self) -> Point {
-        let point = self.get_first_of_value('S');
-        let idx = self.get_index(point.row, point.column);
-        self.cost_values[idx] = 'a' as i32;
-        point
+    fn update_start(&mut self) -> Option<Pos> {
+        self.replace_first('S', 'a')
     }
 
-    fn update_end(&mut self) -> Point {
-        let point = self.get_first_of_value('E');
-        let idx = self.get_index(point.row, point.column);
-        self.cost_values[idx] = 'z' as i32;
-        point
+    fn update_end(&mut self) -> Option<Pos> {
+        self.replace_first('E', 'z')
     }
 
-    fn get_index(&self, row: i32, column: i32) -> usize {
-        (row * self.width + column) as usize
+    fn replace_first(&mut self, val: char, replacement: char) -> Option<Pos> {
+        let point = self.cost_values.position(|&v| v == val as u8)?;
+        self.cost_values[point] = replacement as u8;
+        Some(point)
     }
 
-    fn get_height(&self, row: i32, column: i32) -> i32 {
-        self.cost_values[self.get_index(row, column)]
-    }
-
-    fn get_available_adjacent(&self, point: &HillPoint) -> Vec<HillPoint> {
-        let row = point.coords.row;
-        let col = point.coords.column;
-        let coords = vec![(0, 1), (0, -1), (-1, 0), (1, 0)];
-
-        coords
-            .into_iter()
-            .filter(|(dr, dc)| {
-                let r = row + dr;
-                let c = col + dc;
-                let oob = self.out_of_bounds(r, c);
-                if oob {
-                    return false;
-                }
-
-                let height = self.get_height(r, c);
-                point.cost >= height - 1
-            })
-            .map(|(dr, dc)| {
-                let new_row = row + dr;
-                let new_col = col + dc;
-                let height = self.get_height(new_row, new_col);
-                HillPoint {
-                    coords: Point {
-                        row: new_row,
-                        column: new_col,
-                    },
-                    cost: height,
-                }
-            })
-            .collect()
-    }
-
-    fn out_of_bounds(&self, row: i32, column: i32) -> bool {
-        if row < 0 || row >= self.height {
-            return true;
-        }
-        if column < 0 || column >= self.width {
-            return true;
-        }
-        false
+    /// Positions reachable in one step, at most one higher than `pos`.
+    fn get_available_adjacent(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        let height = self.cost_values[pos];
+        self.cost_values
+            .neighbours4(pos)
+            .filter(move |&adjacent| height + 1 >= self.cost_values[adjacent])
     }
 
-    fn find_shortest_path_len(&self, start: Point, end: Point) -> i32 {
-        let mut came_from: HashMap<Point, Point> = HashMap::new();
-        let mut path_len_so_far: HashMap<Point, i32> = HashMap::new();
-        let mut priority_queue: BinaryHeap<HillPoint> = BinaryHeap::new();
-
-        let current_point = start;
-
-        let mut current = HillPoint {
-            cost: self.get_height(current_point.row, current_point.column),
-            coords: current_point,
-        };
-
-        priority_queue.push(current);
-        *came_from.entry(current_point).or_insert(current_point) = current_point;
-        *path_len_so_far.entry(current_point).or_insert(0) = 0;
-
-        while !priority_queue.is_empty() {
-            current = priority_queue.pop().unwrap();
-            if current.coords == end {
-                break;
-            }
-
-            for adjacent in self.get_available_adjacent(&current) {
-                let new_path_len = path_len_so_far.get(&current.coords).unwrap() + 1;
-
-                if !path_len_so_far.contains_key(&adjacent.coords)
-                    || new_path_len < *path_len_so_far.get(&adjacent.coords).unwrap()
-                {
-                    *path_len_so_far
-                        .entry(adjacent.coords)
-                        .or_insert(new_path_len) = new_path_len;
-
-                    priority_queue.push(adjacent);
-                    *came_from.entry(adjacent.coords).or_insert(current.coords) = current.coords;
-                }
-            }
-        }
-
-        *path_len_so_far.get(&end).unwrap_or(&i32::MAX)
+    /// Shortest route to `end` from the closest of `starts`.
+    fn find_shortest_path(&self, starts: &[Pos], end: Pos) -> Option<Path<Pos, usize>> {
+        bfs(
+            starts.iter().copied(),
+            |&pos| self.get_available_adjacent(pos),
+            |&pos| pos == end,
+        )
     }
 }
 
-fn parse_matrix(input: &str) -> HillMatrix {
-    let lines = to_non_empty_lines(input);
-    let height = lines.len() as i32;
-    let width = lines.first().unwrap().len() as i32;
-    let values: Vec<_> = lines
-        .iter()
-        .flat_map(|l| l.chars().map(|c| c as i32))
-        .collect();
-
-    HillMatrix {
-        cost_values: values,
-        height,
-        width,
-    }
+fn parse_matrix(input: &str) -> Result<HillMatrix, SolveError> {
+    let cost_values = Grid::parse(input, |c| {
+        (c.is_ascii_lowercase() || c == 'S' || c == 'E').then_some(c as u8)
+    })?;
+    Ok(HillMatrix { cost_values })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 12;
-
     #[test]
-    fn test_part_1() {
+    fn test_shortest_path() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let (matrix, start, end) = Day12::parse(EXAMPLE).unwrap();
 
         // when
-        let result = run_first(&data);
+        let path = matrix.find_shortest_path(&[start], end).unwrap();
 
         // then
-        let expected = "361";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(path.cost, 31);
+        assert_eq!(path.start(), Some(&(0, 0)));
+        assert_eq!(path.goal(), Some(&(2, 5)));
     }
 
     #[test]
-    fn test_part_2() {
+    fn test_shortest_path_from_any_start() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let (matrix, _start, end) = Day12::parse(EXAMPLE).unwrap();
 
         // when
-        let result = run_second(&data);
+        let path = matrix
+            .find_shortest_path(&matrix.get_all_starts('a'), end)
+            .unwrap();
 
         // then
-        let expected = "354";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(path.cost, 29);
+        assert_eq!(path.start(), Some(&(4, 0)));
     }
 }
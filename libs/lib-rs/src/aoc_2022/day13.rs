@@ -1,201 +1,349 @@
-use crate::common::parse::{parse_numbers, to_non_empty_lines};
-use itertools::Itertools;
 use std::cmp::Ordering;
-use std::collections::VecDeque;
+use std::fmt;
+use std::slice;
+use std::str::FromStr;
 
-pub fn run_first(input: &str) -> String {
-    let pairs = parse_pairs(input);
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::params;
+use crate::solution::Solution;
 
-    pairs
-        .iter()
-        .enumerate()
-        .map(|(idx, (left, right))| (idx, compare(left, right)))
-        .filter(|&(_idx, is_in_order)| is_in_order == 1)
-        .map(|(idx, _)| idx + 1)
-        .sum::<usize>()
-        .to_string()
+pub struct Day13;
+
+const EXAMPLE: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]";
+
+params! {
+    pub struct Day13Params {
+        /// Divider packets added in part two, separated by spaces
+        dividers: Dividers = Dividers::default(),
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    let mut packets = to_non_empty_lines(input);
-    packets.push("[[2]]".to_string());
-    packets.push("[[6]]".to_string());
+impl Solution for Day13 {
+    type Input = Vec<PacketValue>;
+    type Params = Day13Params;
 
-    let sorted: Vec<_> = packets
-        .iter()
-        .sorted_by(|a, b| match compare(a, b) {
-            1 => Ordering::Less,
-            -1 => Ordering::Greater,
-            _ => Ordering::Equal,
-        })
-        .collect();
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("13").second("140")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        let lines: Vec<_> = input.split('\n').filter(|l| !l.is_empty()).collect();
+        let packets = lines
+            .iter()
+            .map(|line| parse_value(input, line))
+            .collect::<Result<Vec<_>, _>>()?;
+        if lines.len() % 2 != 0 {
+            return Err(SolveError::at(input, lines[lines.len() - 1]));
+        }
+        Ok(packets)
+    }
 
-    let first_pos = sorted.iter().position(|&p| p == "[[2]]").unwrap() + 1;
-    let second_pos = sorted.iter().position(|&p| p == "[[6]]").unwrap() + 1;
+    fn part_one(packets: &Self::Input) -> Result<String, SolveError> {
+        Ok(packets
+            .chunks(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0].packet_cmp(&pair[1]).is_lt())
+            .map(|(idx, _)| idx + 1)
+            .sum::<usize>()
+            .to_string())
+    }
+
+    fn part_two(packets: &Self::Input) -> Result<String, SolveError> {
+        Self::part_two_with(packets, &Day13Params::default())
+    }
 
-    (first_pos * second_pos).to_string()
+    fn part_two_with(packets: &Self::Input, params: &Day13Params) -> Result<String, SolveError> {
+        Ok(decoder_key(packets, &params.dividers.0).to_string())
+    }
+}
+
+/// Product of the 1-based positions of the `dividers` once sorted along with the `packets`.
+///
+/// A position is the number of packets ordered before, so nothing needs sorting. Dividers equal
+/// in packet order keep their order.
+fn decoder_key(packets: &[PacketValue], dividers: &[PacketValue]) -> usize {
+    dividers
+        .iter()
+        .enumerate()
+        .map(|(idx, divider)| {
+            let packets_before = packets
+                .iter()
+                .filter(|packet| packet.packet_cmp(divider).is_lt())
+                .count();
+            let dividers_before = dividers
+                .iter()
+                .enumerate()
+                .filter(|&(other, value)| match value.packet_cmp(divider) {
+                    Ordering::Less => true,
+                    Ordering::Equal => other < idx,
+                    Ordering::Greater => false,
+                })
+                .count();
+            packets_before + dividers_before + 1
+        })
+        .product()
 }
 
-#[derive(Debug)]
-pub enum Type {
-    Integer,
-    List,
+/// Integer or list of values within a packet.
+///
+/// Equality is structural, so `3`, `[3]` and `[[3]]` differ even though they are equal in
+/// packet order, see [`PacketValue::packet_cmp`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PacketValue {
+    Int(u64),
+    List(Vec<PacketValue>),
 }
 
-pub fn compare(left: &str, right: &str) -> i32 {
-    let left_type = get_type(left);
-    let right_type = get_type(right);
-
-    match (left_type, right_type) {
-        (Type::Integer, Type::Integer) => {
-            let left_nums = parse_numbers(left);
-            let right_nums = parse_numbers(right);
-            let l = left_nums.first().unwrap();
-            let r = right_nums.first().unwrap();
-            if l == r {
-                return 0;
+impl PacketValue {
+    /// Packet order: integers compare as numbers and lists element by element, the shorter
+    /// list first when one is a prefix of the other. An integer compared to a list is a list
+    /// holding only it.
+    pub fn packet_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (PacketValue::Int(left), PacketValue::Int(right)) => left.cmp(right),
+            (PacketValue::List(left), PacketValue::List(right)) => packet_cmp_lists(left, right),
+            (PacketValue::Int(_), PacketValue::List(right)) => {
+                packet_cmp_lists(slice::from_ref(self), right)
             }
-            if l < r {
-                1
-            } else {
-                -1
+            (PacketValue::List(left), PacketValue::Int(_)) => {
+                packet_cmp_lists(left, slice::from_ref(other))
             }
         }
-        (Type::Integer, Type::List) => compare(&format!("[{left}]"), right),
-        (Type::List, Type::Integer) => compare(left, &format!("[{right}]")),
-        _ => {
-            let mut left_children = get_children(left);
-            let mut right_children = get_children(right);
-            while !left_children.is_empty() || !right_children.is_empty() {
-                if left_children.is_empty() {
-                    return 1;
-                }
-                if right_children.is_empty() {
-                    return -1;
-                }
-                let curr_left = left_children.pop_front().unwrap();
-                let curr_right = right_children.pop_front().unwrap();
-                let res = compare(&curr_left, &curr_right);
-                if res != 0 {
-                    return res;
-                }
-            }
-            0
+    }
+
+    /// Order of the shapes alone, an integer before a list, to break ties of packet order.
+    fn structural_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (PacketValue::Int(left), PacketValue::Int(right)) => left.cmp(right),
+            (PacketValue::List(left), PacketValue::List(right)) => left
+                .iter()
+                .zip(right)
+                .map(|(left, right)| left.structural_cmp(right))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| left.len().cmp(&right.len())),
+            (PacketValue::Int(_), PacketValue::List(_)) => Ordering::Less,
+            (PacketValue::List(_), PacketValue::Int(_)) => Ordering::Greater,
         }
     }
 }
 
-pub fn get_type(part: &str) -> Type {
-    if part.contains('[') {
-        return Type::List;
+fn packet_cmp_lists(left: &[PacketValue], right: &[PacketValue]) -> Ordering {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left.packet_cmp(right))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| left.len().cmp(&right.len()))
+}
+
+/// [`PacketValue::packet_cmp`], with values equal in packet order but not in structure
+/// ordered by [`PacketValue::structural_cmp`] to stay consistent with `Eq`.
+impl Ord for PacketValue {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.packet_cmp(other)
+            .then_with(|| self.structural_cmp(other))
     }
-    Type::Integer
 }
 
-pub fn get_children(part: &str) -> VecDeque<String> {
-    let without_braces: String = part[1..part.len() - 1].to_string();
+impl PartialOrd for PacketValue {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Reads a packet, which is a list of integers and nested lists.
+impl FromStr for PacketValue {
+    type Err = SolveError;
 
-    let mut open_count = 0;
-    let mut indices: VecDeque<usize> = VecDeque::new();
-    for (idx, c) in without_braces.chars().enumerate() {
-        match c {
-            ',' => {
-                if open_count == 0 {
-                    indices.push_back(idx)
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        fn value(bytes: &[u8]) -> Option<(PacketValue, &[u8])> {
+            match bytes.first()? {
+                b'0'..=b'9' => {
+                    let digits = bytes.iter().take_while(|b| b.is_ascii_digit()).count();
+                    let int = std::str::from_utf8(&bytes[..digits]).ok()?.parse().ok()?;
+                    Some((PacketValue::Int(int), &bytes[digits..]))
                 }
+                b'[' => list(&bytes[1..]),
+                _ => None,
             }
-            '[' => {
-                open_count += 1;
+        }
+
+        fn list(mut bytes: &[u8]) -> Option<(PacketValue, &[u8])> {
+            let mut values = vec![];
+            if let Some(rest) = bytes.strip_prefix(b"]") {
+                return Some((PacketValue::List(values), rest));
             }
-            ']' => {
-                open_count -= 1;
+            loop {
+                let (item, rest) = value(bytes)?;
+                values.push(item);
+                match rest.split_first()? {
+                    (b',', rest) => bytes = rest,
+                    (b']', rest) => return Some((PacketValue::List(values), rest)),
+                    _ => return None,
+                }
             }
-            _ => {
-                continue;
+        }
+
+        match value(text.as_bytes()) {
+            Some((packet @ PacketValue::List(_), [])) => Ok(packet),
+            _ => Err(SolveError::at(text, text)),
+        }
+    }
+}
+
+impl fmt::Display for PacketValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PacketValue::Int(int) => write!(f, "{int}"),
+            PacketValue::List(values) => {
+                write!(f, "[")?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
             }
         }
     }
+}
 
-    let mut result: VecDeque<String> = VecDeque::new();
-    let mut prev_idx = 0;
+/// Packets separated by whitespace, `[[2]] [[6]]` in the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dividers(Vec<PacketValue>);
 
-    for idx in indices {
-        let val = without_braces[prev_idx..idx].to_string();
-        result.push_back(val);
-        prev_idx = idx + 1;
+impl Default for Dividers {
+    fn default() -> Self {
+        let divider = |int| PacketValue::List(vec![PacketValue::List(vec![PacketValue::Int(int)])]);
+        Dividers(vec![divider(2), divider(6)])
     }
+}
 
-    result.push_back(without_braces[prev_idx..without_braces.len()].to_string());
+impl FromStr for Dividers {
+    type Err = SolveError;
 
-    result.into_iter().filter(|i| !i.is_empty()).collect()
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let dividers = text
+            .split_whitespace()
+            .map(|divider| parse_value(text, divider))
+            .collect::<Result<Vec<_>, _>>()?;
+        if dividers.is_empty() {
+            return Err(SolveError::NoSolution);
+        }
+        Ok(Dividers(dividers))
+    }
 }
 
-pub fn parse_pairs(input: &str) -> Vec<(String, String)> {
-    let lines = to_non_empty_lines(input);
-
-    lines
-        .chunks(2)
-        .map(|c| (c[0].clone(), c[1].clone()))
-        .collect()
+impl fmt::Display for Dividers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, divider) in self.0.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{divider}")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
+    use crate::Part;
 
     use super::*;
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 13;
-    use crate::common::parse::test_utils::vec_of_strings;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
+    fn packet(text: &str) -> PacketValue {
+        text.parse().unwrap()
+    }
 
-        // then
-        let expected = "6240";
-        assert_eq!(result, expected.to_string());
+    fn compare(left: &str, right: &str) -> Ordering {
+        packet(left).packet_cmp(&packet(right))
     }
 
     #[test]
-    fn test_part_2() {
+    fn test_parse_display() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
+        let text = "[1,[2,[3,[4,[5,6,7]]]],8,[],10]";
 
         // then
-        let expected = "23142";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_get_children() {
+        assert_eq!(packet(text).to_string(), text);
         assert_eq!(
-            get_children("[[1],[2,3,4]]"),
-            vec_of_strings!["[1]", "[2,3,4]"]
+            packet("[[1],4]"),
+            PacketValue::List(vec![
+                PacketValue::List(vec![PacketValue::Int(1)]),
+                PacketValue::Int(4)
+            ])
+        );
+        assert_ne!(packet("[[1],4]"), packet("[1,4]"));
+        assert!("3".parse::<PacketValue>().is_err());
+        assert!("[1,]".parse::<PacketValue>().is_err());
+        assert_eq!(
+            Day13::parse("[1]\n[2,x]"),
+            Err(SolveError::parse(2, 1, "[2,x]"))
         );
     }
 
     #[test]
     fn test_compare() {
-        assert_eq!(compare("[1,1,3,1,1]", "[1,1,5,1,1]"), 1);
-        assert_eq!(compare("[[1],[2,3,4]]", "[[1],4]"), 1);
-        assert_eq!(compare("[3]", "[3]"), 0);
-        assert_eq!(compare("[9]", "[[8,7,6]]"), -1);
-        assert_eq!(compare("[[4,4],4,4]", "[[4,4],4,4,4]"), 1);
-        assert_eq!(compare("[7,7,7,7]", "[7,7,7]"), -1);
-        assert_eq!(compare("[0]", "[3]"), 1);
-        assert_eq!(compare("[[[]]]", "[[]]"), -1);
+        assert_eq!(compare("[1,1,3,1,1]", "[1,1,5,1,1]"), Ordering::Less);
+        assert_eq!(compare("[[1],[2,3,4]]", "[[1],4]"), Ordering::Less);
+        assert_eq!(compare("[3]", "[3]"), Ordering::Equal);
+        assert_eq!(compare("[9]", "[[8,7,6]]"), Ordering::Greater);
+        assert_eq!(compare("[[4,4],4,4]", "[[4,4],4,4,4]"), Ordering::Less);
+        assert_eq!(compare("[7,7,7,7]", "[7,7,7]"), Ordering::Greater);
+        assert_eq!(compare("[0]", "[3]"), Ordering::Less);
+        assert_eq!(compare("[[[]]]", "[[]]"), Ordering::Greater);
         assert_eq!(
             compare("[1,[2,[3,[4,[5,6,7]]]],8,9]", "[1,[2,[3,[4,[5,6,0]]]],8,9]"),
-            -1
+            Ordering::Greater
         );
+        assert_eq!(compare("[[3]]", "[3]"), Ordering::Equal);
+        assert_ne!(packet("[[3]]"), packet("[3]"));
+        // Ties of packet order are broken by structure, so sorting still follows packet order
+        assert_eq!(packet("[3]").cmp(&packet("[[3]]")), Ordering::Less);
+        assert_eq!(packet("[4]").cmp(&packet("[[3]]")), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_custom_dividers() {
+        // given
+        let params = |dividers: &str| Day13Params {
+            dividers: dividers.parse().unwrap(),
+        };
+
+        // when
+        let default = Day13::run_with(EXAMPLE, Part::Second, &params("[[2]] [[6]]"));
+        let single = Day13::run_with(EXAMPLE, Part::Second, &params("[[6]]"));
+        let three = Day13::run_with(EXAMPLE, Part::Second, &params("[[6]] [] [[2]]"));
+
+        // then
+        assert_eq!(default, Ok("140".to_string()));
+        assert_eq!(single, Ok("13".to_string()));
+        // [] sorts first, ahead of every packet
+        assert_eq!(three, Ok((15 * 11).to_string()));
+        assert_eq!(Day13Params::default(), params("[[2]] [[6]]"));
+        assert!("".parse::<Dividers>().is_err());
     }
 }
@@ -1,41 +1,45 @@
 use std::collections::HashMap;
 
-use crate::common::parse::to_non_empty_lines;
+use crate::common::geom::Point2;
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-pub fn run_first(input: &str) -> String {
-    let mut rockfall = parse_input(input);
-    let sand_source = Point { x: 500, y: 0 };
-    rockfall.count_till_abyss(&sand_source).to_string()
-}
+pub struct Day14;
 
-pub fn run_second(input: &str) -> String {
-    let mut rockfall = parse_input(input);
-    let sand_source = Point { x: 500, y: 0 };
-    rockfall.count_till_fill(&sand_source).to_string()
-}
+const EXAMPLE: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9";
 
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
-}
+impl Solution for Day14 {
+    type Input = Rockfall;
+    type Params = ();
 
-impl Point {
-    fn move_by(&self, vector: (i64, i64)) -> Point {
-        let (x, y) = vector;
-        Point {
-            x: self.x + x,
-            y: self.y + y,
-        }
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("24").second("93")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_one(rockfall: &Self::Input) -> Result<String, SolveError> {
+        let sand_source = Point::new(500, 0);
+        Ok(rockfall.clone().count_till_abyss(&sand_source)?.to_string())
+    }
+
+    fn part_two(rockfall: &Self::Input) -> Result<String, SolveError> {
+        let sand_source = Point::new(500, 0);
+        Ok(rockfall.clone().count_till_fill(&sand_source)?.to_string())
     }
 }
 
-#[derive(Debug)]
-struct Rockfall {
+type Point = Point2<i64>;
+
+#[derive(Debug, Clone)]
+pub struct Rockfall {
     filled: HashMap<Point, Fill>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum Fill {
     Rock,
     Sand,
@@ -54,17 +58,17 @@ impl Rockfall {
         }
     }
 
-    fn count_till_abyss(&mut self, source: &Point) -> i64 {
-        let deepest = self.deepest();
+    fn count_till_abyss(&mut self, source: &Point) -> Result<i64, SolveError> {
+        let deepest = self.deepest().ok_or(SolveError::NoSolution)?;
         let mut count = 0;
         while let DropResult::Rested = self.drop_into_abyss(source, &deepest) {
             count += 1;
         }
-        count
+        Ok(count)
     }
 
-    fn count_till_fill(&mut self, source: &Point) -> i64 {
-        let deepest = self.deepest();
+    fn count_till_fill(&mut self, source: &Point) -> Result<i64, SolveError> {
+        let deepest = self.deepest().ok_or(SolveError::NoSolution)?;
         let mut count = 0;
         loop {
             let point = self.drop_on_the_floor(source, &deepest);
@@ -73,7 +77,7 @@ impl Rockfall {
                 break;
             }
         }
-        count
+        Ok(count)
     }
 
     fn drop_on_the_floor(&mut self, source: &Point, deepest: &Point) -> Point {
@@ -85,9 +89,9 @@ impl Rockfall {
                 return curr;
             }
 
-            let down = curr.move_by((0, 1));
-            let diag_left = curr.move_by((-1, 1));
-            let diag_right = curr.move_by((1, 1));
+            let down = curr + Point::new(0, 1);
+            let diag_left = curr + Point::new(-1, 1);
+            let diag_right = curr + Point::new(1, 1);
 
             if !self.filled.contains_key(&down) {
                 curr = down;
@@ -116,9 +120,9 @@ impl Rockfall {
             if curr.y > deepest.y {
                 return DropResult::Abyss;
             }
-            let down = curr.move_by((0, 1));
-            let diag_left = curr.move_by((-1, 1));
-            let diag_right = curr.move_by((1, 1));
+            let down = curr + Point::new(0, 1);
+            let diag_left = curr + Point::new(-1, 1);
+            let diag_right = curr + Point::new(1, 1);
 
             if !self.filled.contains_key(&down) {
                 curr = down;
@@ -141,85 +145,36 @@ impl Rockfall {
     }
 
     fn fill_line(&mut self, from: &Point, to: &Point) {
-        let x_delta = (to.x - from.x).signum();
-        let y_delta = (to.y - from.y).signum();
-        let len = i64::max((to.x - from.x).abs(), (to.y - from.y).abs());
-
-        for n in 0..=len {
-            let x = from.x + n * x_delta;
-            let y = from.y + n * y_delta;
-            self.filled.insert(Point { x, y }, Fill::Rock);
+        let step = (*to - *from).signum();
+        for n in 0..=from.chebyshev(to) {
+            self.filled.insert(*from + step * n, Fill::Rock);
         }
     }
 
-    fn deepest(&self) -> Point {
-        *self
-            .filled
-            .iter()
-            .max_by(|&a, &b| a.0.y.cmp(&b.0.y))
-            .unwrap()
-            .0
+    fn deepest(&self) -> Option<Point> {
+        self.filled.keys().max_by(|&a, &b| a.y.cmp(&b.y)).copied()
     }
 }
 
-fn parse_input(input: &str) -> Rockfall {
-    let lines = to_non_empty_lines(input);
+fn parse_input(input: &str) -> Result<Rockfall, SolveError> {
+    let lines = input.split('\n').filter(|l| !l.is_empty());
 
     let mut rockfall = Rockfall::new();
 
     for line in lines {
-        let points: Vec<Point> = line
+        let points = line
             .split(" -> ")
             .filter(|t| !t.is_empty())
             .map(|t| {
-                let coord: Vec<_> = t.split(',').collect();
-                Point {
-                    x: coord.first().unwrap().parse().unwrap(),
-                    y: coord.last().unwrap().parse().unwrap(),
-                }
+                let (x, y) = t.split_once(',').ok_or_else(|| SolveError::at(input, t))?;
+                Ok(Point::new(parse_value(input, x)?, parse_value(input, y)?))
             })
-            .collect();
+            .collect::<Result<Vec<_>, SolveError>>()?;
 
         points
             .windows(2)
             .for_each(|p| rockfall.fill_line(&p[0], &p[1]));
     }
 
-    rockfall
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 14;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "1068";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "27936";
-        assert_eq!(result, expected.to_string());
-    }
+    Ok(rockfall)
 }
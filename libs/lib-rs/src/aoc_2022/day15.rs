@@ -1,137 +1,188 @@
 use std::collections::HashSet;
 
-use crate::common::parse::{parse_signed_numbers, to_non_empty_lines};
-
-pub fn run_first(input: &str) -> String {
-    let mut zone = parse_zone(input);
-    zone.count_invalid(2_000_000).to_string()
+use crate::common::geom::Point2;
+use crate::common::interval::{Interval, IntervalSet};
+use crate::common::parse::parse_signed_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::params;
+use crate::solution::Solution;
+
+pub struct Day15;
+
+const EXAMPLE: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
+
+params! {
+    pub struct Day15Params {
+        /// Row scanned for positions without a beacon in part one
+        row: i64 = 2_000_000,
+        /// Largest x and y of the distress beacon in part two
+        limit: i64 = 4_000_000,
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    let zone = parse_zone(input);
-    zone.tuning_freq(4_000_000).to_string()
-}
+impl Solution for Day15 {
+    type Input = Zone;
+    type Params = Day15Params;
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE)
+        .params(&[("row", "10"), ("limit", "20")])
+        .first("26")
+        .second("56000011")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_zone(input)
+    }
+
+    fn part_one(zone: &Self::Input) -> Result<String, SolveError> {
+        Self::part_one_with(zone, &Day15Params::default())
+    }
 
-#[derive(Hash, Debug, Copy, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
+    fn part_two(zone: &Self::Input) -> Result<String, SolveError> {
+        Self::part_two_with(zone, &Day15Params::default())
+    }
+
+    fn part_one_with(zone: &Self::Input, params: &Day15Params) -> Result<String, SolveError> {
+        Ok(zone.count_invalid(params.row).to_string())
+    }
+
+    fn part_two_with(zone: &Self::Input, params: &Day15Params) -> Result<String, SolveError> {
+        zone.tuning_freq(params.limit)
+            .map(|freq| freq.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 }
 
-#[derive(Debug)]
-struct Zone {
-    sensors: Vec<(i64, i64, i64)>,
-    grid: HashSet<(i64, i64)>,
+#[derive(Debug, Clone)]
+pub struct Zone {
+    /// Every sensor with the distance to its closest beacon.
+    sensors: Vec<(Point2<i64>, i64)>,
+    beacons: HashSet<Point2<i64>>,
 }
 
 impl Zone {
-    fn count_invalid(&mut self, target_y: i64) -> i64 {
-        let mut invalid = 0;
-
-        for (x, y, distance) in &self.sensors {
-            for dx in x - distance..x + distance {
-                let dx_distance = (x - dx).abs() + (target_y - y).abs();
-                let target = (dx, target_y);
-                if dx_distance <= *distance && self.grid.get(&target).is_none() {
-                    self.grid.insert(target);
-                    invalid += 1;
-                }
-            }
-        }
+    /// Positions of `target_y` within range of a sensor, where the closest beacon would be.
+    fn covered(&self, target_y: i64) -> IntervalSet<i64> {
+        self.sensors
+            .iter()
+            .filter_map(|&(sensor, distance)| {
+                let reach = distance - (target_y - sensor.y).abs();
+                (reach >= 0).then(|| Interval::closed(sensor.x - reach, sensor.x + reach))
+            })
+            .collect()
+    }
 
-        invalid
+    /// Positions of `target_y` that cannot hold a beacon, the known beacons aside.
+    fn count_invalid(&self, target_y: i64) -> i64 {
+        let covered = self.covered(target_y);
+        let beacons = self
+            .beacons
+            .iter()
+            .filter(|beacon| beacon.y == target_y && covered.contains(beacon.x))
+            .count();
+
+        covered.len() - beacons as i64
     }
 
-    fn tuning_freq(&self, limit: i64) -> i64 {
-        let range = 0..limit;
+    fn tuning_freq(&self, limit: i64) -> Option<i64> {
+        let range = 0..=limit;
 
-        for (sx, sy, sd) in &self.sensors {
-            for j in 0..*sd {
+        for &(sensor, sd) in &self.sensors {
+            for j in 0..sd {
                 let k = sd - j + 1;
-                let deltas = vec![(1, 1), (1, -1), (-1, 1), (-1, -1)];
+                let deltas = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
                 for (xd, yd) in deltas {
-                    let curr_x = sx + j * xd;
-                    let curr_y = sy + k * yd;
+                    let curr = sensor + Point2::new(j * xd, k * yd);
 
-                    if !(range.contains(&curr_x) && range.contains(&curr_y)) {
+                    if !(range.contains(&curr.x) && range.contains(&curr.y)) {
                         continue;
                     }
                     if self
                         .sensors
                         .iter()
-                        .any(|s| is_in_range(s, &(curr_x, curr_y)))
+                        .any(|(other, distance)| other.manhattan(&curr) <= *distance)
                     {
                         continue;
                     }
-                    return 4_000_000 * curr_x + curr_y;
+                    return Some(4_000_000 * curr.x + curr.y);
                 }
             }
         }
 
-        panic!("Did not found")
+        None
     }
 }
 
-fn is_in_range(s: &(i64, i64, i64), p: &(i64, i64)) -> bool {
-    let d = md(&(s.0, s.1), p);
-    d <= s.2
-}
-
-fn md(a: &(i64, i64), b: &(i64, i64)) -> i64 {
-    let (ax, ay) = a;
-    let (bx, by) = b;
-    (bx - ax).abs() + (by - ay).abs()
-}
+fn parse_zone(input: &str) -> Result<Zone, SolveError> {
+    let lines = input.split('\n').filter(|l| !l.is_empty());
+    let mut sensors: Vec<(Point2<i64>, i64)> = vec![];
+    let mut beacons: HashSet<Point2<i64>> = HashSet::new();
+
+    for line in lines {
+        let [sx, sy, bx, by] = parse_signed_numbers(line)[..] else {
+            return Err(SolveError::at(input, line));
+        };
+        let (sensor, beacon) = (Point2::new(sx, sy), Point2::new(bx, by));
+        sensors.push((sensor, sensor.manhattan(&beacon)));
+        beacons.insert(beacon);
+    }
 
-fn parse_zone(input: &str) -> Zone {
-    let lines = to_non_empty_lines(input);
-    let mut sensors: Vec<(i64, i64, i64)> = vec![];
-    let mut grid: HashSet<(i64, i64)> = HashSet::new();
-
-    lines.iter().for_each(|line| {
-        let nums = parse_signed_numbers(line);
-        let s = (nums[0], nums[1]);
-        let b = (nums[2], nums[3]);
-        let distance = md(&s, &b);
-        sensors.push((s.0, s.1, distance));
-        grid.insert(s);
-    });
-
-    Zone { sensors, grid }
+    Ok(Zone { sensors, beacons })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
+    use crate::Part;
 
     use super::*;
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 15;
-
     #[test]
-    fn test_part_1() {
+    fn test_example_params() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let params = Day15Params { row: 10, limit: 20 };
 
         // when
-        let result = run_first(&data);
+        let first = Day15::run_with(EXAMPLE, Part::First, &params);
+        let second = Day15::run_with(EXAMPLE, Part::Second, &params);
 
         // then
-        let expected = "5040644";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(first, Ok("26".to_string()));
+        assert_eq!(second, Ok("56000011".to_string()));
     }
 
     #[test]
-    fn test_part_2() {
+    fn test_count_invalid() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let zone = Day15::parse(EXAMPLE).unwrap();
 
-        // when
-        let result = run_second(&data);
+        // then
+        // Row 9 has no beacon, row 10 has the one at x=2, which does not count
+        assert_eq!(zone.count_invalid(9), 25);
+        assert_eq!(zone.count_invalid(10), 26);
+        assert_eq!(zone.covered(10).intervals(), [Interval::new(-2, 25)]);
+    }
+
+    #[test]
+    fn test_tuning_freq_on_limit() {
+        // given
+        // Covers every position up to 4 but the corner at x=4, y=4
+        let zone = Day15::parse("Sensor at x=0, y=0: closest beacon is at x=7, y=0").unwrap();
 
         // then
-        let expected = "11016575214126";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(zone.tuning_freq(4), Some(16_000_004));
+        assert_eq!(zone.tuning_freq(3), None);
     }
 }
@@ -1,246 +1,224 @@
-use std::cmp::Ordering;
-use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
-use std::hash::Hash;
-
-use crate::common::parse::to_non_empty_lines;
-
-pub fn run_first(input: &str) -> String {
-    let matrix = parse_input(input);
-    let paths = matrix.paths_lookup();
-    matrix.max_pressure(30, &paths).to_string()
-}
-
-pub fn run_second(input: &str) -> String {
-    let matrix = parse_input(input);
-    let paths = matrix.paths_lookup();
-    matrix.me_and_my_bro(26, &paths).to_string()
-}
-
-#[derive(Debug)]
-struct ValveMatrix {
-    neighbours: HashMap<String, Vec<String>>,
-    flows: HashMap<String, i64>,
-    valves: Vec<String>,
-}
-
-#[derive(Hash, Eq, PartialEq, Debug, Clone)]
-pub struct Valve {
-    name: String,
-    cost: i64,
-}
+use std::collections::HashMap;
+
+use crate::common::graph::{Graph, Interner};
+use crate::common::grid::Grid;
+use crate::common::parse::{captures, list_items, non_empty_lines, parse_value};
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day16;
+
+const EXAMPLE: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+Valve BB has flow rate=13; tunnels lead to valves CC, AA
+Valve CC has flow rate=2; tunnels lead to valves DD, BB
+Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE
+Valve EE has flow rate=3; tunnels lead to valves FF, DD
+Valve FF has flow rate=0; tunnels lead to valves EE, GG
+Valve GG has flow rate=0; tunnels lead to valves FF, HH
+Valve HH has flow rate=22; tunnel leads to valve GG
+Valve II has flow rate=0; tunnels lead to valves AA, JJ
+Valve JJ has flow rate=21; tunnel leads to valve II";
+
+impl Solution for Day16 {
+    type Input = (ValveMatrix, Grid<Option<usize>>);
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("1651").second("1707")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        let matrix = parse_input(input)?;
+        let paths = matrix.paths_lookup();
+        Ok((matrix, paths))
+    }
 
-impl PartialOrd<Self> for Valve {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    fn part_one((matrix, paths): &Self::Input) -> Result<String, SolveError> {
+        Ok(matrix.max_pressure(30, paths)?.to_string())
     }
-}
 
-impl Ord for Valve {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
+    fn part_two((matrix, paths): &Self::Input) -> Result<String, SolveError> {
+        Ok(matrix.me_and_my_bro(26, paths)?.to_string())
     }
 }
 
-#[derive(Hash, Debug, Eq, PartialEq)]
-struct Simulation {
-    pub last_valve: String,
-    pub open: BTreeSet<String>,
-    pub time_left: i64,
+#[derive(Debug)]
+pub struct ValveMatrix {
+    valves: Interner<String>,
+    tunnels: Graph<usize>,
+    flows: Vec<i64>,
+    start: usize,
 }
 
 impl ValveMatrix {
-    fn max_pressure(&self, minutes: i64, paths: &HashMap<(String, String), i64>) -> i64 {
-        let valves = HashSet::new();
-        let nonzero = self.nonzero_valves();
-        self.get_pressure(minutes, &valves, "AA", &nonzero, paths)
+    fn max_pressure(&self, minutes: i64, paths: &Grid<Option<usize>>) -> Result<i64, SolveError> {
+        let best = self.best_by_opened(minutes, paths)?;
+        Ok(best.values().copied().max().unwrap_or(0))
     }
 
-    fn me_and_my_bro(&self, minutes: i64, paths: &HashMap<(String, String), i64>) -> i64 {
-        let mut max = 0;
+    /// Best pressure of two walkers opening disjoint sets of valves.
+    fn me_and_my_bro(&self, minutes: i64, paths: &Grid<Option<usize>>) -> Result<i64, SolveError> {
+        let mut best: Vec<_> = self.best_by_opened(minutes, paths)?.into_iter().collect();
+        best.sort_unstable_by_key(|&(_, pressure)| std::cmp::Reverse(pressure));
 
-        let nonzero: Vec<_> = self.nonzero_valves().into_iter().collect();
-
-        let limit = 32767;
-        for i in 0..limit {
-            let mut me: HashSet<String> = HashSet::new();
-            let mut elebro: HashSet<String> = HashSet::new();
-
-            for (j, item) in nonzero.iter().enumerate().take(15) {
-                if (i >> j) & 1 == 1 {
-                    me.insert(item.to_string());
-                } else {
-                    elebro.insert(item.to_string());
-                }
+        let mut max = 0;
+        for (idx, &(mine, my_pressure)) in best.iter().enumerate() {
+            // The other walker cannot do better than this one from here on
+            if 2 * my_pressure <= max {
+                break;
             }
-
-            if me.len() < 4 || elebro.len() < 4 {
-                continue;
+            for &(theirs, their_pressure) in &best[idx + 1..] {
+                if my_pressure + their_pressure <= max {
+                    break;
+                }
+                if mine & theirs == 0 {
+                    max = my_pressure + their_pressure;
+                    break;
+                }
             }
-            let my_res = self.get_pressure(minutes, &HashSet::new(), "AA", &me, paths);
-            let elebro_res = self.get_pressure(minutes, &HashSet::new(), "AA", &elebro, paths);
-            max = i64::max(max, my_res + elebro_res)
         }
-
-        max
+        Ok(max)
     }
 
-    fn nonzero_valves(&self) -> HashSet<String> {
-        self.valves
-            .iter()
-            .cloned()
-            .filter(|v| *self.flows.get(v).unwrap() > 0)
-            .collect()
+    /// Tunnel distances between the start and the valves worth opening, the other valves are
+    /// only corridors between them.
+    fn paths_lookup(&self) -> Grid<Option<usize>> {
+        self.tunnels
+            .compress(|valve| valve == self.start || self.flows[valve] > 0)
+            .floyd_warshall()
     }
 
-    fn paths_lookup(&self) -> HashMap<(String, String), i64> {
-        let mut nonzero = self.nonzero_valves();
-        let mut lookup = HashMap::new();
-        nonzero.insert("AA".to_string());
-
-        for from in &nonzero {
-            for to in &nonzero {
-                if from == to {
-                    continue;
-                }
-                let key = (from.to_string(), to.to_string());
-                if lookup.contains_key(&key) {
-                    continue;
-                }
-                let cost = self.shortest_path(from, to);
-                lookup.insert((from.to_string(), to.to_string()), cost);
-                lookup.insert((to.to_string(), from.to_string()), cost);
-            }
+    /// Valves worth opening, a valve's bit in the opened masks is its index here.
+    fn useful_valves(&self) -> Result<Vec<usize>, SolveError> {
+        let useful: Vec<_> = (0..self.valves.len())
+            .filter(|&v| self.flows[v] > 0)
+            .collect();
+        if useful.len() > u64::BITS as usize {
+            return Err(SolveError::Overflow);
         }
-
-        lookup
+        Ok(useful)
     }
 
-    fn get_pressure(
+    /// Best pressure released within `minutes` for every set of valves that can be opened in
+    /// time, keyed by the mask of the opened valves.
+    fn best_by_opened(
         &self,
-        minutes_left: i64,
-        open: &HashSet<String>,
-        curr_valve: &str,
-        nonzero_valves: &HashSet<String>,
-        paths: &HashMap<(String, String), i64>,
-    ) -> i64 {
-        if minutes_left <= 0 {
-            return 0;
-        }
-
-        let rpm = self.pressure(open);
-        let mut max_rp_sub = minutes_left * rpm;
-
-        for next_valve in nonzero_valves.iter().filter(|v| !open.contains(*v)) {
-            let path_dur = paths
-                .get(&(curr_valve.to_string(), next_valve.to_string()))
-                .unwrap()
-                + 1;
-            let mut this_rp = i64::min(minutes_left, path_dur) * rpm;
-            let mut new_open = open.clone();
-            new_open.insert(next_valve.to_string());
-            if path_dur < minutes_left {
-                this_rp += self.get_pressure(
-                    minutes_left - path_dur,
-                    &new_open,
-                    next_valve,
-                    nonzero_valves,
-                    paths,
-                );
-            }
-            max_rp_sub = i64::max(max_rp_sub, this_rp);
-        }
-
-        max_rp_sub
+        minutes: i64,
+        paths: &Grid<Option<usize>>,
+    ) -> Result<HashMap<u64, i64>, SolveError> {
+        let mut search = OpeningSearch {
+            matrix: self,
+            useful: self.useful_valves()?,
+            paths,
+            best: HashMap::new(),
+        };
+        search.open_from(self.start, minutes, 0, 0);
+        Ok(search.best)
     }
+}
 
-    fn pressure(&self, open: &HashSet<String>) -> i64 {
-        open.iter().map(|v| self.flows.get(v).unwrap()).sum()
-    }
+/// Depth-first walk through the orders of opening the useful valves.
+struct OpeningSearch<'a> {
+    matrix: &'a ValveMatrix,
+    useful: Vec<usize>,
+    paths: &'a Grid<Option<usize>>,
+    best: HashMap<u64, i64>,
+}
 
-    fn shortest_path(&self, from: &str, to: &str) -> i64 {
-        let mut q: VecDeque<(HashSet<String>, String, i64)> = VecDeque::new();
-        q.push_back((HashSet::new(), from.to_string(), 0));
+impl OpeningSearch<'_> {
+    /// Walks to every valve not opened yet from `valve`, recording the pressure of each set of
+    /// opened valves along the way.
+    fn open_from(&mut self, valve: usize, minutes_left: i64, opened: u64, pressure: i64) {
+        let entry = self.best.entry(opened).or_insert(0);
+        *entry = (*entry).max(pressure);
 
-        while !q.is_empty() {
-            let (traversed, next, cost) = q.pop_front().unwrap();
-            if next == to {
-                return cost;
+        for bit in 0..self.useful.len() {
+            let next = self.useful[bit];
+            if opened & (1 << bit) != 0 {
+                continue;
             }
-
-            for n in self
-                .neighbours
-                .get(&next)
-                .unwrap()
-                .iter()
-                .filter(|n| !traversed.contains(*n))
-            {
-                let mut new_traversed = traversed.clone();
-                new_traversed.insert(next.clone());
-                q.push_back((new_traversed, n.to_string(), cost + 1))
+            // Unreachable valves have no distance
+            let Some(path_len) = self.paths[(valve, next)] else {
+                continue;
+            };
+            let left = minutes_left - path_len as i64 - 1;
+            if left > 0 {
+                let released = pressure + left * self.matrix.flows[next];
+                self.open_from(next, left, opened | 1 << bit, released);
             }
         }
-
-        i64::MAX
     }
 }
 
-fn parse_input(input: &str) -> ValveMatrix {
-    let mut neighbours: HashMap<String, Vec<String>> = HashMap::new();
-    let mut flows: HashMap<String, i64> = HashMap::new();
-    let mut valves: Vec<String> = vec![];
-
-    let lines = to_non_empty_lines(input);
-
-    for line in lines {
-        let (valve_str, tunnels_str) = line.split_once(';').unwrap();
-        let valve_tokens: Vec<&str> = valve_str.split(' ').filter(|t| !t.is_empty()).collect();
-        let name = valve_tokens[1];
-
-        let flow_rate = valve_tokens[4]
-            .split('=')
-            .nth(1)
-            .unwrap()
-            .parse::<i64>()
-            .unwrap();
-
-        let tunnels: Vec<_> = tunnels_str
-            .split(' ')
-            .filter(|t| !t.is_empty())
-            .skip(4)
-            .map(|t| t.replace(',', ""))
-            .collect();
+fn parse_input(input: &str) -> Result<ValveMatrix, SolveError> {
+    let mut valves = Interner::new();
+    let mut flows: Vec<i64> = vec![];
+    let mut leads: Vec<(usize, &str)> = vec![];
+
+    for line in non_empty_lines(input) {
+        let [name, rate, tunnels] = captures(input, line, "Valve {} has flow rate={}; {}")?;
+        let tunnels = tunnels
+            .strip_prefix("tunnels lead to valves ")
+            .or_else(|| tunnels.strip_prefix("tunnel leads to valve "))
+            .ok_or_else(|| SolveError::at(input, tunnels))?;
+
+        let valve = valves.intern(name.to_string());
+        flows.resize(valves.len(), 0);
+        flows[valve] = parse_value(input, rate)?;
+        leads.extend(list_items(tunnels).map(|tunnel| (valve, tunnel)));
+    }
 
-        flows.insert(name.to_string(), flow_rate);
-        neighbours.insert(name.to_string(), tunnels);
-        valves.push(name.to_string())
+    let mut graph = Graph::new(valves.len());
+    for (valve, tunnel) in leads {
+        let next = valves
+            .id(tunnel)
+            .ok_or_else(|| SolveError::at(input, tunnel))?;
+        graph.add_edge(valve, next, 1);
     }
+    let start = valves.id("AA").ok_or(SolveError::NoSolution)?;
 
-    ValveMatrix {
-        flows,
-        neighbours,
+    Ok(ValveMatrix {
         valves,
-    }
+        tunnels: graph,
+        flows,
+        start,
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 16;
-
     #[test]
-    fn test_part_1() {
+    fn test_paths_lookup() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let matrix = parse_input(EXAMPLE).unwrap();
+        let id = |name| matrix.valves.id(name).unwrap();
 
         // when
-        let result = run_first(&data);
+        let paths = matrix.paths_lookup();
+
+        // then
+        assert_eq!(paths[(id("AA"), id("HH"))], Some(5));
+        assert_eq!(paths[(id("JJ"), id("AA"))], Some(2));
+        assert_eq!(paths[(id("BB"), id("CC"))], Some(1));
+        // Corridor valves are compressed away
+        assert_eq!(paths[(id("AA"), id("GG"))], None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // given
+        let bad_rate = EXAMPLE.replace("rate=13", "rate=x");
+        let unknown_valve = EXAMPLE.replace("valve GG", "valve ZZ");
 
         // then
-        let expected = "2056";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(
+            parse_input(&bad_rate).unwrap_err(),
+            SolveError::parse(2, 24, "x")
+        );
+        assert_eq!(
+            parse_input(&unknown_valve).unwrap_err(),
+            SolveError::parse(8, 50, "ZZ")
+        );
     }
 }
@@ -2,75 +2,104 @@ use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Formatter;
 
-pub fn run_first(input: &str) -> String {
-    let jets = parse_jets(input);
-    let mut tetris = Rocktris::new();
-    tetris.drop_rocks(2022, &jets);
-    tetris.max_height().to_string()
+use crate::common::cycle::find_cycle;
+use crate::common::geom::Point2;
+use crate::common::parse::find_invalid;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::params;
+use crate::solution::Solution;
+
+pub struct Day17;
+
+params! {
+    pub struct Day17Params {
+        /// Rocks dropped in part one
+        first_rocks: u64 = 2022,
+        /// Rocks dropped in part two
+        second_rocks: u64 = 1_000_000_000_000,
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    let jets = parse_jets(input);
-    let num_rocks = 1_000_000_000_000_i64;
+impl Solution for Day17 {
+    type Input = Vec<i64>;
+    type Params = Day17Params;
 
-    // Magic numbers
-    let cycle_start = 1713;
-    let height_at_start = 2651;
-    let cycle_increase = 2660;
-    let rocks_per_cycle = 1700;
+    const EXAMPLES: &'static [Example] =
+        &[Example::new(">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>")
+            .first("3068")
+            .second("1514285714288")];
 
-    let num_left_after_start = num_rocks - cycle_start;
-    let num_left_after_cycles = num_left_after_start % rocks_per_cycle;
-    let num_cycles = (num_left_after_start - num_left_after_cycles) / rocks_per_cycle;
-    let cycles_height = num_cycles * cycle_increase;
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_jets(input)
+    }
 
-    let mut tetris = Rocktris::new();
+    fn part_one(jets: &Self::Input) -> Result<String, SolveError> {
+        Self::part_one_with(jets, &Day17Params::default())
+    }
 
-    // After initial part without cycle and the cycle itself there will be some trailing rocks
-    // We can't just take that number and calculate the height from start, because we don't know
-    // what will be the "bottom" and rocks may fall differently
-    // However, we know the bottom is the same after each cycle, so just calculate the height
-    // for start_rocks + 1 cycle rocks + trailing rocks
-    // the bottom between cycle and trailing will be correct
-    tetris.drop_rocks(num_left_after_cycles + rocks_per_cycle + cycle_start, &jets);
-    // The height will be too high, so we need to subtract the start height and cycle height
-    let height_after_cycles = tetris.max_height() - height_at_start - cycle_increase;
-    let sum = height_at_start + cycles_height + height_after_cycles;
-    sum.to_string()
-}
+    fn part_two(jets: &Self::Input) -> Result<String, SolveError> {
+        Self::part_two_with(jets, &Day17Params::default())
+    }
 
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-struct Point {
-    x: i64,
-    y: i64,
+    fn part_one_with(jets: &Self::Input, params: &Day17Params) -> Result<String, SolveError> {
+        Ok(tower_height(jets, params.first_rocks).to_string())
+    }
+
+    fn part_two_with(jets: &Self::Input, params: &Day17Params) -> Result<String, SolveError> {
+        Ok(tower_height(jets, params.second_rocks).to_string())
+    }
 }
 
-impl Point {
-    fn move_by(&self, vector: (i64, i64)) -> Point {
-        let (x, y) = vector;
-        Point {
-            x: self.x + x,
-            y: self.y + y,
-        }
+/// Height of the tower after `num_rocks` rocks.
+///
+/// The shape, the jet and the top of the tower eventually repeat, from then on every cycle of
+/// rocks adds the same height so only the first cycle is simulated.
+fn tower_height(jets: &[i64], num_rocks: u64) -> i64 {
+    let mut tetris = Rocktris::new();
+    let mut heights = vec![tetris.max_height()];
+    let states = std::iter::from_fn(|| {
+        let state = tetris.fingerprint();
+        tetris.drop_rock(jets);
+        heights.push(tetris.max_height());
+        Some(state)
+    });
+    let limit = usize::try_from(num_rocks).unwrap_or(usize::MAX);
+    match find_cycle(states.take(limit.saturating_add(1))) {
+        Some(cycle) => cycle.extrapolate(&heights, num_rocks),
+        None => heights[limit],
     }
 }
 
+/// `y` grows upwards, the floor is below `y = 0`.
+type Point = Point2<i64>;
+
 #[derive(Debug)]
 struct Shape {
     points: Vec<Point>,
 }
 
 impl Shape {
-    fn move_by(&self, vector: (i64, i64)) -> Shape {
-        let new_points = self.points.iter().map(|p| p.move_by(vector)).collect();
+    fn move_by(&self, vector: Point) -> Shape {
+        let new_points = self.points.iter().map(|&p| p + vector).collect();
 
         Shape { points: new_points }
     }
 }
 
+/// Rows below the top of the tower compared when looking for a cycle, see
+/// [`Rocktris::fingerprint`].
+const FINGERPRINT_DEPTH: i64 = 64;
+
+const MAX_WIDTH: i64 = 7;
+
 #[derive(Debug)]
 struct Rocktris {
+    shapes: Vec<Shape>,
     rocks: HashSet<Point>,
+    height: i64,
+    dropped: usize,
+    jet_idx: usize,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -82,63 +111,93 @@ enum Action {
 impl Rocktris {
     pub fn new() -> Self {
         Self {
+            shapes: all_shapes(),
             rocks: HashSet::new(),
+            height: 0,
+            dropped: 0,
+            jet_idx: 0,
         }
     }
 
-    fn drop_rocks(&mut self, count: i64, jets: &[i64]) {
-        let shapes = all_shapes();
-        let max_width = 7;
+    fn drop_rock(&mut self, jets: &[i64]) {
         let left_wall_offset = 2;
         let bottom_offset = 3;
-        let mut bottom = 0;
 
-        let mut jet_idx = 0;
+        let shape: &Shape = &self.shapes[self.dropped % self.shapes.len()];
+        let mut curr = shape.move_by(Point::new(left_wall_offset, bottom_offset + self.height));
         let mut action = Action::Push;
-
-        for i in 0..count {
-            let shape_idx = i as usize % shapes.len();
-            let shape: &Shape = &shapes[shape_idx];
-            let initial = shape.move_by((left_wall_offset, bottom_offset + bottom));
-            let mut curr = initial;
-            loop {
-                match action {
-                    Action::Push => {
-                        let idx = jet_idx % jets.len();
-                        jet_idx += 1;
-                        let jet_push_x_delta = &jets[idx];
-                        let after_push = curr.move_by((*jet_push_x_delta, 0));
-
-                        if self.can_be_pushed(&after_push, max_width) {
-                            curr = after_push;
-                        }
-                        action = Action::Fall;
+        loop {
+            match action {
+                Action::Push => {
+                    let jet_push_x_delta = jets[self.jet_idx];
+                    self.jet_idx = (self.jet_idx + 1) % jets.len();
+                    let after_push = curr.move_by(Point::new(jet_push_x_delta, 0));
+
+                    if self.can_be_pushed(&after_push, MAX_WIDTH) {
+                        curr = after_push;
                     }
-                    Action::Fall => {
-                        let after_fall = curr.move_by((0, -1));
-
-                        if self.can_fall_down(&after_fall) {
-                            curr = after_fall;
-                            action = Action::Push;
-                        } else {
-                            self.place_shape(&curr);
-                            action = Action::Push;
-                            break;
-                        }
+                    action = Action::Fall;
+                }
+                Action::Fall => {
+                    let after_fall = curr.move_by(Point::new(0, -1));
+
+                    if self.can_fall_down(&after_fall) {
+                        curr = after_fall;
+                        action = Action::Push;
+                    } else {
+                        self.place_shape(&curr);
+                        break;
                     }
                 }
             }
-            bottom = self.max_height();
         }
+        self.dropped += 1;
+    }
+
+    /// Next shape, next jet and the empty cells a falling rock could still reach, as one mask
+    /// per row from the top of the tower down.
+    ///
+    /// Rocks only move sideways and down through empty cells, so every cell they test is one of
+    /// these or blocks them, and the rest of the tower makes no difference. Two states with the
+    /// same fingerprint grow the tower the same way as long as the reachable cells end within
+    /// [`FINGERPRINT_DEPTH`] rows.
+    ///
+    /// Deeper regions are cut off and their depth is not compared, like a column open down to
+    /// the floor because no jet ever pushes a rock into it. Such a region stays out of reach
+    /// for the same reason in every repetition, which the tests check against a plain
+    /// simulation, but it is not proven for every jet pattern.
+    fn fingerprint(&self) -> (usize, usize, Vec<u8>) {
+        let mut reachable: u8 = (1 << MAX_WIDTH) - 1;
+        let mut rows = vec![];
+        for y in ((self.height - FINGERPRINT_DEPTH).max(0)..self.height).rev() {
+            let empty = (0..MAX_WIDTH)
+                .filter(|&x| !self.rocks.contains(&Point::new(x, y)))
+                .fold(0, |row, x| row | 1 << x);
+            // Down from the row above, then sideways until nothing new is reached
+            reachable &= empty;
+            loop {
+                let spread = (reachable | reachable << 1 | reachable >> 1) & empty;
+                if spread == reachable {
+                    break;
+                }
+                reachable = spread;
+            }
+            if reachable == 0 {
+                break;
+            }
+            rows.push(reachable);
+        }
+        (self.dropped % self.shapes.len(), self.jet_idx, rows)
     }
 
     fn max_height(&self) -> i64 {
-        self.rocks.iter().map(|r| r.y).max().unwrap_or(0) + 1
+        self.height
     }
 
     fn place_shape(&mut self, shape: &Shape) {
         shape.points.iter().for_each(|p| {
             self.rocks.insert(*p);
+            self.height = self.height.max(p.y + 1);
         });
     }
 
@@ -168,7 +227,7 @@ fn points_to_string(points: &HashSet<Point>, max_height: i64) -> String {
     for y in (0..max_height).rev() {
         let mut line = "|".to_string();
         for x in 0..7 {
-            let point = Point { x, y };
+            let point = Point::new(x, y);
             if points.contains(&point) {
                 line += "#";
             } else {
@@ -193,97 +252,106 @@ fn all_shapes() -> Vec<Shape> {
         // Horizontal Line
         Shape {
             points: vec![
-                Point { x: 0, y: 0 },
-                Point { x: 1, y: 0 },
-                Point { x: 2, y: 0 },
-                Point { x: 3, y: 0 },
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(3, 0),
             ],
         },
         // Cross
         Shape {
             points: vec![
-                Point { x: 0, y: 1 },
-                Point { x: 1, y: 1 },
-                Point { x: 2, y: 1 },
-                Point { x: 1, y: 0 },
-                Point { x: 1, y: 2 },
+                Point::new(0, 1),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(1, 0),
+                Point::new(1, 2),
             ],
         },
         // L
         Shape {
             points: vec![
-                Point { x: 0, y: 0 },
-                Point { x: 1, y: 0 },
-                Point { x: 2, y: 0 },
-                Point { x: 2, y: 1 },
-                Point { x: 2, y: 2 },
+                Point::new(0, 0),
+                Point::new(1, 0),
+                Point::new(2, 0),
+                Point::new(2, 1),
+                Point::new(2, 2),
             ],
         },
         // Vertical Line
         Shape {
             points: vec![
-                Point { x: 0, y: 0 },
-                Point { x: 0, y: 1 },
-                Point { x: 0, y: 2 },
-                Point { x: 0, y: 3 },
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(0, 2),
+                Point::new(0, 3),
             ],
         },
         // Fat square
         Shape {
             points: vec![
-                Point { x: 0, y: 0 },
-                Point { x: 0, y: 1 },
-                Point { x: 1, y: 0 },
-                Point { x: 1, y: 1 },
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(1, 0),
+                Point::new(1, 1),
             ],
         },
     ]
 }
 
-fn parse_jets(input: &str) -> Vec<i64> {
-    input
-        .trim()
+fn parse_jets(input: &str) -> Result<Vec<i64>, SolveError> {
+    let jets = input.trim();
+    if let Some(invalid) = find_invalid(jets, |c| c == '>' || c == '<') {
+        return Err(SolveError::at(input, invalid));
+    }
+    if jets.is_empty() {
+        return Err(SolveError::NoSolution);
+    }
+
+    Ok(jets
         .chars()
-        .map(|c| match c {
-            '>' => 1,
-            '<' => -1,
-            _ => panic!("Unexpected character: {c}"),
-        })
-        .collect()
+        .map(|c| if c == '>' { 1 } else { -1 })
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 17;
-
     #[test]
-    fn test_part_1() {
+    fn test_tower_height() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
+        let jets = Day17::parse(Day17::EXAMPLES[0].input).unwrap();
 
         // then
-        let expected = "3137";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(tower_height(&jets, 0), 0);
+        assert_eq!(tower_height(&jets, 1), 1);
+        assert_eq!(tower_height(&jets, 10), 17);
+        assert_eq!(tower_height(&jets, 2022), 3068);
+        assert_eq!(tower_height(&jets, 1_000_000_000_000), 1514285714288);
     }
 
     #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
+    fn test_cycle_matches_simulation() {
+        // Jets that leave columns open down to the floor, or push every rock to one side
+        for pattern in ["<", ">", "<>", "<<>", "<>>>", "<<<<<<>", "><<>>><<<>"] {
+            // given
+            let jets = Day17::parse(pattern).unwrap();
+            let mut tetris = Rocktris::new();
+            let mut heights = vec![0];
+            for _ in 0..3000 {
+                tetris.drop_rock(&jets);
+                heights.push(tetris.max_height());
+            }
 
-        // then
-        let expected = "1564705882327";
-        assert_eq!(result, expected.to_string());
+            // then
+            for rocks in [500, 1234, 2022, 3000] {
+                assert_eq!(
+                    tower_height(&jets, rocks),
+                    heights[rocks as usize],
+                    "jets {pattern}, {rocks} rocks"
+                );
+            }
+        }
     }
 }
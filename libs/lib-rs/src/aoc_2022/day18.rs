@@ -1,108 +1,85 @@
 use std::collections::{HashSet, VecDeque};
 
-use crate::common::parse::{parse_numbers, to_non_empty_lines};
-
-pub fn run_first(input: &str) -> String {
-    let points = parse_input(input);
-    surface_area(&points).to_string()
-}
-
-pub fn run_second(input: &str) -> String {
-    let points = parse_input(input);
-    exterior_surface_area(&points).to_string()
-}
-
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-struct Point3D {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-
-impl Point3D {
-    fn move_by(&self, vector: &(i64, i64, i64)) -> Point3D {
-        let (x, y, z) = vector;
-
-        Point3D {
-            x: self.x + x,
-            y: self.y + y,
-            z: self.z + z,
-        }
+use crate::common::geom::Point3;
+use crate::common::parse::parse_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day18;
+
+const EXAMPLE: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+impl Solution for Day18 {
+    type Input = HashSet<Point>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("64").second("58")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_input(input)
     }
 
-    fn min(&self) -> i64 {
-        i64::min(self.x, i64::min(self.y, self.z))
+    fn part_one(points: &Self::Input) -> Result<String, SolveError> {
+        Ok(surface_area(points).to_string())
     }
 
-    fn max(&self) -> i64 {
-        i64::max(self.x, i64::max(self.y, self.z))
+    fn part_two(points: &Self::Input) -> Result<String, SolveError> {
+        Ok(exterior_surface_area(points).to_string())
     }
+}
 
-    fn in_bounds(&self, min: i64, max: i64) -> bool {
-        let range = min..=max;
-        range.contains(&self.x) && range.contains(&self.y) && range.contains(&self.z)
-    }
+type Point = Point3<i64>;
 
-    fn surrounding(&self) -> Vec<Point3D> {
-        let deltas: Vec<(i64, i64, i64)> = vec![
-            (1, 0, 0),
-            (-1, 0, 0),
-            (0, 1, 0),
-            (0, -1, 0),
-            (0, 0, 1),
-            (0, 0, -1),
-        ];
-
-        deltas.iter().map(|d| self.move_by(d)).collect()
-    }
+fn in_bounds(point: &Point, min: i64, max: i64) -> bool {
+    let range = min..=max;
+    range.contains(&point.x) && range.contains(&point.y) && range.contains(&point.z)
 }
 
-fn surface_area(points: &HashSet<Point3D>) -> i64 {
-    let deltas: Vec<(i64, i64, i64)> = vec![
-        (1, 0, 0),
-        (-1, 0, 0),
-        (0, 1, 0),
-        (0, -1, 0),
-        (0, 0, 1),
-        (0, 0, -1),
-    ];
-
+fn surface_area(points: &HashSet<Point>) -> i64 {
     points
         .iter()
-        .map(|p| {
-            deltas
-                .iter()
-                .filter(|&d| !points.contains(&p.move_by(d)))
-                .count() as i64
-        })
+        .map(|p| p.neighbours6().filter(|n| !points.contains(n)).count() as i64)
         .sum()
 }
 
-fn exterior_surface_area(points: &HashSet<Point3D>) -> i64 {
-    let min = points.iter().map(|p| p.min()).min().unwrap() - 1;
-    let max = points.iter().map(|p| p.max()).max().unwrap() + 1;
+fn exterior_surface_area(points: &HashSet<Point>) -> i64 {
+    let min = points
+        .iter()
+        .map(|p| p.x.min(p.y).min(p.z))
+        .min()
+        .unwrap_or(0)
+        - 1;
+    let max = points
+        .iter()
+        .map(|p| p.x.max(p.y).max(p.z))
+        .max()
+        .unwrap_or(0)
+        + 1;
 
     let mut surface_size = 0_i64;
-    let mut seen: HashSet<Point3D> = HashSet::new();
-    let mut to_check: VecDeque<Point3D> = VecDeque::new();
-
-    seen.insert(Point3D {
-        x: min,
-        y: min,
-        z: min,
-    });
-    to_check.push_back(Point3D {
-        x: min,
-        y: min,
-        z: min,
-    });
-
-    while !to_check.is_empty() {
-        let curr = to_check.pop_front().unwrap();
+    let mut seen: HashSet<Point> = HashSet::new();
+    let mut to_check: VecDeque<Point> = VecDeque::new();
+
+    seen.insert(Point::new(min, min, min));
+    to_check.push_back(Point::new(min, min, min));
+
+    while let Some(curr) = to_check.pop_front() {
         let surrounding: HashSet<_> = curr
-            .surrounding()
-            .into_iter()
-            .filter(|p| p.in_bounds(min, max) && !seen.contains(p))
+            .neighbours6()
+            .filter(|p| in_bounds(p, min, max) && !seen.contains(p))
             .collect();
 
         let intersecting: HashSet<_> = surrounding
@@ -121,53 +98,13 @@ fn exterior_surface_area(points: &HashSet<Point3D>) -> i64 {
     surface_size
 }
 
-fn parse_input(input: &str) -> HashSet<Point3D> {
-    to_non_empty_lines(input)
-        .iter()
-        .map(|l| {
-            let nums = parse_numbers(l);
-
-            Point3D {
-                x: nums[0],
-                y: nums[1],
-                z: nums[2],
-            }
+fn parse_input(input: &str) -> Result<HashSet<Point>, SolveError> {
+    input
+        .split('\n')
+        .filter(|l| !l.is_empty())
+        .map(|l| match parse_numbers(l)[..] {
+            [x, y, z] => Ok(Point::new(x, y, z)),
+            _ => Err(SolveError::at(input, l)),
         })
         .collect()
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 18;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "3576";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "2066";
-        assert_eq!(result, expected.to_string());
-    }
-}
@@ -1,24 +1,41 @@
 use std::collections::HashMap;
 
-use crate::common::parse::{parse_numbers, to_non_empty_lines};
-
-pub fn run_first(input: &str) -> String {
-    let blueprints = parse_input(input);
-    blueprints
-        .iter()
-        .map(|&b| b.id() * find_max(24, b))
-        .sum::<i64>()
-        .to_string()
-}
+use crate::common::parse::parse_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day19;
+
+const EXAMPLE: &str = "Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore. Each obsidian robot costs 3 ore and 14 clay. Each geode robot costs 2 ore and 7 obsidian.
+Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsidian robot costs 3 ore and 8 clay. Each geode robot costs 3 ore and 12 obsidian.";
 
-pub fn run_second(input: &str) -> String {
-    let blueprints = parse_input(input);
-    blueprints
-        .iter()
-        .take(3)
-        .map(|&b| find_max(32, b))
-        .product::<i64>()
-        .to_string()
+impl Solution for Day19 {
+    type Input = Vec<Blueprint>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("33").second("3472")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_input(input)
+    }
+
+    fn part_one(blueprints: &Self::Input) -> Result<String, SolveError> {
+        Ok(blueprints
+            .iter()
+            .map(|&b| b.id() * find_max(24, b))
+            .sum::<i64>()
+            .to_string())
+    }
+
+    fn part_two(blueprints: &Self::Input) -> Result<String, SolveError> {
+        Ok(blueprints
+            .iter()
+            .take(3)
+            .map(|&b| find_max(32, b))
+            .product::<i64>()
+            .to_string())
+    }
 }
 
 fn find_max(time: i64, blueprint: Blueprint) -> i64 {
@@ -35,7 +52,7 @@ fn find_max(time: i64, blueprint: Blueprint) -> i64 {
         if time == 0 {
             num_geodes = num_geodes.max(ores.geode());
             continue;
-        } else if ores.geode() + robots.geode() * time + time * time < num_geodes {
+        } else if ores.geode() + robots.geode() * time + time * (time - 1) / 2 <= num_geodes {
             continue;
         } else if let Some(&t) = visited.get(&(robots, ores)) {
             if time > t {
@@ -96,7 +113,7 @@ enum Mineral {
 }
 
 #[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
-struct Blueprint([i64; 7]);
+pub struct Blueprint([i64; 7]);
 
 impl Blueprint {
     fn id(&self) -> i64 {
@@ -246,45 +263,15 @@ impl Ores {
     }
 }
 
-fn parse_input(input: &str) -> Vec<Blueprint> {
-    to_non_empty_lines(input)
-        .iter()
-        .map(|l| Blueprint(parse_numbers(l).try_into().unwrap()))
+fn parse_input(input: &str) -> Result<Vec<Blueprint>, SolveError> {
+    input
+        .split('\n')
+        .filter(|l| !l.is_empty())
+        .map(|l| {
+            parse_numbers(l)
+                .try_into()
+                .map(Blueprint)
+                .map_err(|_| SolveError::at(input, l))
+        })
         .collect()
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 19;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "1528";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "16926";
-        assert_eq!(result, expected.to_string());
-    }
-}
@@ -1,80 +1,280 @@
-use std::collections::VecDeque;
-
 use crate::common::parse::parse_signed_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day20;
+
+const EXAMPLE: &str = "1
+2
+-3
+3
+-2
+0
+4";
+
+impl Solution for Day20 {
+    type Input = Vec<i64>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("3").second("1623178306")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        Ok(parse_signed_numbers(input))
+    }
 
-pub fn run_first(input: &str) -> String {
-    let initial_numbers = parse_signed_numbers(input);
-    mix_sum(1, &initial_numbers).to_string()
+    fn part_one(numbers: &Self::Input) -> Result<String, SolveError> {
+        grove_sum(&mix(numbers, 1, 1)?)
+            .map(|sum| sum.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
+
+    fn part_two(numbers: &Self::Input) -> Result<String, SolveError> {
+        grove_sum(&mix(numbers, 811589153, 10)?)
+            .map(|sum| sum.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
 }
 
-pub fn run_second(input: &str) -> String {
-    let initial_numbers: Vec<_> = parse_signed_numbers(input)
+/// Multiplies `values` by `key` and mixes them `rounds` times, returning them in their final
+/// order.
+///
+/// Every value moves in its original order by as many places as it is worth. A value moved to
+/// the front of the list is put at its end, like in the puzzle.
+pub fn mix(values: &[i64], key: i64, rounds: usize) -> Result<Vec<i64>, SolveError> {
+    let values = values
         .iter()
-        .map(|n| n * 811589153)
-        .collect();
-    mix_sum(10, &initial_numbers).to_string()
+        .map(|value| value.checked_mul(key).ok_or(SolveError::Overflow))
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut order = OrderTree::new(values.len());
+    // Places a value can land on once taken out of the list
+    let places = values.len() as i64 - 1;
+    if places > 0 {
+        for _ in 0..rounds {
+            for (id, &value) in values.iter().enumerate() {
+                let from = order.remove(id).ok_or(SolveError::NoSolution)?;
+                let to = (from as i64 + value.rem_euclid(places)) % places;
+                order.insert(if to == 0 { places } else { to } as usize, id);
+            }
+        }
+    }
+    Ok(order.to_vec().into_iter().map(|id| values[id]).collect())
+}
+
+/// Sum of the values 1000, 2000 and 3000 places after the `0`, wrapping around.
+fn grove_sum(values: &[i64]) -> Option<i64> {
+    let zero_idx = values.iter().position(|&value| value == 0)?;
+    Some(
+        [1000, 2000, 3000]
+            .iter()
+            .map(|idx| values[(zero_idx + idx) % values.len()])
+            .sum(),
+    )
+}
+
+/// The ids `0..len` in a changing order, kept in an order-statistic tree.
+///
+/// The tree is a treap keyed by position: every id is a node that knows its parent and the
+/// size of its subtree, so the position of an id is found by walking up to the root. Removing
+/// and inserting split and merge the tree at a position. All of it takes O(log n) expected.
+struct OrderTree {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+    size: usize,
+    /// Heap order on these keeps the tree balanced in expectation.
+    priority: u64,
+}
+
+impl Node {
+    fn new(id: usize) -> Self {
+        // splitmix64, a fixed shuffle keeps the mixing deterministic
+        let mut priority = (id as u64).wrapping_add(0x9e37_79b9_7f4a_7c15);
+        priority = (priority ^ (priority >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        priority = (priority ^ (priority >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        Node {
+            left: None,
+            right: None,
+            parent: None,
+            size: 1,
+            priority: priority ^ (priority >> 31),
+        }
+    }
 }
 
-fn mix_sum(times: usize, vec: &[i64]) -> i64 {
-    let mut deque: VecDeque<_> = vec.iter().cloned().enumerate().collect();
-
-    for _ in 0..times {
-        for (i, &n) in vec.iter().enumerate() {
-            let idx = deque.iter().position(|&(a, b)| a == i && b == n).unwrap();
-            deque.rotate_left(idx);
-            deque.pop_front();
-            if n <= 0 {
-                deque.rotate_right((n.abs() % deque.len() as i64) as usize);
-            } else {
-                deque.rotate_left((n % deque.len() as i64) as usize);
+impl OrderTree {
+    fn new(len: usize) -> Self {
+        let mut tree = OrderTree {
+            nodes: (0..len).map(Node::new).collect(),
+            root: None,
+        };
+        for id in 0..len {
+            tree.root = tree.merge(tree.root, Some(id));
+        }
+        tree.detach(tree.root);
+        tree
+    }
+
+    /// Takes `id` out of the list, returning the position it was at.
+    fn remove(&mut self, id: usize) -> Option<usize> {
+        self.nodes.get(id)?;
+        let position = self.position(id);
+        let (before, rest) = self.split(self.root, position);
+        let (_, after) = self.split(rest, 1);
+        self.root = self.merge(before, after);
+        self.detach(self.root);
+        self.nodes[id] = Node::new(id);
+        Some(position)
+    }
+
+    /// Puts `id`, taken out before, at `position`, after the ids before it.
+    fn insert(&mut self, position: usize, id: usize) {
+        let (before, after) = self.split(self.root, position);
+        let before = self.merge(before, Some(id));
+        self.root = self.merge(before, after);
+        self.detach(self.root);
+    }
+
+    /// Ids before `id`, counted on the way up to the root.
+    fn position(&self, id: usize) -> usize {
+        let mut position = self.size(self.nodes[id].left);
+        let mut node = id;
+        while let Some(parent) = self.nodes[node].parent {
+            if self.nodes[parent].right == Some(node) {
+                position += self.size(self.nodes[parent].left) + 1;
             }
-            deque.push_back((i, n));
+            node = parent;
         }
+        position
     }
 
-    let values: Vec<_> = deque.into_iter().map(|(_, value)| value).collect();
+    /// Splits `tree` into its first `count` ids and the rest.
+    fn split(&mut self, tree: Option<usize>, count: usize) -> (Option<usize>, Option<usize>) {
+        let Some(node) = tree else {
+            return (None, None);
+        };
+        let left_size = self.size(self.nodes[node].left);
+        if count <= left_size {
+            let (before, after) = self.split(self.nodes[node].left, count);
+            self.nodes[node].left = after;
+            self.update(node);
+            (before, Some(node))
+        } else {
+            let (before, after) = self.split(self.nodes[node].right, count - left_size - 1);
+            self.nodes[node].right = before;
+            self.update(node);
+            (Some(node), after)
+        }
+    }
 
-    vec![1000, 2000, 3000]
-        .iter()
-        .map(|idx| {
-            let pos = values.iter().position(|&a| a == 0).unwrap();
-            values[(pos + idx) % values.len()]
-        })
-        .sum::<i64>()
+    /// Joins two trees, every id of `left` before every id of `right`.
+    fn merge(&mut self, left: Option<usize>, right: Option<usize>) -> Option<usize> {
+        let (left, right) = match (left, right) {
+            (None, tree) | (tree, None) => return tree,
+            (Some(left), Some(right)) => (left, right),
+        };
+        if self.nodes[left].priority > self.nodes[right].priority {
+            self.nodes[left].right = self.merge(self.nodes[left].right, Some(right));
+            self.update(left);
+            Some(left)
+        } else {
+            self.nodes[right].left = self.merge(Some(left), self.nodes[right].left);
+            self.update(right);
+            Some(right)
+        }
+    }
+
+    /// Recounts the size of `node` and points its children back at it.
+    fn update(&mut self, node: usize) {
+        let Node { left, right, .. } = self.nodes[node];
+        self.nodes[node].size = 1 + self.size(left) + self.size(right);
+        for child in [left, right].into_iter().flatten() {
+            self.nodes[child].parent = Some(node);
+        }
+    }
+
+    /// Clears the parent a split may have left on a new root.
+    fn detach(&mut self, root: Option<usize>) {
+        if let Some(root) = root {
+            self.nodes[root].parent = None;
+        }
+    }
+
+    fn size(&self, tree: Option<usize>) -> usize {
+        tree.map_or(0, |node| self.nodes[node].size)
+    }
+
+    /// Ids in their current order.
+    fn to_vec(&self) -> Vec<usize> {
+        let mut ids = Vec::with_capacity(self.nodes.len());
+        let mut stack = vec![];
+        let mut node = self.root;
+        while node.is_some() || !stack.is_empty() {
+            while let Some(current) = node {
+                stack.push(current);
+                node = self.nodes[current].left;
+            }
+            if let Some(current) = stack.pop() {
+                ids.push(current);
+                node = self.nodes[current].right;
+            }
+        }
+        ids
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::read_to_string;
-
     use super::*;
 
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 20;
-
     #[test]
-    fn test_part_1() {
+    fn test_mix() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let numbers = Day20::parse(EXAMPLE).unwrap();
 
         // when
-        let result = run_first(&data);
+        let once = mix(&numbers, 1, 1).unwrap();
+        let decrypted = mix(&numbers, 811589153, 10).unwrap();
 
         // then
-        let expected = "17490";
-        assert_eq!(result, expected.to_string());
+        assert_eq!(once, vec![1, 2, -3, 4, 0, 3, -2]);
+        assert_eq!(grove_sum(&once), Some(3));
+        assert_eq!(grove_sum(&decrypted), Some(1623178306));
     }
 
     #[test]
-    fn test_part_2() {
+    fn test_order_tree_matches_vec() {
         // given
-        let data = read_to_string(YEAR, DAY, false);
+        let mut tree = OrderTree::new(50);
+        let mut ids: Vec<usize> = (0..50).collect();
 
-        // when
-        let result = run_second(&data);
+        for step in 0..500 {
+            // when
+            let id = step * 7 % 50;
+            let to = step * 13 % 50;
+            let from = ids.iter().position(|&other| other == id).unwrap();
+            ids.remove(from);
+            ids.insert(to.min(ids.len()), id);
 
-        // then
-        let expected = "1632917375836";
-        assert_eq!(result, expected.to_string());
+            // then
+            assert_eq!(tree.remove(id), Some(from));
+            tree.insert(to.min(49), id);
+        }
+        assert_eq!(tree.to_vec(), ids);
+    }
+
+    #[test]
+    fn test_mix_edge_cases() {
+        assert_eq!(mix(&[], 1, 1), Ok(vec![]));
+        assert_eq!(mix(&[5], 1, 3), Ok(vec![5]));
+        assert_eq!(mix(&[i64::MAX, 0], 2, 1), Err(SolveError::Overflow));
+        // Moving by a whole lap of the other values ends where it started
+        assert_eq!(mix(&[0, 4, 2], 1, 1), Ok(vec![0, 4, 2]));
     }
 }
@@ -1,205 +1,190 @@
-use crate::common::parse::to_non_empty_lines;
+use crate::common::parse::parse_value;
 use std::collections::HashMap;
 
-pub fn run_first(input: &str) -> String {
-    let monkeys = parse_monkeys(input);
-    solve(&monkeys, "root").to_string()
-}
-
-pub fn run_second(input: &str) -> String {
-    let monkeys = parse_monkeys(input);
-    bounded_solve(&monkeys).to_string()
-}
-
-fn bounded_solve(monkeys: &Vec<Monkey>) -> i64 {
-    let mut delta = 100_000_000_000;
-    let mut bound = 0;
-    let mut sign = 1;
-
-    let mut solution = 0;
-
-    'outer: while delta >= 1 {
-        for num in 1..=10 {
-            let next = bound + num * delta * sign;
-            let result = solve_for_eq(monkeys, "root", next);
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day21;
+
+const EXAMPLE: &str = "root: pppw + sjmn
+dbpl: 5
+cczh: sllz + lgvd
+zczc: 2
+ptdq: humn - dvpt
+dvpt: 3
+lfqf: 4
+humn: 5
+ljgn: 2
+sjmn: drzm * dbpl
+sllz: 4
+pppw: cczh / lfqf
+lgvd: ljgn * ptdq
+drzm: hmdt - zczc
+hmdt: 32";
+
+impl Solution for Day21 {
+    type Input = Vec<Monkey>;
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("152").second("301")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        parse_monkeys(input)
+    }
 
-            if result == 0 {
-                solution = next;
-                break 'outer;
-            }
+    fn part_one(monkeys: &Self::Input) -> Result<String, SolveError> {
+        Ok(solve(monkeys, "root")?.to_string())
+    }
 
-            if sign.signum() != result.signum() {
-                bound = next;
-                break;
-            }
-            bound = next;
-        }
+    fn part_two(monkeys: &Self::Input) -> Result<String, SolveError> {
+        Ok(solve_humn(monkeys)?.to_string())
+    }
+}
 
-        if delta > 1 {
-            delta /= 10;
+/// Walks from `root` down to `humn`, undoing one operation per monkey on the way.
+fn solve_humn(monkeys: &[Monkey]) -> Result<i64, SolveError> {
+    let by_name: HashMap<&str, &Monkey> = monkeys.iter().map(|m| (m.name.as_str(), m)).collect();
+    let needs = |name: &str| match by_name.get(name) {
+        Some(Monkey { needs, .. }) if needs.len() == 2 => {
+            Ok((needs[0].as_str(), needs[1].as_str()))
         }
-
-        sign *= -1;
+        _ => Err(SolveError::NoSolution),
+    };
+
+    let (left, right) = needs("root")?;
+    let (mut name, mut target) = match (evaluate(&by_name, left)?, evaluate(&by_name, right)?) {
+        (None, Some(value)) => (left, value),
+        (Some(value), None) => (right, value),
+        _ => return Err(SolveError::NoSolution),
+    };
+
+    while name != "humn" {
+        let (left, right) = needs(name)?;
+        let operation = by_name[name].operation;
+        (name, target) = match (evaluate(&by_name, left)?, evaluate(&by_name, right)?) {
+            (None, Some(right)) => (left, undo_left(operation, target, right)?),
+            (Some(left), None) => (right, undo_right(operation, left, target)?),
+            _ => return Err(SolveError::NoSolution),
+        };
     }
+    Ok(target)
+}
 
-    solution
+/// Value yelled by `name`, `None` when it depends on what `humn` yells.
+fn evaluate(by_name: &HashMap<&str, &Monkey>, name: &str) -> Result<Option<i64>, SolveError> {
+    if name == "humn" {
+        return Ok(None);
+    }
+    let monkey = by_name.get(name).ok_or(SolveError::NoSolution)?;
+    if let Some(value) = monkey.value {
+        return Ok(Some(value));
+    }
+    let [left, right] = &monkey.needs[..] else {
+        return Err(SolveError::NoSolution);
+    };
+    match (evaluate(by_name, left)?, evaluate(by_name, right)?) {
+        (Some(left), Some(right)) => apply(monkey.operation, left, right).map(Some),
+        _ => Ok(None),
+    }
 }
 
-fn solve(monkeys: &Vec<Monkey>, name: &str) -> i64 {
-    let mut solved: HashMap<_, _> = monkeys
-        .iter()
-        .filter(|m| m.value.is_some())
-        .map(|m| (m.name.clone(), m.value.unwrap()))
-        .collect();
+/// Solves `x op right = target` for `x`.
+fn undo_left(operation: char, target: i64, right: i64) -> Result<i64, SolveError> {
+    match operation {
+        '+' => apply('-', target, right),
+        '-' => apply('+', target, right),
+        '*' if right == 0 || target % right != 0 => Err(SolveError::NoSolution),
+        '*' => apply('/', target, right),
+        _ => apply('*', target, right),
+    }
+}
 
-    while solved.get(name).is_none() {
-        for m in monkeys {
-            if solved.contains_key(&m.name) {
-                continue;
-            }
-            let can_solve = m.needs.iter().all(|n| solved.contains_key(n));
-            if can_solve {
-                let left = *solved.get(&m.needs[0]).unwrap();
-                let right = *solved.get(&m.needs[1]).unwrap();
-
-                let result = match m.operation {
-                    '+' => left + right,
-                    '-' => left - right,
-                    '*' => left * right,
-                    '/' => left / right,
-                    _ => panic!("Unexpected operation: {}", m.operation),
-                };
-
-                solved.insert(m.name.clone(), result);
-            }
-        }
+/// Solves `left op x = target` for `x`.
+fn undo_right(operation: char, left: i64, target: i64) -> Result<i64, SolveError> {
+    match operation {
+        '+' => apply('-', target, left),
+        '-' => apply('-', left, target),
+        '*' if left == 0 || target % left != 0 => Err(SolveError::NoSolution),
+        '*' => apply('/', target, left),
+        _ => apply('/', left, target),
     }
+}
 
-    *solved.get(name).unwrap()
+fn solve(monkeys: &[Monkey], name: &str) -> Result<i64, SolveError> {
+    let solved = solve_all(monkeys, name)?;
+    Ok(solved[name])
 }
 
-fn solve_for_eq(monkeys: &Vec<Monkey>, name: &str, number: i64) -> i64 {
+/// Solves monkeys until `name` is known.
+fn solve_all(monkeys: &[Monkey], name: &str) -> Result<HashMap<String, i64>, SolveError> {
     let mut solved: HashMap<_, _> = monkeys
         .iter()
-        .filter(|m| m.value.is_some())
-        .map(|m| {
-            if m.name == "humn" {
-                (m.name.clone(), number)
-            } else {
-                (m.name.clone(), m.value.unwrap())
-            }
-        })
+        .filter_map(|m| m.value.map(|value| (m.name.clone(), value)))
         .collect();
 
-    while solved.get(name).is_none() {
+    while !solved.contains_key(name) {
+        let solved_before = solved.len();
         for m in monkeys {
             if solved.contains_key(&m.name) {
                 continue;
             }
-            let can_solve = m.needs.iter().all(|n| solved.contains_key(n));
-            if can_solve {
-                let left = *solved.get(&m.needs[0]).unwrap();
-                let right = *solved.get(&m.needs[1]).unwrap();
-
-                let result = match m.operation {
-                    '+' => left + right,
-                    '-' => left - right,
-                    '*' => left * right,
-                    '/' => left / right,
-                    _ => panic!("Unexpected operation: {}", m.operation),
-                };
-
-                solved.insert(m.name.clone(), result);
+            if let [left, right] = &m.needs[..] {
+                if let (Some(&left), Some(&right)) = (solved.get(left), solved.get(right)) {
+                    solved.insert(m.name.clone(), apply(m.operation, left, right)?);
+                }
             }
         }
+        if solved.len() == solved_before {
+            return Err(SolveError::NoSolution);
+        }
     }
 
-    let root = monkeys.iter().find(|m| m.name == "root").unwrap();
-    let left = *solved.get(&root.needs[0]).unwrap();
-    let right = *solved.get(&root.needs[1]).unwrap();
+    Ok(solved)
+}
 
-    left - right
+fn apply(operation: char, left: i64, right: i64) -> Result<i64, SolveError> {
+    match operation {
+        '+' => left.checked_add(right),
+        '-' => left.checked_sub(right),
+        '*' => left.checked_mul(right),
+        _ if right == 0 => return Err(SolveError::NoSolution),
+        _ => left.checked_div(right),
+    }
+    .ok_or(SolveError::Overflow)
 }
 
 #[derive(Debug)]
-struct Monkey {
+pub struct Monkey {
     name: String,
     value: Option<i64>,
     needs: Vec<String>,
     operation: char,
 }
 
-fn parse_monkeys(input: &str) -> Vec<Monkey> {
-    let lines = to_non_empty_lines(input);
-
-    lines
-        .iter()
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, SolveError> {
+    input
+        .split('\n')
+        .filter(|l| !l.is_empty())
         .map(|l| {
-            let tokens: Vec<_> = l.split(": ").collect();
-
-            let name = tokens[0];
-            let op_tokens: Vec<_> = tokens[1].split(' ').filter(|t| !t.is_empty()).collect();
-
-            match op_tokens.len() {
-                1 => {
-                    let value = op_tokens[0].parse::<i64>().unwrap();
-                    Monkey {
-                        name: name.to_string(),
-                        needs: vec![],
-                        value: Some(value),
-                        operation: ' ',
-                    }
-                }
-                3 => {
-                    let left_op = op_tokens[0];
-                    let op = op_tokens[1];
-                    let right_op = op_tokens[2];
-
-                    Monkey {
-                        name: name.to_string(),
-                        needs: vec![left_op.to_string(), right_op.to_string()],
-                        value: None,
-                        operation: op.chars().next().unwrap(),
-                    }
-                }
-                _ => panic!("Unexpected tokens: {op_tokens:?}"),
+            let (name, job) = l.split_once(": ").ok_or_else(|| SolveError::at(input, l))?;
+            let op_tokens: Vec<_> = job.split(' ').filter(|t| !t.is_empty()).collect();
+
+            match op_tokens[..] {
+                [value] => Ok(Monkey {
+                    name: name.to_string(),
+                    needs: vec![],
+                    value: Some(parse_value(input, value)?),
+                    operation: ' ',
+                }),
+                [left_op, op @ ("+" | "-" | "*" | "/"), right_op] => Ok(Monkey {
+                    name: name.to_string(),
+                    needs: vec![left_op.to_string(), right_op.to_string()],
+                    value: None,
+                    operation: op.chars().next().unwrap_or(' '),
+                }),
+                _ => Err(SolveError::at(input, job)),
             }
         })
         .collect()
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::utils::read_to_string;
-
-    use super::*;
-
-    const YEAR: u32 = 2022;
-    const DAY: u8 = 21;
-
-    #[test]
-    fn test_part_1() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_first(&data);
-
-        // then
-        let expected = "145167969204648";
-        assert_eq!(result, expected.to_string());
-    }
-
-    #[test]
-    fn test_part_2() {
-        // given
-        let data = read_to_string(YEAR, DAY, false);
-
-        // when
-        let result = run_second(&data);
-
-        // then
-        let expected = "3330805295850";
-        assert_eq!(result, expected.to_string());
-    }
-}
@@ -2,76 +2,102 @@ const DIGITS: [&str; 9] = [
     "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
 ];
 
-pub fn run_first(input: &str) -> String {
-    input
-        .split('\n')
-        .map(|s| s.to_string())
-        .map(|l| {
-            let digits_only = l.chars().filter(|c| c.is_ascii_digit()).collect::<String>();
-            let first_digit = digits_only.chars().next().unwrap();
-            let last_digit = digits_only.chars().last().unwrap();
-
-            let num = str::parse::<i64>(format!("{}{}", first_digit, last_digit).as_str()).unwrap();
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-            num
-        })
-        .sum::<i64>()
-        .to_string()
-}
+pub struct Day01;
 
-pub fn run_second(input: &str) -> String {
-    input
-        .split('\n')
-        .map(|s| s.to_string())
-        .map(|l| {
-            let first_digit = find_first_digit(&l);
-            let last_digit = find_last_digit(&l);
+const EXAMPLE: &str = "1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet";
 
-            let num = str::parse::<i64>(format!("{}{}", first_digit, last_digit).as_str()).unwrap();
+const SPELLED_EXAMPLE: &str = "two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen";
 
-            num
-        })
-        .sum::<i64>()
-        .to_string()
-}
+impl Solution for Day01 {
+    type Input = Vec<String>;
+    type Params = ();
 
-fn find_first_digit(line: &str) -> i64 {
-    let digits_str = vec![
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    const EXAMPLES: &'static [Example] = &[
+        Example::new(EXAMPLE).first("142"),
+        Example::new(SPELLED_EXAMPLE).second("281"),
     ];
 
-    for digit in digits_str {
-        if line.starts_with(digit) {
-            return get_digit_value(digit).unwrap();
-        }
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        Ok(input
+            .split('\n')
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect())
     }
 
-    match line[0..1].parse() {
-        Ok(val) => val,
-        Err(_) => find_first_digit(&line[1..]),
+    fn part_one(lines: &Self::Input) -> Result<String, SolveError> {
+        sum_calibration_digits(lines)
     }
-}
 
-fn find_last_digit(line: &str) -> i64 {
-    for digit in DIGITS {
-        if line.ends_with(digit) {
-            return get_digit_value(digit).unwrap();
-        }
+    fn part_two(lines: &Self::Input) -> Result<String, SolveError> {
+        sum_calibration_words(lines)
     }
+}
 
-    let last_c = &line[line.len() - 1..];
+fn sum_calibration_digits(lines: &[String]) -> Result<String, SolveError> {
+    Ok(lines
+        .iter()
+        .map(|l| {
+            let mut digits = l.chars().filter_map(|c| c.to_digit(10));
+            let first_digit = digits.next().ok_or(SolveError::NoSolution)?;
+            let last_digit = digits.next_back().unwrap_or(first_digit);
 
-    match last_c.parse() {
-        Ok(val) => val,
-        Err(_) => {
-            let new_line = &line[0..line.len() - 1];
-            find_last_digit(new_line)
-        }
-    }
+            Ok((first_digit * 10 + last_digit) as i64)
+        })
+        .sum::<Result<i64, SolveError>>()?
+        .to_string())
+}
+
+fn sum_calibration_words(lines: &[String]) -> Result<String, SolveError> {
+    Ok(lines
+        .iter()
+        .map(|l| {
+            let first_digit = find_first_digit(l).ok_or(SolveError::NoSolution)?;
+            let last_digit = find_last_digit(l).ok_or(SolveError::NoSolution)?;
+
+            Ok(first_digit * 10 + last_digit)
+        })
+        .sum::<Result<i64, SolveError>>()?
+        .to_string())
+}
+
+fn find_first_digit(line: &str) -> Option<i64> {
+    line.char_indices().find_map(|(idx, c)| {
+        let rest = &line[idx..];
+        DIGITS
+            .iter()
+            .find(|&&digit| rest.starts_with(digit))
+            .and_then(|digit| get_digit_value(digit))
+            .or_else(|| c.to_digit(10).map(i64::from))
+    })
+}
+
+fn find_last_digit(line: &str) -> Option<i64> {
+    line.char_indices().rev().find_map(|(idx, c)| {
+        let rest = &line[..idx + c.len_utf8()];
+        DIGITS
+            .iter()
+            .find(|&&digit| rest.ends_with(digit))
+            .and_then(|digit| get_digit_value(digit))
+            .or_else(|| c.to_digit(10).map(i64::from))
+    })
 }
 
 fn get_digit_value(digit: &str) -> Option<i64> {
-    let idx = DIGITS.iter().position(|&d| d == digit).unwrap();
+    let idx = DIGITS.iter().position(|&d| d == digit)?;
     Some((idx + 1) as i64)
 }
 
@@ -81,52 +107,30 @@ mod tests {
 
     #[test]
     fn test_find_first_digit() {
-        assert_eq!(find_first_digit("one"), 1);
-        assert_eq!(find_first_digit("two"), 2);
-        assert_eq!(find_first_digit("three"), 3);
-        assert_eq!(find_first_digit("four"), 4);
-        assert_eq!(find_first_digit("five"), 5);
-        assert_eq!(find_first_digit("six"), 6);
-        assert_eq!(find_first_digit("seven"), 7);
-        assert_eq!(find_first_digit("eight"), 8);
-        assert_eq!(find_first_digit("nine"), 9);
-        assert_eq!(find_first_digit("1abc2"), 1);
-        assert_eq!(find_first_digit("pqr3stu8vwx"), 3);
-        assert_eq!(find_first_digit("a1b2c3d4e5f"), 1);
-        assert_eq!(find_first_digit("treb7uchet"), 7);
+        assert_eq!(find_first_digit("one"), Some(1));
+        assert_eq!(find_first_digit("two"), Some(2));
+        assert_eq!(find_first_digit("three"), Some(3));
+        assert_eq!(find_first_digit("four"), Some(4));
+        assert_eq!(find_first_digit("five"), Some(5));
+        assert_eq!(find_first_digit("six"), Some(6));
+        assert_eq!(find_first_digit("seven"), Some(7));
+        assert_eq!(find_first_digit("eight"), Some(8));
+        assert_eq!(find_first_digit("nine"), Some(9));
+        assert_eq!(find_first_digit("1abc2"), Some(1));
+        assert_eq!(find_first_digit("pqr3stu8vwx"), Some(3));
+        assert_eq!(find_first_digit("a1b2c3d4e5f"), Some(1));
+        assert_eq!(find_first_digit("treb7uchet"), Some(7));
         assert_eq!(
             find_first_digit("8sevengzfvjrhnsb6ddb8ninerkgkxthtfkvbcmqs"),
-            8
+            Some(8)
         );
-        assert_eq!(find_first_digit("1seven336"), 1);
+        assert_eq!(find_first_digit("1seven336"), Some(1));
     }
 
     #[test]
     fn test_find_last_digit() {
-        assert_eq!(find_last_digit("two1nine"), 9);
-        assert_eq!(find_last_digit("eightwothree"), 3);
-        assert_eq!(find_last_digit("abcone2threexyz"), 3);
-    }
-
-    #[test]
-    fn test_run_first() {
-        let input = "1abc2
-pqr3stu8vwx
-a1b2c3d4e5f
-treb7uchet";
-        assert_eq!(run_first(input), "142");
-    }
-
-    #[test]
-    fn test_run_second() {
-        let input = "two1nine
-eightwothree
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-
-        assert_eq!(run_second(input), "281");
+        assert_eq!(find_last_digit("two1nine"), Some(9));
+        assert_eq!(find_last_digit("eightwothree"), Some(3));
+        assert_eq!(find_last_digit("abcone2threexyz"), Some(3));
     }
 }
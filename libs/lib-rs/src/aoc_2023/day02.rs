@@ -1,25 +1,50 @@
-pub fn run_first(input: &str) -> String {
-    let constraints = (12, 13, 14);
-
-    input
-        .split('\n')
-        .map(parse_line)
-        .filter(|(_, game_draws)| is_valid_game(constraints, game_draws))
-        .map(|(game_id, _)| game_id)
-        .sum::<usize>()
-        .to_string()
-}
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
+
+pub struct Day02;
+
+const EXAMPLE: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+impl Solution for Day02 {
+    type Input = Vec<(usize, Vec<GameDraws>)>;
+    type Params = ();
 
-pub fn run_second(input: &str) -> String {
-    input
-        .split('\n')
-        .map(|l| {
-            let (_game_id, game_draws) = parse_line(l);
-            let (red, green, blue) = max_color_values(&game_draws);
-            red * green * blue
-        })
-        .sum::<usize>()
-        .to_string()
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("8").second("2286")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        input
+            .split('\n')
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_line(line).ok_or_else(|| SolveError::at(input, line)))
+            .collect()
+    }
+
+    fn part_one(games: &Self::Input) -> Result<String, SolveError> {
+        let constraints = (12, 13, 14);
+
+        Ok(games
+            .iter()
+            .filter(|(_, game_draws)| is_valid_game(constraints, game_draws))
+            .map(|(game_id, _)| game_id)
+            .sum::<usize>()
+            .to_string())
+    }
+
+    fn part_two(games: &Self::Input) -> Result<String, SolveError> {
+        Ok(games
+            .iter()
+            .map(|(_game_id, game_draws)| {
+                let (red, green, blue) = max_color_values(game_draws);
+                red * green * blue
+            })
+            .sum::<usize>()
+            .to_string())
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -29,13 +54,6 @@ enum Color {
     Blue,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Constraints {
-    red: usize,
-    green: usize,
-    blue: usize,
-}
-
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Draw {
     color: Color,
@@ -43,7 +61,7 @@ struct Draw {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct GameDraws {
+pub struct GameDraws {
     draws: Vec<Draw>,
 }
 
@@ -53,7 +71,7 @@ fn is_valid_game(constraints: (i64, i64, i64), game_draws: &[GameDraws]) -> bool
         .all(|game_draw| is_valid_game_draw(constraints, game_draw))
 }
 
-fn max_color_values(game_draws: &Vec<GameDraws>) -> (usize, usize, usize) {
+fn max_color_values(game_draws: &[GameDraws]) -> (usize, usize, usize) {
     let mut red = 0;
     let mut green = 0;
     let mut blue = 0;
@@ -85,41 +103,37 @@ fn is_valid_game_draw(constraints: (i64, i64, i64), game_draw: &GameDraws) -> bo
     red >= 0 && green >= 0 && blue >= 0
 }
 
-fn parse_line(line: &str) -> (usize, Vec<GameDraws>) {
-    let parts = line.split(':').collect::<Vec<_>>();
-    let game_str = parts[0];
-    let game_id = game_str
-        .split(' ')
-        .nth(1)
-        .unwrap()
-        .parse::<usize>()
-        .unwrap();
-
-    let draws_str = parts[1];
+fn parse_line(line: &str) -> Option<(usize, Vec<GameDraws>)> {
+    let (game_str, draws_str) = line.split_once(':')?;
+    let game_id = game_str.strip_prefix("Game ")?.parse::<usize>().ok()?;
+
     let draws = draws_str
         .split(';')
         .map(parse_game_draws)
-        .collect::<Vec<_>>();
+        .collect::<Option<Vec<_>>>()?;
 
-    (game_id, draws)
+    Some((game_id, draws))
 }
 
-fn parse_game_draws(line: &str) -> GameDraws {
-    GameDraws {
-        draws: line.split(',').map(parse_draw).collect::<Vec<_>>(),
-    }
+fn parse_game_draws(line: &str) -> Option<GameDraws> {
+    Some(GameDraws {
+        draws: line
+            .split(',')
+            .map(parse_draw)
+            .collect::<Option<Vec<_>>>()?,
+    })
 }
 
-fn parse_draw(line: &str) -> Draw {
-    let parts = line.trim().split(' ').collect::<Vec<_>>();
-    let value = parts[0].parse::<usize>().unwrap();
-    let color = match parts[1] {
+fn parse_draw(line: &str) -> Option<Draw> {
+    let (value, color) = line.trim().split_once(' ')?;
+    let value = value.parse::<usize>().ok()?;
+    let color = match color {
         "blue" => Color::Blue,
         "red" => Color::Red,
         "green" => Color::Green,
-        c => panic!("Unknown color {}", c),
+        _ => return None,
     };
-    Draw { color, value }
+    Some(Draw { color, value })
 }
 
 #[cfg(test)]
@@ -130,11 +144,12 @@ mod tests {
     fn test_parse_draw() {
         assert_eq!(
             parse_draw("3 blue"),
-            Draw {
+            Some(Draw {
                 color: Color::Blue,
                 value: 3
-            }
+            })
         );
+        assert_eq!(parse_draw("3 purple"), None);
     }
 
     #[test]
@@ -151,7 +166,7 @@ mod tests {
                 },
             ],
         };
-        assert_eq!(parse_game_draws("3 blue, 4 red"), expected);
+        assert_eq!(parse_game_draws("3 blue, 4 red"), Some(expected));
     }
 
     #[test]
@@ -196,13 +211,13 @@ mod tests {
                 },
             ],
         );
-        assert_eq!(parse_line(line), expected);
+        assert_eq!(parse_line(line), Some(expected));
     }
 
     #[test]
     fn test_is_valid_game_draw_valid() {
         let line = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
-        let game_draw = parse_line(line).1;
+        let game_draw = parse_line(line).unwrap().1;
 
         let constraints = (12, 13, 14);
         assert!(is_valid_game_draw(constraints, &game_draw[0]));
@@ -211,31 +226,9 @@ mod tests {
     #[test]
     fn test_is_valid_game_draw_not_valid() {
         let line = "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
-        let game_draw = parse_line(line).1;
+        let game_draw = parse_line(line).unwrap().1;
 
         let constraints = (12, 13, 14);
         assert!(!is_valid_game_draw(constraints, &game_draw[0]));
     }
-
-    #[test]
-    fn test_run_first() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-        assert_eq!(run_first(input), "8");
-    }
-
-    #[test]
-    fn test_run_second() {
-        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
-Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
-Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
-Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
-Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
-
-        assert_eq!(run_second(input), "2286");
-    }
 }
@@ -1,102 +1,210 @@
+use crate::common::interval::{Interval, IntervalSet, RangeMap};
 use crate::common::parse::parse_numbers;
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::solution::Solution;
 
-pub fn run_first(input: &str) -> String {
-    let seeds = parse_numbers(input.lines().next().unwrap());
+pub struct Day05;
 
-    let groups = input
-        .split("\n\n")
-        .skip(1)
-        .map(parse_resource_group)
-        .collect::<Vec<_>>();
+const EXAMPLE: &str = "seeds: 79 14 55 13
 
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+impl Solution for Day05 {
+    type Input = (Vec<i64>, Vec<ResourceGroup>);
+    type Params = ();
+
+    const EXAMPLES: &'static [Example] = &[Example::new(EXAMPLE).first("35").second("46")];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError> {
+        let seeds_line = input.lines().next().unwrap_or_default();
+        if !seeds_line.starts_with("seeds:") {
+            return Err(SolveError::at(input, seeds_line));
+        }
+        let seeds = parse_numbers(seeds_line);
+
+        let groups = input
+            .split("\n\n")
+            .skip(1)
+            .map(|group| parse_resource_group(group).ok_or_else(|| SolveError::at(input, group)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok((seeds, groups))
+    }
+
+    fn part_one((seeds, groups): &Self::Input) -> Result<String, SolveError> {
+        seeds
+            .iter()
+            .map(|seed| get_location(*seed, groups))
+            .min()
+            .flatten()
+            .map(|location| location.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
+
+    fn part_two((seeds, groups): &Self::Input) -> Result<String, SolveError> {
+        let seed_ranges = parse_seed_ranges(seeds).ok_or(SolveError::NoSolution)?;
+        split_chain(seed_ranges, groups)
+            .and_then(|chain| chain.last()?.ranges.min())
+            .map(|location| location.to_string())
+            .ok_or(SolveError::NoSolution)
+    }
+}
+
+/// Seeds given as pairs of a start and a length, `None` for an odd count.
+fn parse_seed_ranges(seeds: &[i64]) -> Option<IntervalSet<i64>> {
     seeds
-        .iter()
-        .map(|seed| get_location(*seed, &groups))
-        .min()
-        .unwrap()
-        .to_string()
+        .chunks(2)
+        .map(|chunk| match *chunk {
+            [start, length] => Some(Interval::with_length(start, length)),
+            _ => None,
+        })
+        .collect()
 }
 
-fn get_location(seed: i64, groups: &[ResourceGroup]) -> i64 {
-    let mut value = seed;
+/// Groups in the order they lead from seed to location.
+fn get_chain(groups: &[ResourceGroup]) -> Option<Vec<&ResourceGroup>> {
+    let mut chain = Vec::new();
     let mut curr_group = "seed";
 
-    while curr_group != "location" {
-        let group = groups.iter().find(|g| g.from == curr_group).unwrap();
-        let new_value = group.map(value);
-        let new_group = &group.to;
-        curr_group = new_group;
-        value = new_value;
+    // Each group can be used at most once on the way to the location
+    for _ in 0..groups.len() {
+        if curr_group == "location" {
+            return Some(chain);
+        }
+        let group = groups.iter().find(|g| g.from == curr_group)?;
+        chain.push(group);
+        curr_group = &group.to;
     }
 
-    value
+    (curr_group == "location").then_some(chain)
 }
 
-pub fn run_second(input: &str) -> String {
-    let seeds = parse_numbers(input.lines().next().unwrap());
+fn get_location(seed: i64, groups: &[ResourceGroup]) -> Option<i64> {
+    Some(
+        get_chain(groups)?
+            .iter()
+            .fold(seed, |value, group| group.map(value)),
+    )
+}
 
-    let groups = input
-        .split("\n\n")
-        .skip(1)
-        .map(parse_resource_group)
-        .collect::<Vec<_>>();
+/// Values of one resource reached by a set of seeds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stage<'a> {
+    pub resource: &'a str,
+    /// Pieces of the previous stage's ranges with the offset that moved them here.
+    pub splits: Vec<(Interval<i64>, i64)>,
+    pub ranges: IntervalSet<i64>,
+}
 
-    seeds
-        .chunks(2)
-        .map(|chunk| {
-            let start = chunk[0];
-            let length = chunk[1];
-
-            (start..start + length)
-                .map(|seed| get_location(seed, &groups))
-                .min()
-                .unwrap()
-        })
-        .min()
-        .unwrap()
-        .to_string()
+/// Follows whole seed ranges to the locations, the ranges split wherever a map shifts their
+/// parts differently. Starts with the seeds and ends with the locations.
+pub fn split_chain(seeds: IntervalSet<i64>, groups: &[ResourceGroup]) -> Option<Vec<Stage<'_>>> {
+    let mut stages = vec![Stage {
+        resource: "seed",
+        splits: Vec::new(),
+        ranges: seeds,
+    }];
+    for group in get_chain(groups)? {
+        let splits: Vec<_> = stages
+            .last()?
+            .ranges
+            .intervals()
+            .iter()
+            .flat_map(|&interval| group.split(interval))
+            .collect();
+        let ranges = splits
+            .iter()
+            .map(|(piece, offset)| piece.shift(*offset))
+            .collect();
+        stages.push(Stage {
+            resource: &group.to,
+            splits,
+            ranges,
+        });
+    }
+    Some(stages)
 }
 
-fn parse_range(line: &str) -> ResourceRange {
-    let nums = parse_numbers(line);
-    ResourceRange::new(nums[0], nums[1], nums[2])
+fn parse_range(line: &str) -> Option<ResourceRange> {
+    match parse_numbers(line)[..] {
+        [dest_start, source_start, length] => {
+            Some(ResourceRange::new(dest_start, source_start, length))
+        }
+        _ => None,
+    }
 }
+
 #[derive(Debug)]
-struct ResourceGroup {
+pub struct ResourceGroup {
     pub from: String,
     pub to: String,
-    ranges: Vec<ResourceRange>,
+    ranges: RangeMap,
 }
 
-fn parse_resource_group(input: &str) -> ResourceGroup {
+fn parse_resource_group(input: &str) -> Option<ResourceGroup> {
     let mut lines = input.lines();
-    let mut name_parts = lines.next().unwrap().split(' ').next().unwrap().split('-');
-    let from = name_parts.next().unwrap().trim();
-    let to = name_parts.nth(1).unwrap().trim();
+    let name = lines.next()?.strip_suffix(" map:")?;
+    let (from, to) = name.split_once("-to-")?;
 
-    let ranges = lines.map(parse_range).collect();
-    ResourceGroup::new(from, to, ranges)
+    let ranges = lines.map(parse_range).collect::<Option<_>>()?;
+    Some(ResourceGroup::new(from.trim(), to.trim(), ranges))
 }
 
 impl ResourceGroup {
-    pub fn new(from: &str, to: &str, ranges: Vec<ResourceRange>) -> Self {
+    fn new(from: &str, to: &str, ranges: Vec<ResourceRange>) -> Self {
+        let mut map = RangeMap::new();
+        for range in ranges {
+            map.insert(range.source(), range.offset());
+        }
         Self {
             from: from.to_string(),
             to: to.to_string(),
-            ranges,
+            ranges: map,
         }
     }
 
     pub fn map(&self, value: i64) -> i64 {
-        for range in &self.ranges {
-            if range.can_map(value) {
-                return range.map(value);
-            }
-        }
-        value
+        self.ranges.map(value)
+    }
+
+    /// Pieces of `interval` the map shifts by the same offset, with that offset.
+    pub fn split(&self, interval: Interval<i64>) -> Vec<(Interval<i64>, i64)> {
+        self.ranges.split(interval)
     }
 }
 
+/// A line of a map, `length` values from `source_start` go to `dest_start` onwards.
 #[derive(Debug)]
 struct ResourceRange {
     dest_start: i64,
@@ -113,13 +221,12 @@ impl ResourceRange {
         }
     }
 
-    pub fn can_map(&self, source: i64) -> bool {
-        source >= self.source_start && source < self.source_start + self.length
+    fn source(&self) -> Interval<i64> {
+        Interval::with_length(self.source_start, self.length)
     }
 
-    pub fn map(&self, source: i64) -> i64 {
-        let delta = self.dest_start - self.source_start;
-        source + delta
+    fn offset(&self) -> i64 {
+        self.dest_start - self.source_start
     }
 }
 
@@ -127,85 +234,81 @@ impl ResourceRange {
 mod tests {
     use super::*;
 
-    const TEST_DATA: &str = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-
     #[test]
-    fn test_run_first() {
+    fn test_split_chain() {
         // given
-        let data = TEST_DATA;
+        let (seeds, groups) = Day05::parse(EXAMPLE).unwrap();
+        let seeds = parse_seed_ranges(&seeds).unwrap();
 
         // when
-        let result = run_first(data);
+        let chain = split_chain(seeds, &groups).unwrap();
 
         // then
-        assert_eq!(result, "35");
+        let resources: Vec<_> = chain.iter().map(|stage| stage.resource).collect();
+        assert_eq!(
+            resources,
+            [
+                "seed",
+                "soil",
+                "fertilizer",
+                "water",
+                "light",
+                "temperature",
+                "humidity",
+                "location"
+            ]
+        );
+        assert_eq!(chain[0].ranges.len(), 27);
+        assert_eq!(
+            chain[1].splits,
+            vec![(Interval::new(55, 68), 2), (Interval::new(79, 93), 2)]
+        );
+        assert_eq!(chain[7].ranges.len(), 27);
+        assert_eq!(chain[7].ranges.min(), Some(46));
     }
 
     #[test]
-    fn test_run_second() {
+    fn test_split() {
         // given
-        let data = TEST_DATA;
+        let group = parse_resource_group("seed-to-soil map:\n50 98 2\n52 50 48").unwrap();
 
         // when
-        let result = run_second(data);
+        let pieces = group.split(Interval::with_length(79, 14));
 
         // then
-        assert_eq!(result, "46");
+        assert_eq!(pieces, vec![(Interval::new(79, 93), 2)]);
+        assert_eq!(
+            group.split(Interval::new(95, 101)),
+            vec![
+                (Interval::new(95, 98), 2),
+                (Interval::new(98, 100), -48),
+                (Interval::new(100, 101), 0)
+            ]
+        );
     }
+
     #[test]
     fn test_parse_group() {
         let input = "seed-to-soil map:
 50 98 2
 52 50 48";
-        let group = parse_resource_group(input);
+        let group = parse_resource_group(input).unwrap();
         println!("{:?}", group);
         assert_eq!(group.from, "seed");
         assert_eq!(group.to, "soil");
-        assert_eq!(group.ranges.len(), 2);
+        assert_eq!(group.ranges.ranges().len(), 2);
     }
 
     #[test]
     fn test_map_range() {
         let range = ResourceRange::new(52, 50, 48);
-        assert_eq!(range.map(79), 81);
+        assert_eq!(79 + range.offset(), 81);
     }
 
     #[test]
     fn test_can_map() {
         let range = ResourceRange::new(52, 50, 48);
-        assert!(range.can_map(79));
-        assert!(!range.can_map(14));
+        assert!(range.source().contains(79));
+        assert!(!range.source().contains(14));
     }
 }
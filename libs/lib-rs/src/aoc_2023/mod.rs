@@ -1,6 +1,16 @@
+use crate::register;
+
 pub mod day01;
 pub mod day02;
 pub mod day03;
-
 pub mod day04;
 pub mod day05;
+
+register! {
+    2023;
+    1 => day01::Day01,
+    2 => day02::Day02,
+    3 => day03::Day03,
+    4 => day04::Day04,
+    5 => day05::Day05,
+}
@@ -0,0 +1,198 @@
+/// Bytes of a hex string, two digits per byte. An odd last digit fills the high half of the
+/// last byte. `None` when a character is not a hex digit.
+pub fn hex_to_bytes(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let high = (pair[0] as char).to_digit(16)?;
+            let low = match pair.get(1) {
+                Some(&c) => (c as char).to_digit(16)?,
+                None => 0,
+            };
+            Some((high << 4 | low) as u8)
+        })
+        .collect()
+}
+
+/// Reads big-endian bit fields of any width up to 64 from a byte slice.
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    len: usize,
+    position: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        BitReader::with_len(bytes, bytes.len() * 8)
+    }
+
+    /// Reader of the first `len` bits of `bytes`, the rest counts as missing.
+    pub fn with_len(bytes: &'a [u8], len: usize) -> Self {
+        BitReader {
+            bytes,
+            len: len.min(bytes.len() * 8),
+            position: 0,
+        }
+    }
+
+    /// Bits read so far.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.len - self.position
+    }
+
+    /// The next `bits` bits as a number, `None` without moving when fewer are left.
+    pub fn read(&mut self, bits: usize) -> Option<u64> {
+        assert!(bits <= 64, "cannot read {bits} bits at once");
+        if bits > self.remaining() {
+            return None;
+        }
+        let value = (self.position..self.position + bits).fold(0, |value, idx| {
+            let bit = self.bytes[idx / 8] >> (7 - idx % 8) & 1;
+            value << 1 | bit as u64
+        });
+        self.position += bits;
+        Some(value)
+    }
+
+    pub fn read_bool(&mut self) -> Option<bool> {
+        self.read(1).map(|bit| bit == 1)
+    }
+}
+
+/// Writes big-endian bit fields, the counterpart of [`BitReader`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        BitWriter::default()
+    }
+
+    /// Bits written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The lowest `bits` bits of `value`, highest first.
+    pub fn write(&mut self, value: u64, bits: usize) {
+        assert!(bits <= 64, "cannot write {bits} bits at once");
+        for shift in (0..bits).rev() {
+            self.push(value >> shift & 1 == 1);
+        }
+    }
+
+    pub fn write_bool(&mut self, bit: bool) {
+        self.push(bit);
+    }
+
+    /// Every bit written to `other`.
+    pub fn append(&mut self, other: &BitWriter) {
+        let mut reader = other.reader();
+        while let Some(bit) = reader.read_bool() {
+            self.push(bit);
+        }
+    }
+
+    /// Reader over the bits written so far.
+    pub fn reader(&self) -> BitReader<'_> {
+        BitReader::with_len(&self.bytes, self.len)
+    }
+
+    /// Bytes written so far, the last one padded with zeros.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Upper case hex digits of the bits, the last one padded with zeros.
+    pub fn to_hex(&self) -> String {
+        let digits = self.len.div_ceil(4);
+        self.bytes
+            .iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect::<String>()[..digits]
+            .to_string()
+    }
+
+    fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+        if bit {
+            self.bytes[self.len / 8] |= 0x80 >> (self.len % 8);
+        }
+        self.len += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_to_bytes() {
+        assert_eq!(hex_to_bytes("D2FE28"), Some(vec![0xD2, 0xFE, 0x28]));
+        assert_eq!(hex_to_bytes("abc"), Some(vec![0xAB, 0xC0]));
+        assert_eq!(hex_to_bytes("0G"), None);
+    }
+
+    #[test]
+    fn test_read() {
+        // given
+        let bytes = [0xD2, 0xFE, 0x28];
+        let mut reader = BitReader::new(&bytes);
+
+        // then
+        assert_eq!(reader.read(3), Some(6));
+        assert_eq!(reader.read(3), Some(4));
+        assert_eq!(reader.read_bool(), Some(true));
+        assert_eq!(reader.read(4), Some(0b0111));
+        assert_eq!(reader.position(), 11);
+        assert_eq!(reader.read(14), None);
+        assert_eq!(reader.remaining(), 13);
+        assert_eq!(reader.read(0), Some(0));
+    }
+
+    #[test]
+    fn test_read_with_len() {
+        // given
+        let mut reader = BitReader::with_len(&[0xFF], 5);
+
+        // then
+        assert_eq!(reader.read(6), None);
+        assert_eq!(reader.read(5), Some(0b11111));
+        assert_eq!(reader.read_bool(), None);
+    }
+
+    #[test]
+    fn test_write() {
+        // given
+        let mut writer = BitWriter::new();
+        let mut tail = BitWriter::new();
+
+        // when
+        writer.write(6, 3);
+        writer.write(4, 3);
+        tail.write_bool(true);
+        tail.write(0b0111, 4);
+        writer.append(&tail);
+
+        // then
+        assert_eq!(writer.len(), 11);
+        assert_eq!(writer.as_bytes(), [0xD2, 0xE0]);
+        assert_eq!(writer.to_hex(), "D2E");
+        assert_eq!(writer.reader().read(11), Some(0b110_1001_0111));
+        assert_eq!(BitWriter::new().to_hex(), "");
+    }
+}
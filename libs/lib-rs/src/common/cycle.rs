@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Steps `start..start + length` of a simulation repeat forever.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Step before the first repetition that is in the same state as step `n`.
+    pub fn equivalent(&self, n: u64) -> usize {
+        if n < self.start as u64 {
+            return n as usize;
+        }
+        self.start + ((n - self.start as u64) % self.length as u64) as usize
+    }
+
+    /// Value of a metric at step `n`, from its values at steps `0..=start + length`.
+    ///
+    /// The metric must grow by the same amount over every repetition, like the height of a
+    /// tower that gets the same layers added.
+    pub fn extrapolate(&self, values: &[i64], n: u64) -> i64 {
+        if let Some(&value) = usize::try_from(n).ok().and_then(|n| values.get(n)) {
+            return value;
+        }
+        let per_cycle = values[self.start + self.length] - values[self.start];
+        let cycles = (n - self.start as u64) / self.length as u64;
+        values[self.equivalent(n)] + cycles as i64 * per_cycle
+    }
+}
+
+/// Finds the first repeated state in `states`, the state after each step starting with the
+/// initial one.
+///
+/// Stops consuming `states` at the repetition, `None` when they run out first.
+pub fn find_cycle<S: Eq + Hash>(states: impl IntoIterator<Item = S>) -> Option<Cycle> {
+    let mut seen = HashMap::new();
+    for (step, state) in states.into_iter().enumerate() {
+        if let Some(start) = seen.insert(state, step) {
+            return Some(Cycle {
+                start,
+                length: step - start,
+            });
+        }
+    }
+    None
+}
+
+/// Brent's cycle detection for `x, f(x), f(f(x)), ...` without keeping the visited states.
+pub fn brent<S: Clone + Eq>(initial: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = f(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+    Cycle { start, length }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0, 1, 2, 3, 4, 2, 3, 4, ...
+    fn next(state: &u32) -> u32 {
+        if *state == 4 {
+            2
+        } else {
+            state + 1
+        }
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        // when
+        let cycle = find_cycle(std::iter::successors(Some(0), |state| Some(next(state))));
+
+        // then
+        assert_eq!(
+            cycle,
+            Some(Cycle {
+                start: 2,
+                length: 3
+            })
+        );
+        assert_eq!(find_cycle(0..10), None);
+    }
+
+    #[test]
+    fn test_brent() {
+        assert_eq!(
+            brent(0, next),
+            Cycle {
+                start: 2,
+                length: 3
+            }
+        );
+        assert_eq!(
+            brent(7, |_| 7),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_equivalent() {
+        // given
+        let cycle = Cycle {
+            start: 2,
+            length: 3,
+        };
+
+        // then
+        assert_eq!(cycle.equivalent(1), 1);
+        assert_eq!(cycle.equivalent(5), 2);
+        assert_eq!(cycle.equivalent(1_000_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_extrapolate() {
+        // given
+        let cycle = Cycle {
+            start: 2,
+            length: 3,
+        };
+        // Grows by 1, 4, then 2, 3, 5 in every repetition
+        let values = [0, 1, 5, 7, 10, 15];
+
+        // then
+        assert_eq!(cycle.extrapolate(&values, 4), 10);
+        assert_eq!(cycle.extrapolate(&values, 8), 25);
+        assert_eq!(cycle.extrapolate(&values, 9), 27);
+    }
+}
@@ -0,0 +1,394 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::error::SolveError;
+
+/// Numbers points can be built from.
+pub trait Coord: Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>> Coord for T {}
+
+fn distance<T: Coord>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/// Point or vector on a plane, `y` grows downwards like the rows of a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coord> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y)
+    }
+
+    /// Distance when diagonal steps are allowed.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        distance(self.x, other.x).max(distance(self.y, other.y))
+    }
+}
+
+impl<T: Coord + From<i8>> Point2<T> {
+    /// Vector with every coordinate clamped to `-1..=1`, the single step towards its direction.
+    pub fn signum(&self) -> Self {
+        let signum = |value: T| value.clamp(T::from(-1), T::from(1));
+        Point2::new(signum(self.x), signum(self.y))
+    }
+
+    /// The four points one step up, right, down and left.
+    pub fn neighbours4(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| point + direction.offset())
+    }
+
+    /// [`Point2::neighbours4`] followed by the four diagonal points.
+    pub fn neighbours8(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        let diagonals = [(1, -1), (1, 1), (-1, 1), (-1, -1)]
+            .map(|(x, y)| point + Point2::new(T::from(x), T::from(y)));
+        self.neighbours4().chain(diagonals)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point2 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point2 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point2 {
+            x: self.x * factor,
+            y: self.y * factor,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point2 {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// Point or vector in space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coord> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        distance(self.x, other.x) + distance(self.y, other.y) + distance(self.z, other.z)
+    }
+
+    /// Distance when diagonal steps are allowed.
+    pub fn chebyshev(&self, other: &Self) -> T {
+        distance(self.x, other.x)
+            .max(distance(self.y, other.y))
+            .max(distance(self.z, other.z))
+    }
+
+    /// Smallest value of every coordinate of both points.
+    pub fn component_min(&self, other: &Self) -> Self {
+        Point3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// Largest value of every coordinate of both points.
+    pub fn component_max(&self, other: &Self) -> Self {
+        Point3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+}
+
+impl<T: Coord + From<i8>> Point3<T> {
+    /// The six points sharing a face with this one.
+    pub fn neighbours6(&self) -> impl Iterator<Item = Self> {
+        let point = *self;
+        [
+            (1, 0, 0),
+            (-1, 0, 0),
+            (0, 1, 0),
+            (0, -1, 0),
+            (0, 0, 1),
+            (0, 0, -1),
+        ]
+        .into_iter()
+        .map(move |(x, y, z)| point + Point3::new(T::from(x), T::from(y), T::from(z)))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3 {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3 {
+            x: self.x - other.x,
+            y: self.y - other.y,
+            z: self.z - other.z,
+        }
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Point3 {
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point3 {
+            x: -self.x,
+            y: -self.y,
+            z: -self.z,
+        }
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// Heading on a plane, up is north and towards smaller `y`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Reads `U`/`R`/`D`/`L`, `^`/`>`/`v`/`<` or `N`/`E`/`S`/`W`.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | '^' | 'N' => Some(Direction::Up),
+            'R' | '>' | 'E' => Some(Direction::Right),
+            'D' | 'v' | 'S' => Some(Direction::Down),
+            'L' | '<' | 'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// [`Direction::from_char`] of a single character `token`, reporting its position within
+    /// `input` on failure.
+    pub fn parse(input: &str, token: &str) -> Result<Self, SolveError> {
+        let mut chars = token.chars();
+        match (chars.next().and_then(Direction::from_char), chars.next()) {
+            (Some(direction), None) => Ok(direction),
+            _ => Err(SolveError::at(input, token)),
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Vector of a single step.
+    pub fn offset<T: From<i8>>(self) -> Point2<T> {
+        let (x, y) = match self {
+            Direction::Up => (0, -1),
+            Direction::Right => (1, 0),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+        };
+        Point2 {
+            x: T::from(x),
+            y: T::from(y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_point2() {
+        // given
+        let a = Point2::new(1, 5);
+        let b = Point2::new(4, 1);
+
+        // then
+        assert_eq!(a + b, Point2::new(5, 6));
+        assert_eq!(a - b, Point2::new(-3, 4));
+        assert_eq!(-(a * 2), Point2::new(-2, -10));
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!((a - b).signum(), Point2::new(-1, 1));
+        assert_eq!(Point2::new(3usize, 7).manhattan(&Point2::new(5, 2)), 7);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        // given
+        let point = Point2::new(0i64, 0);
+
+        // when
+        let neighbours4: Vec<_> = point.neighbours4().collect();
+        let neighbours8: Vec<_> = point.neighbours8().collect();
+
+        // then
+        assert_eq!(
+            neighbours4,
+            vec![
+                Point2::new(0, -1),
+                Point2::new(1, 0),
+                Point2::new(0, 1),
+                Point2::new(-1, 0)
+            ]
+        );
+        assert_eq!(neighbours8.len(), 8);
+        assert!(neighbours8.iter().all(|n| n.chebyshev(&point) == 1));
+        assert_eq!(Point3::new(1i64, 1, 1).neighbours6().count(), 6);
+    }
+
+    #[test]
+    fn test_point3() {
+        // given
+        let a = Point3::new(1, 2, 3);
+        let b = Point3::new(3, 0, 4);
+
+        // then
+        assert_eq!(a + b, Point3::new(4, 2, 7));
+        assert_eq!(a.manhattan(&b), 5);
+        assert_eq!(a.chebyshev(&b), 2);
+        assert_eq!(a.component_min(&b), Point3::new(1, 0, 3));
+        assert_eq!(a.component_max(&b), Point3::new(3, 2, 4));
+    }
+
+    #[test]
+    fn test_direction() {
+        // given
+        let input = "R 4\n^ 2\nX 1";
+
+        // then
+        assert_eq!(Direction::parse(input, &input[0..1]), Ok(Direction::Right));
+        assert_eq!(Direction::parse(input, &input[4..5]), Ok(Direction::Up));
+        assert_eq!(
+            Direction::parse(input, &input[8..9]),
+            Err(SolveError::parse(3, 1, "X"))
+        );
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction::Down.offset::<i64>(), Point2::new(0, 1));
+    }
+}
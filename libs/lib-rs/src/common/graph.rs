@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Add;
+
+use crate::common::grid::Grid;
+use crate::common::search::bfs_all;
+
+/// Gives every distinct name a dense id, in the order they are first seen.
+#[derive(Debug, Clone)]
+pub struct Interner<K> {
+    ids: HashMap<K, usize>,
+    names: Vec<K>,
+}
+
+impl<K> Default for Interner<K> {
+    fn default() -> Self {
+        Interner {
+            ids: HashMap::new(),
+            names: Vec::new(),
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash> Interner<K> {
+    pub fn new() -> Self {
+        Interner::default()
+    }
+
+    /// Id of `name`, a new one when it was not seen yet.
+    pub fn intern(&mut self, name: K) -> usize {
+        if let Some(&id) = self.ids.get(&name) {
+            return id;
+        }
+        let id = self.names.len();
+        self.ids.insert(name.clone(), id);
+        self.names.push(name);
+        id
+    }
+
+    pub fn id<Q>(&self, name: &Q) -> Option<usize>
+    where
+        K: std::borrow::Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &K {
+        &self.names[id]
+    }
+
+    pub fn names(&self) -> &[K] {
+        &self.names
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// Weighted graph over the ids `0..len`, stored as adjacency lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Graph<W> {
+    edges: Vec<Vec<(usize, W)>>,
+}
+
+impl<W> Default for Graph<W> {
+    fn default() -> Self {
+        Graph { edges: Vec::new() }
+    }
+}
+
+impl<W: Copy + Ord + Default + Add<Output = W>> Graph<W> {
+    /// Graph of `len` nodes without any edge.
+    pub fn new(len: usize) -> Self {
+        Graph {
+            edges: (0..len).map(|_| Vec::new()).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Edge from `from` to `to`, the graph grows to hold both.
+    pub fn add_edge(&mut self, from: usize, to: usize, weight: W) {
+        let len = self.len().max(from + 1).max(to + 1);
+        self.edges.resize_with(len, Vec::new);
+        self.edges[from].push((to, weight));
+    }
+
+    /// Edges both ways between `a` and `b`.
+    pub fn add_undirected(&mut self, a: usize, b: usize, weight: W) {
+        self.add_edge(a, b, weight);
+        self.add_edge(b, a, weight);
+    }
+
+    /// Nodes reached from `node` with the weight of the edge to each of them.
+    pub fn neighbours(&self, node: usize) -> &[(usize, W)] {
+        &self.edges[node]
+    }
+
+    pub fn degree(&self, node: usize) -> usize {
+        self.edges[node].len()
+    }
+
+    /// Shortest distance between every pair of nodes, indexed by `(from, to)`.
+    pub fn floyd_warshall(&self) -> Grid<Option<W>> {
+        let len = self.len();
+        let mut distances = Grid::new(len, len, None);
+        for (from, edges) in self.edges.iter().enumerate() {
+            distances[(from, from)] = Some(W::default());
+            for &(to, weight) in edges {
+                if distances[(from, to)].is_none_or(|best| weight < best) {
+                    distances[(from, to)] = Some(weight);
+                }
+            }
+        }
+        for via in 0..len {
+            for from in 0..len {
+                let Some(first) = distances[(from, via)] else {
+                    continue;
+                };
+                for to in 0..len {
+                    if let Some(second) = distances[(via, to)] {
+                        let distance = first + second;
+                        if distances[(from, to)].is_none_or(|best| distance < best) {
+                            distances[(from, to)] = Some(distance);
+                        }
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    /// Fewest edges between every pair of nodes, ignoring the weights, with a BFS from each.
+    pub fn bfs_all_pairs(&self) -> Grid<Option<usize>> {
+        let len = self.len();
+        let mut distances = Grid::new(len, len, None);
+        for from in 0..len {
+            let explored = bfs_all([from], |&node| {
+                self.edges[node].iter().map(|&(next, _)| next)
+            });
+            for (&to, steps) in explored.costs() {
+                distances[(from, to)] = Some(steps);
+            }
+        }
+        distances
+    }
+
+    /// Collapses the corridors of an undirected graph: every node not matching `keep` with
+    /// exactly two neighbours is removed and its neighbours are linked directly, with the sum
+    /// of both weights.
+    ///
+    /// Ids don't change, removed nodes are left without edges.
+    pub fn compress(&self, keep: impl Fn(usize) -> bool) -> Self {
+        let mut compressed = self.clone();
+        for node in 0..compressed.len() {
+            if keep(node) || compressed.degree(node) != 2 {
+                continue;
+            }
+            let [(a, to_a), (b, to_b)] = compressed.edges[node][..] else {
+                continue;
+            };
+            if a == b {
+                continue;
+            }
+            compressed.edges[node].clear();
+            compressed.edges[a].retain(|&(next, _)| next != node);
+            compressed.edges[b].retain(|&(next, _)| next != node);
+            compressed.link(a, b, to_a + to_b);
+        }
+        compressed
+    }
+
+    /// Undirected edge between `a` and `b`, keeping the lighter one when they are already linked.
+    fn link(&mut self, a: usize, b: usize, weight: W) {
+        for (from, to) in [(a, b), (b, a)] {
+            match self.edges[from].iter_mut().find(|(next, _)| *next == to) {
+                Some((_, existing)) => *existing = (*existing).min(weight),
+                None => self.edges[from].push((to, weight)),
+            }
+        }
+    }
+
+    /// Groups of nodes linked to each other, every group sorted and the groups sorted by their
+    /// smallest node. Edges are followed both ways.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut undirected: Vec<Vec<usize>> = vec![Vec::new(); self.len()];
+        for (from, edges) in self.edges.iter().enumerate() {
+            for &(to, _) in edges {
+                undirected[from].push(to);
+                undirected[to].push(from);
+            }
+        }
+
+        let mut seen = vec![false; self.len()];
+        let mut components = Vec::new();
+        for start in 0..self.len() {
+            if seen[start] {
+                continue;
+            }
+            let explored = bfs_all([start], |&node| undirected[node].clone());
+            let mut nodes: Vec<_> = explored.costs().map(|(&node, _)| node).collect();
+            nodes.sort_unstable();
+            for &node in &nodes {
+                seen[node] = true;
+            }
+            components.push(nodes);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 - 1 - 2 - 3 with a shortcut 0 - 3 of weight 5 and a lone edge 4 - 5.
+    fn sample() -> Graph<usize> {
+        let mut graph = Graph::new(6);
+        for (a, b, weight) in [(0, 1, 1), (1, 2, 1), (2, 3, 1), (0, 3, 5), (4, 5, 2)] {
+            graph.add_undirected(a, b, weight);
+        }
+        graph
+    }
+
+    #[test]
+    fn test_interner() {
+        // given
+        let mut names = Interner::new();
+
+        // when
+        let aa = names.intern("AA".to_string());
+        let bb = names.intern("BB".to_string());
+
+        // then
+        assert_eq!((aa, bb), (0, 1));
+        assert_eq!(names.intern("AA".to_string()), aa);
+        assert_eq!(names.id("BB"), Some(bb));
+        assert_eq!(names.id("CC"), None);
+        assert_eq!(names.name(bb), "BB");
+        assert_eq!(names.len(), 2);
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        // when
+        let distances = sample().floyd_warshall();
+
+        // then
+        assert_eq!(distances[(0, 3)], Some(3));
+        assert_eq!(distances[(3, 0)], Some(3));
+        assert_eq!(distances[(2, 2)], Some(0));
+        assert_eq!(distances[(4, 5)], Some(2));
+        assert_eq!(distances[(0, 5)], None);
+    }
+
+    #[test]
+    fn test_bfs_all_pairs() {
+        // when
+        let distances = sample().bfs_all_pairs();
+
+        // then
+        assert_eq!(distances[(0, 3)], Some(1));
+        assert_eq!(distances[(1, 3)], Some(2));
+        assert_eq!(distances[(0, 4)], None);
+    }
+
+    #[test]
+    fn test_compress() {
+        // when
+        let compressed = sample().compress(|node| node == 0 || node == 3);
+
+        // then
+        assert_eq!(compressed.neighbours(0), [(3, 3)]);
+        assert_eq!(compressed.neighbours(3), [(0, 3)]);
+        assert_eq!(compressed.degree(1), 0);
+        assert_eq!(compressed.neighbours(4), [(5, 2)]);
+        assert_eq!(compressed.floyd_warshall()[(0, 3)], Some(3));
+    }
+
+    #[test]
+    fn test_components() {
+        // given
+        let mut graph = sample();
+        graph.add_edge(6, 4, 1);
+
+        // then
+        assert_eq!(graph.components(), vec![vec![0, 1, 2, 3], vec![4, 5, 6]]);
+    }
+}
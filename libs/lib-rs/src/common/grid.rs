@@ -0,0 +1,377 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::SolveError;
+
+/// `(row, column)` of a cell, `(0, 0)` is the top left corner.
+pub type Pos = (usize, usize);
+
+/// Offsets of the orthogonal neighbours: right, left, up and down.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, 1), (0, -1), (-1, 0), (1, 0)];
+
+/// [`ORTHOGONAL`] followed by the diagonal neighbours.
+pub const ALL_AROUND: [(isize, isize); 8] = [
+    (0, 1),
+    (0, -1),
+    (-1, 0),
+    (1, 0),
+    (1, 1),
+    (1, -1),
+    (-1, 1),
+    (-1, -1),
+];
+
+/// Rectangular grid stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.rearranged(self.height, self.width, |(row, column)| (column, row))
+    }
+
+    /// Turns the grid a quarter clockwise.
+    pub fn rotate_right(&self) -> Self {
+        let height = self.height;
+        self.rearranged(height, self.width, |(row, column)| {
+            (height - 1 - column, row)
+        })
+    }
+
+    /// Turns the grid a quarter counter-clockwise.
+    pub fn rotate_left(&self) -> Self {
+        let width = self.width;
+        self.rearranged(self.height, width, |(row, column)| {
+            (column, width - 1 - row)
+        })
+    }
+
+    /// Mirrors the columns, left becomes right.
+    pub fn flip_horizontal(&self) -> Self {
+        let width = self.width;
+        self.rearranged(width, self.height, |(row, column)| {
+            (row, width - 1 - column)
+        })
+    }
+
+    /// Mirrors the rows, top becomes bottom.
+    pub fn flip_vertical(&self) -> Self {
+        let height = self.height;
+        self.rearranged(self.width, height, |(row, column)| {
+            (height - 1 - row, column)
+        })
+    }
+
+    /// Builds a `width` x `height` grid whose cell at `pos` is this grid's cell at `source(pos)`.
+    fn rearranged(&self, width: usize, height: usize, source: impl Fn(Pos) -> Pos) -> Self {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |column| (row, column)))
+            .map(|pos| self[source(pos)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Grid from equally long rows, `None` when they are not.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        Some(Grid {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Parses every non-empty line of `input` as a row, `cell` rejects a character with `None`.
+    pub fn parse(input: &str, cell: impl Fn(char) -> Option<T>) -> Result<Self, SolveError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for line in input.split('\n').filter(|line| !line.is_empty()) {
+            let before = cells.len();
+            for (idx, c) in line.char_indices() {
+                let value =
+                    cell(c).ok_or_else(|| SolveError::at(input, &line[idx..idx + c.len_utf8()]))?;
+                cells.push(value);
+            }
+            if *width.get_or_insert(cells.len() - before) != cells.len() - before {
+                return Err(SolveError::at(input, line));
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, (row, column): Pos) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    fn index_of(&self, pos: Pos) -> Option<usize> {
+        self.contains(pos).then(|| pos.0 * self.width + pos.1)
+    }
+
+    /// `pos` moved by `(rows, columns)`, `None` when that leaves the grid.
+    pub fn offset(&self, (row, column): Pos, (rows, columns): (isize, isize)) -> Option<Pos> {
+        let pos = (
+            row.checked_add_signed(rows)?,
+            column.checked_add_signed(columns)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Orthogonal neighbours within the grid, in the order of [`ORTHOGONAL`].
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ORTHOGONAL
+            .into_iter()
+            .filter_map(move |step| self.offset(pos, step))
+    }
+
+    /// Orthogonal and diagonal neighbours within the grid, in the order of [`ALL_AROUND`].
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        ALL_AROUND
+            .into_iter()
+            .filter_map(move |step| self.offset(pos, step))
+    }
+
+    /// Positions from `start` (excluded) in steps of `step` until the edge of the grid.
+    pub fn ray(&self, start: Pos, step: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.offset(start, step), move |&pos| self.offset(pos, step))
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn values_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        self.cells.iter().skip(column).step_by(self.width.max(1))
+    }
+
+    /// Position of the first cell, row by row, matching `predicate`.
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.iter()
+            .find(|(_, value)| predicate(value))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a block of single digits such as `2199943210`.
+    pub fn parse_digits(input: &str) -> Result<Self, SolveError> {
+        Grid::parse(input, |c| c.to_digit(10).map(|digit| digit as u8))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(value) => value,
+            None => panic!(
+                "{pos:?} is outside of a {}x{} grid",
+                self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(pos) {
+            Some(value) => value,
+            None => panic!("{pos:?} is outside of a {width}x{height} grid"),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for value in row {
+                write!(f, "{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn letters() -> Grid<char> {
+        Grid::parse("abc\ndef", Some).unwrap()
+    }
+
+    #[test]
+    fn test_parse_digits() {
+        // when
+        let grid = Grid::parse_digits("219\n398\n").unwrap();
+
+        // then
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 8);
+        assert_eq!(grid.get((2, 0)), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Grid::parse_digits("12\n3x").unwrap_err(),
+            SolveError::parse(2, 2, "x")
+        );
+        assert_eq!(
+            Grid::parse_digits("12\n345").unwrap_err(),
+            SolveError::parse(2, 1, "345")
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        // given
+        let grid = Grid::parse_digits("123\n456\n789").unwrap();
+
+        // then
+        assert_eq!(
+            grid.neighbours4((0, 1)).collect::<Vec<_>>(),
+            vec![(0, 2), (0, 0), (1, 1)]
+        );
+        assert_eq!(grid.neighbours4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbours8((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_rows_columns_and_rays() {
+        // given
+        let grid = letters();
+
+        // then
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.ray((0, 0), (0, 1)).collect::<Vec<_>>(),
+            [(0, 1), (0, 2)]
+        );
+        assert_eq!(grid.ray((1, 2), (-1, -1)).collect::<Vec<_>>(), [(0, 1)]);
+        assert_eq!(grid.ray((0, 2), (0, 1)).count(), 0);
+    }
+
+    #[test]
+    fn test_transformations() {
+        // given
+        let grid = letters();
+
+        // then
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+    }
+
+    #[test]
+    fn test_from_rows() {
+        assert_eq!(
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4]]),
+            Some(Grid::parse_digits("12\n34").unwrap())
+        );
+        assert_eq!(Grid::from_rows(vec![vec![1, 2], vec![3]]), None);
+    }
+
+    #[test]
+    fn test_position_and_map() {
+        // given
+        let grid = letters();
+
+        // then
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
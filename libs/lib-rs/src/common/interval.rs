@@ -0,0 +1,356 @@
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// Integer-like values intervals can be built from.
+pub trait Bound: Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> {}
+
+impl<T: Copy + Ord + Default + Add<Output = T> + Sub<Output = T>> Bound for T {}
+
+/// Half-open interval `start..end`, empty when `end <= start`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Bound> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    /// Interval of `length` values from `start`, the way the puzzles usually describe ranges.
+    pub fn with_length(start: T, length: T) -> Self {
+        Interval::new(start, start + length)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether every value of `other` is in this interval, always true for an empty `other`.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+
+    /// Values in both intervals, `None` when there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    pub fn shift(&self, offset: T) -> Self {
+        Interval::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl Interval<i64> {
+    /// Interval from `first` to `last`, both included.
+    pub fn closed(first: i64, last: i64) -> Self {
+        Interval::new(first, last + 1)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Union of intervals, kept sorted with no two of them overlapping or touching.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Bound> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet::new()
+    }
+}
+
+impl<T: Bound> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of values in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::default(), |total, interval| total + interval.len())
+    }
+
+    /// Smallest value in the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|interval| interval.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let idx = self
+            .intervals
+            .partition_point(|interval| interval.end <= value);
+        self.intervals
+            .get(idx)
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // Intervals ending before the new one starts stay, the following ones touching it merge
+        let first = self
+            .intervals
+            .partition_point(|other| other.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|other| other.start <= interval.end);
+        let merged = self.intervals[first..last]
+            .iter()
+            .fold(interval, |merged, other| {
+                Interval::new(merged.start.min(other.start), merged.end.max(other.end))
+            });
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        for &interval in &other.intervals {
+            union.insert(interval);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intersection = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            if let Some(overlap) = a.intersection(b) {
+                intersection.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet {
+            intervals: intersection,
+        }
+    }
+
+    /// Values of this set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let Some(bounds) = self.bounds() else {
+            return IntervalSet::new();
+        };
+        self.intersection(&other.gaps(bounds))
+    }
+
+    /// Values of `within` that are not in the set.
+    pub fn gaps(&self, within: Interval<T>) -> Self {
+        let mut gaps = IntervalSet::new();
+        let mut start = within.start;
+        for interval in &self.intervals {
+            gaps.insert(Interval::new(start, interval.start.min(within.end)));
+            start = start.max(interval.end);
+        }
+        gaps.insert(Interval::new(start, within.end));
+        gaps
+    }
+
+    /// Smallest interval holding the whole set.
+    fn bounds(&self) -> Option<Interval<T>> {
+        let first = self.intervals.first()?;
+        let last = self.intervals.last()?;
+        Some(Interval::new(first.start, last.end))
+    }
+}
+
+impl<T: Bound> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+/// Piecewise-linear map that shifts the values of each source interval by its own offset and
+/// keeps the values outside of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeMap {
+    /// Sorted by start, the source intervals don't overlap.
+    ranges: Vec<(Interval<i64>, i64)>,
+}
+
+impl RangeMap {
+    pub fn new() -> Self {
+        RangeMap::default()
+    }
+
+    /// Shifts the values of `source` by `offset`, `source` must not overlap the earlier ones.
+    pub fn insert(&mut self, source: Interval<i64>, offset: i64) {
+        let idx = self
+            .ranges
+            .partition_point(|(other, _)| other.start < source.start);
+        self.ranges.insert(idx, (source, offset));
+    }
+
+    pub fn ranges(&self) -> &[(Interval<i64>, i64)] {
+        &self.ranges
+    }
+
+    pub fn map(&self, value: i64) -> i64 {
+        self.ranges
+            .iter()
+            .find(|(source, _)| source.contains(value))
+            .map_or(value, |(_, offset)| value + offset)
+    }
+
+    /// Splits `interval` at the boundaries of the source intervals, every piece comes with
+    /// the offset it is shifted by.
+    pub fn split(&self, interval: Interval<i64>) -> Vec<(Interval<i64>, i64)> {
+        let mut pieces = Vec::new();
+        let mut start = interval.start;
+        for (source, offset) in &self.ranges {
+            let Some(overlap) = source.intersection(&interval) else {
+                continue;
+            };
+            if start < overlap.start {
+                pieces.push((Interval::new(start, overlap.start), 0));
+            }
+            pieces.push((overlap, *offset));
+            start = overlap.end;
+        }
+        if start < interval.end {
+            pieces.push((Interval::new(start, interval.end), 0));
+        }
+        pieces
+    }
+
+    /// Image of every value of `set`.
+    pub fn map_set(&self, set: &IntervalSet<i64>) -> IntervalSet<i64> {
+        set.intervals()
+            .iter()
+            .flat_map(|&interval| self.split(interval))
+            .map(|(piece, offset)| piece.shift(offset))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals
+            .iter()
+            .map(|&(start, end)| Interval::new(start, end))
+            .collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        // given
+        let interval = Interval::closed(2, 4);
+
+        // then
+        assert_eq!(interval, Interval::new(2, 5));
+        assert_eq!(interval.len(), 3);
+        assert!(interval.contains(4));
+        assert!(!interval.contains(5));
+        assert!(interval.contains_interval(&Interval::closed(3, 4)));
+        assert!(!interval.contains_interval(&Interval::closed(3, 5)));
+        assert!(interval.overlaps(&Interval::closed(4, 6)));
+        assert!(!interval.overlaps(&Interval::closed(5, 6)));
+        assert_eq!(Interval::new(5, 2).len(), 0);
+        assert_eq!(Interval::with_length(79, 14), Interval::new(79, 93));
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        // given
+        let mut intervals = set(&[(0, 2), (5, 7), (10, 12)]);
+
+        // when
+        intervals.insert(Interval::new(2, 5));
+        intervals.insert(Interval::new(11, 15));
+
+        // then
+        assert_eq!(intervals.intervals(), set(&[(0, 7), (10, 15)]).intervals());
+        assert_eq!(intervals.len(), 12);
+        assert!(intervals.contains(6));
+        assert!(!intervals.contains(7));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        // given
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12)]);
+
+        // then
+        assert_eq!(a.union(&b), set(&[(0, 15)]));
+        assert_eq!(a.intersection(&b), set(&[(3, 5), (10, 12)]));
+        assert_eq!(a.difference(&b), set(&[(0, 3), (12, 15)]));
+        assert_eq!(b.difference(&a), set(&[(5, 10)]));
+        assert_eq!(
+            a.gaps(Interval::new(-2, 20)),
+            set(&[(-2, 0), (5, 10), (15, 20)])
+        );
+        assert_eq!(a.gaps(Interval::new(1, 4)), IntervalSet::new());
+    }
+
+    #[test]
+    fn test_range_map() {
+        // given
+        let mut map = RangeMap::new();
+        map.insert(Interval::with_length(98, 2), 50 - 98);
+        map.insert(Interval::with_length(50, 48), 52 - 50);
+
+        // then
+        assert_eq!(map.map(79), 81);
+        assert_eq!(map.map(98), 50);
+        assert_eq!(map.map(14), 14);
+        assert_eq!(
+            map.split(Interval::new(40, 100)),
+            vec![
+                (Interval::new(40, 50), 0),
+                (Interval::new(50, 98), 2),
+                (Interval::new(98, 100), -48)
+            ]
+        );
+        assert_eq!(
+            map.map_set(&set(&[(40, 60), (98, 99)])),
+            set(&[(40, 50), (50, 51), (52, 62)])
+        );
+    }
+}
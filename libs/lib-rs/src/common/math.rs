@@ -0,0 +1,142 @@
+/// Greatest common divisor, never negative and `0` only when both values are.
+pub fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a as i64
+}
+
+/// Least common multiple, never negative, `None` on overflow.
+pub fn lcm(a: i64, b: i64) -> Option<i64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(i64::abs)
+}
+
+/// [`gcd`] of all `values`, `0` when there are none.
+pub fn gcd_of(values: impl IntoIterator<Item = i64>) -> i64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// [`lcm`] of all `values`, `1` when there are none.
+pub fn lcm_of(values: impl IntoIterator<Item = i64>) -> Option<i64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// `(g, x, y)` with `a * x + b * y = g`, where `g` is the [`gcd`] of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a as i128, b as i128);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+    if old_r < 0 {
+        (old_r, old_x, old_y) = (-old_r, -old_x, -old_y);
+    }
+    (old_r as i64, old_x as i64, old_y as i64)
+}
+
+/// `a mod m` in `0..m`, for a positive `m`.
+pub fn rem(a: i64, m: i64) -> i64 {
+    a.rem_euclid(m)
+}
+
+/// `a * b mod m` in `0..m` without overflowing, for a positive `m`.
+pub fn mul_mod(a: i64, b: i64, m: i64) -> i64 {
+    (a as i128 * b as i128).rem_euclid(m as i128) as i64
+}
+
+/// `base ^ exp mod m` in `0..m` by repeated squaring, for a positive `m`.
+pub fn pow_mod(base: i64, mut exp: u64, m: i64) -> i64 {
+    let mut result = rem(1, m);
+    let mut base = rem(base, m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// `x` in `0..m` with `a * x mod m = 1`, `None` when `a` and `m` are not coprime.
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(rem(a, m), m);
+    (g == 1).then(|| rem(x, m))
+}
+
+/// Chinese remainder theorem: the `x` matching every `x mod modulus = residue`, returned along
+/// with the [`lcm`] of the moduli, every solution is `x` plus a multiple of it.
+///
+/// The moduli must be positive but need not be coprime, `None` when the congruences contradict
+/// each other or the combined modulus overflows.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, m), (residue, modulus)| {
+            let (g, p, _) = extended_gcd(m, modulus);
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+            let combined = lcm(m, modulus)?;
+            // x + m * k matches the new congruence for k = p * diff / g mod modulus / g, which
+            // keeps the result below the combined modulus
+            let k = mul_mod(p, diff / g, modulus / g);
+            Some((x + m * k, combined))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(i64::MAX, 2), None);
+        assert_eq!(gcd_of([12, 18, 27]), 3);
+        assert_eq!(lcm_of([23, 19, 13, 17]), Some(96577));
+        assert_eq!(lcm_of([]), Some(1));
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        // when
+        let (g, x, y) = extended_gcd(240, 46);
+
+        // then
+        assert_eq!(g, 2);
+        assert_eq!(240 * x + 46 * y, 2);
+    }
+
+    #[test]
+    fn test_modular_arithmetic() {
+        assert_eq!(rem(-7, 5), 3);
+        assert_eq!(mul_mod(i64::MAX - 1, i64::MAX - 1, i64::MAX), 1);
+        assert_eq!(mul_mod(-3, 4, 5), 3);
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(7, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 7), Some(5));
+        assert_eq!(mod_inverse(4, 8), None);
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // Not coprime but compatible
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+}
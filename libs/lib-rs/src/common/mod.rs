@@ -1 +1,10 @@
+pub mod bits;
+pub mod cycle;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod interval;
+pub mod math;
+pub mod ocr;
 pub mod parse;
+pub mod search;
@@ -0,0 +1,258 @@
+use crate::common::grid::Grid;
+
+/// Fixed-width capital letters drawn with `#` on a grid, glyph rows use `.` for unlit cells.
+struct Font {
+    width: usize,
+    height: usize,
+    /// Unlit columns between two letters.
+    spacing: usize,
+    glyphs: &'static [(char, &'static [&'static str])],
+}
+
+/// Font of most puzzles, 4 columns by 6 rows.
+const SMALL: Font = Font {
+    width: 4,
+    height: 6,
+    spacing: 1,
+    glyphs: &[
+        ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+        ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+        ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+        ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+        ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+        ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+        ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+        ('I', &[".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+        ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+        ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+        ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+        ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+        ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+        ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+        ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+        ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+    ],
+};
+
+/// Font of the puzzles with larger letters, 6 columns by 10 rows.
+const LARGE: Font = Font {
+    width: 6,
+    height: 10,
+    spacing: 2,
+    glyphs: &[
+        (
+            'A',
+            &[
+                "..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'B',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#",
+                "#....#", "#####.",
+            ],
+        ),
+        (
+            'C',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#....#", ".####.",
+            ],
+        ),
+        (
+            'E',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'F',
+            &[
+                "######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'G',
+            &[
+                ".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#",
+                "#...##", ".###.#",
+            ],
+        ),
+        (
+            'H',
+            &[
+                "#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'J',
+            &[
+                "...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.",
+                "#...#.", ".###..",
+            ],
+        ),
+        (
+            'K',
+            &[
+                "#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..",
+                "#...#.", "#....#",
+            ],
+        ),
+        (
+            'L',
+            &[
+                "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+        (
+            'N',
+            &[
+                "#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##",
+                "#...##", "#....#",
+            ],
+        ),
+        (
+            'P',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....",
+                "#.....", "#.....",
+            ],
+        ),
+        (
+            'R',
+            &[
+                "#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'X',
+            &[
+                "#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.",
+                "#....#", "#....#",
+            ],
+        ),
+        (
+            'Z',
+            &[
+                "######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....",
+                "#.....", "######",
+            ],
+        ),
+    ],
+};
+
+impl Font {
+    /// Letter drawn with its top left corner at `column`, cells outside of the grid are unlit.
+    fn letter_at(&self, grid: &Grid<bool>, column: usize) -> Option<char> {
+        let lit = |pos| grid.get(pos).copied().unwrap_or(false);
+        self.glyphs
+            .iter()
+            .find(|(_, rows)| {
+                rows.iter().enumerate().all(|(row, pattern)| {
+                    pattern
+                        .chars()
+                        .enumerate()
+                        .all(|(offset, c)| (c == '#') == lit((row, column + offset)))
+                })
+            })
+            .map(|&(letter, _)| letter)
+    }
+}
+
+/// Reads the letters drawn on `grid`, the font is picked from its height.
+///
+/// The first letter starts on the left edge, the last one may miss its unlit right columns.
+/// `None` when the height matches no font or a letter is not recognised.
+pub fn read_letters(grid: &Grid<bool>) -> Option<String> {
+    let font = [SMALL, LARGE]
+        .into_iter()
+        .find(|font| font.height == grid.height())?;
+    if grid.width() == 0 {
+        return None;
+    }
+    let stride = font.width + font.spacing;
+    (0..grid.width().div_ceil(stride))
+        .map(|idx| font.letter_at(grid, idx * stride))
+        .collect()
+}
+
+/// [`read_letters`] of a drawing where `#` is lit and any other character is not.
+///
+/// Blank lines above and below the letters are ignored, shorter lines are padded.
+pub fn read_drawing(drawing: &str) -> Option<String> {
+    let lines: Vec<_> = drawing
+        .lines()
+        .skip_while(|line| !line.contains('#'))
+        .collect();
+    let height = lines.iter().rposition(|line| line.contains('#'))? + 1;
+    let width = lines.iter().map(|line| line.chars().count()).max()?;
+    let rows = lines[..height]
+        .iter()
+        .map(|line| {
+            let mut row: Vec<_> = line.chars().map(|c| c == '#').collect();
+            row.resize(width, false);
+            row
+        })
+        .collect();
+    read_letters(&Grid::from_rows(rows)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_small() {
+        // given
+        let drawing = "
+###  #  #  ##   ##   ##  ###  #  # ####
+#  # #  # #  # #  # #  # #  # #  #    #
+###  #  # #    #  # #    ###  #  #   #
+#  # #  # #    #### #    #  # #  #  #
+#  # #  # #  # #  # #  # #  # #  # #
+###   ##   ##  #  #  ##  ###   ##  ####
+";
+
+        // when
+        let letters = read_drawing(drawing);
+
+        // then
+        assert_eq!(letters, Some("BUCACBUZ".to_string()));
+    }
+
+    #[test]
+    fn test_read_large() {
+        // given
+        let drawing = "
+#....#.....###
+#....#......#.
+#....#......#.
+#....#......#.
+######......#.
+#....#......#.
+#....#......#.
+#....#..#...#.
+#....#..#...#.
+#....#...###..";
+
+        // when
+        let letters = read_drawing(drawing);
+
+        // then
+        assert_eq!(letters, Some("HJ".to_string()));
+    }
+
+    #[test]
+    fn test_unknown_letter() {
+        assert_eq!(read_drawing("#\n#\n#\n#\n#"), None);
+        assert_eq!(read_drawing(""), None);
+    }
+}
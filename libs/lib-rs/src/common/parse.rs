@@ -1,14 +1,20 @@
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::str::FromStr;
 
-#[cfg(test)]
-pub mod test_utils {
-    #[macro_export]
-    macro_rules! vec_of_strings {
-    ($($x:expr),*) => (vec![$($x.to_string()),*]);
+use crate::common::grid::Grid;
+use crate::error::SolveError;
+
+/// Parses `token`, reporting its position within `input` on failure.
+pub fn parse_value<T: FromStr>(input: &str, token: &str) -> Result<T, SolveError> {
+    token.parse().map_err(|_| SolveError::at(input, token))
 }
-    #[allow(clippy::all)]
-    pub(crate) use vec_of_strings;
+
+/// Returns the first character of `text` rejected by `is_valid`, as a subslice of `text`.
+pub fn find_invalid(text: &str, is_valid: impl Fn(char) -> bool) -> Option<&str> {
+    text.char_indices()
+        .find(|&(_, c)| !is_valid(c))
+        .map(|(idx, c)| &text[idx..idx + c.len_utf8()])
 }
 
 pub fn parse_numbers(input: &str) -> Vec<i64> {
@@ -36,3 +42,203 @@ pub fn to_non_empty_lines(input: &str) -> Vec<String> {
         .map(|line| line.to_string())
         .collect()
 }
+
+/// Non-empty lines of `input`, as subslices so errors can point into them.
+pub fn non_empty_lines(input: &str) -> impl Iterator<Item = &str> {
+    input.split('\n').filter(|line| !line.trim().is_empty())
+}
+
+/// Blocks of `input` separated by blank lines.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    input
+        .split("\n\n")
+        .map(|section| section.trim_matches('\n'))
+        .filter(|section| !section.trim().is_empty())
+}
+
+/// Splits `line` at the first `separator` into a trimmed key and value.
+pub fn key_value<'a>(
+    input: &str,
+    line: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), SolveError> {
+    let (key, value) = line
+        .split_once(separator)
+        .ok_or_else(|| SolveError::at(input, line))?;
+    Ok((key.trim(), value.trim()))
+}
+
+/// Trimmed items of a comma separated list, empty for a blank `text`.
+pub fn list_items(text: &str) -> impl Iterator<Item = &str> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|_| !text.trim().is_empty())
+}
+
+/// Parses every item of a comma separated list.
+pub fn comma_list<T: FromStr>(input: &str, text: &str) -> Result<Vec<T>, SolveError> {
+    list_items(text)
+        .map(|item| parse_value(input, item))
+        .collect()
+}
+
+/// Matches `line` against `template`, where every `{}` captures the text up to the literal
+/// following it, and returns the captures.
+///
+/// Placeholders must be separated by literal text, the last one takes the rest of the line.
+/// Surrounding whitespace of `line` is ignored. The error points at the first character that
+/// differs from the leading literal, or at the capture whose following literal is missing.
+///
+/// # Panics
+///
+/// When `template` does not have exactly `N` placeholders.
+///
+/// ```ignore
+/// let [x, y] = captures(input, "x=3, y=-4", "x={}, y={}")?;
+/// ```
+pub fn captures<'a, const N: usize>(
+    input: &str,
+    line: &'a str,
+    template: &str,
+) -> Result<[&'a str; N], SolveError> {
+    let line = line.trim();
+    let mut literals = template.split("{}");
+    let prefix = literals.next().unwrap_or_default();
+    let mut rest = line
+        .strip_prefix(prefix)
+        .ok_or_else(|| mismatch(input, line, prefix))?;
+
+    let mut found = Vec::with_capacity(N);
+    for literal in literals {
+        let end = if literal.is_empty() {
+            rest.len()
+        } else {
+            rest.find(literal)
+                .ok_or_else(|| SolveError::at(input, rest))?
+        };
+        found.push(&rest[..end]);
+        rest = &rest[end + literal.len()..];
+    }
+    if !rest.is_empty() {
+        return Err(SolveError::at(input, rest));
+    }
+
+    Ok(found.try_into().unwrap_or_else(|found: Vec<_>| {
+        panic!("{template:?} has {} placeholders, not {N}", found.len())
+    }))
+}
+
+/// Error at the first character of `text` that differs from `expected`, or right after `text`
+/// when it is too short.
+fn mismatch(input: &str, text: &str, expected: &str) -> SolveError {
+    let matching = text
+        .char_indices()
+        .zip(expected.chars())
+        .find(|&((_, a), b)| a != b)
+        .map_or(text.len().min(expected.len()), |((idx, _), _)| idx);
+    let end = text[matching..]
+        .chars()
+        .next()
+        .map_or(matching, |c| matching + c.len_utf8());
+    SolveError::at(input, &text[matching..end])
+}
+
+/// Grid of the characters of every non-empty line, which must all have the same length.
+pub fn char_grid(input: &str) -> Result<Grid<char>, SolveError> {
+    Grid::parse(input, Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_captures() {
+        // given
+        let input = "Sensor at x=2, y=-18: closest";
+
+        // when
+        let [x, y, rest] = captures(input, input, "Sensor at x={}, y={}: {}").unwrap();
+
+        // then
+        assert_eq!((x, y, rest), ("2", "-18", "closest"));
+    }
+
+    #[test]
+    fn test_captures_mismatch() {
+        // given
+        let input = "move 1 from 2 to 3\nmove 4 form 5 to 6";
+        let line = non_empty_lines(input).nth(1).unwrap();
+
+        // when
+        let error = captures::<3>(input, line, "move {} from {} to {}").unwrap_err();
+
+        // then
+        assert_eq!(error, SolveError::parse(2, 6, "4 form 5 to 6"));
+        assert_eq!(
+            captures::<3>(input, input, "mv {} from {} to {}"),
+            Err(SolveError::parse(1, 2, "o"))
+        );
+    }
+
+    #[test]
+    fn test_captures_trailing_text() {
+        // given
+        let input = "x=1, y=2 z";
+
+        // when
+        let error = captures::<2>(input, input, "x={}, y={} ").unwrap_err();
+
+        // then
+        assert_eq!(error, SolveError::parse(1, 10, "z"));
+    }
+
+    #[test]
+    fn test_sections() {
+        // given
+        let input = "a\nb\n\nc\n\n\nd\n";
+
+        // when
+        let sections: Vec<_> = sections(input).collect();
+
+        // then
+        assert_eq!(sections, vec!["a\nb", "c", "d"]);
+    }
+
+    #[test]
+    fn test_key_value() {
+        // given
+        let input = "name: value\nbroken";
+
+        // then
+        assert_eq!(key_value(input, "name: value", ":"), Ok(("name", "value")));
+        assert_eq!(
+            key_value(input, &input[12..], ":"),
+            Err(SolveError::parse(2, 1, "broken"))
+        );
+    }
+
+    #[test]
+    fn test_comma_list() {
+        // given
+        let input = "items: 79, 98,x";
+
+        // then
+        assert_eq!(comma_list::<i64>(input, &input[7..13]), Ok(vec![79, 98]));
+        assert_eq!(comma_list::<i64>(input, ""), Ok(vec![]));
+        assert_eq!(
+            comma_list::<i64>(input, &input[7..]),
+            Err(SolveError::parse(1, 15, "x"))
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod test_utils {
+    #[macro_export]
+    macro_rules! vec_of_strings {
+    ($($x:expr),*) => (vec![$($x.to_string()),*]);
+}
+    #[allow(clippy::all)]
+    pub(crate) use vec_of_strings;
+}
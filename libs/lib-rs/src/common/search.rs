@@ -0,0 +1,347 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Cheapest route found by a search, `nodes` runs from the start to the goal, both included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub cost: C,
+    pub nodes: Vec<N>,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn start(&self) -> Option<&N> {
+        self.nodes.first()
+    }
+
+    pub fn goal(&self) -> Option<&N> {
+        self.nodes.last()
+    }
+
+    /// Number of steps taken, one less than the number of nodes.
+    pub fn steps(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+}
+
+/// Every node reached by a search with its cost and the node it was reached from.
+#[derive(Debug, Clone)]
+pub struct Explored<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Explored<N, C> {
+    fn new() -> Self {
+        Explored {
+            costs: HashMap::new(),
+            parents: HashMap::new(),
+        }
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn costs(&self) -> impl Iterator<Item = (&N, C)> {
+        self.costs.iter().map(|(node, &cost)| (node, cost))
+    }
+
+    /// Route from the closest start to `node`, `None` when it was not reached.
+    pub fn path_to(&self, node: &N) -> Option<Path<N, C>> {
+        let cost = self.cost(node)?;
+        let mut nodes = vec![node.clone()];
+        while let Some(parent) = self.parents.get(nodes.last()?) {
+            nodes.push(parent.clone());
+        }
+        nodes.reverse();
+        Some(Path { cost, nodes })
+    }
+}
+
+/// Breadth first search where every step costs 1, stops at the first node matching `is_goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let (explored, goal) = breadth_first(starts, neighbours, is_goal);
+    explored.path_to(&goal?)
+}
+
+/// Breadth first search through everything reachable from `starts`.
+pub fn bfs_all<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> Explored<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    breadth_first(starts, neighbours, |_| false).0
+}
+
+fn breadth_first<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Explored<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut explored = Explored::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !explored.costs.contains_key(&start) {
+            explored.costs.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return (explored, Some(node));
+        }
+        let cost = explored.costs[&node] + 1;
+        for next in neighbours(&node) {
+            if !explored.costs.contains_key(&next) {
+                explored.costs.insert(next.clone(), cost);
+                explored.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+
+    (explored, None)
+}
+
+/// Cheapest route to the first node matching `is_goal`, `neighbours` gives the next nodes with
+/// the cost of the step to each of them.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+/// Cheapest cost to everything reachable from `starts`.
+pub fn dijkstra_all<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+) -> Explored<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, neighbours, |_| C::default(), |_| false).0
+}
+
+/// [`dijkstra`] guided by `heuristic`, which must never overestimate the cost left to a goal.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbours: impl FnMut(&N) -> I,
+    heuristic: impl FnMut(&N) -> C,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let (explored, goal) = best_first(starts, neighbours, heuristic, is_goal);
+    explored.path_to(&goal?)
+}
+
+/// Entry of the priority queue, the lowest `priority` is popped first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+fn best_first<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> (Explored<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut explored = Explored::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        let cost = C::default();
+        explored.costs.insert(start.clone(), cost);
+        queue.push(Queued {
+            priority: cost + heuristic(&start),
+            cost,
+            node: start,
+        });
+    }
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if explored.costs.get(&node).is_some_and(|&best| best < cost) {
+            // A cheaper way to this node was queued after this one
+            continue;
+        }
+        if is_goal(&node) {
+            return (explored, Some(node));
+        }
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+            if explored
+                .costs
+                .get(&next)
+                .is_none_or(|&best| next_cost < best)
+            {
+                explored.costs.insert(next.clone(), next_cost);
+                explored.parents.insert(next.clone(), node.clone());
+                queue.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    (explored, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 costs 1 each, the shortcut 0 -> 3 costs 5.
+    fn weighted(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (3, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted(node: &u32) -> Vec<u32> {
+        weighted(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        // when
+        let path = bfs([0], unweighted, |&node| node == 3).unwrap();
+
+        // then
+        assert_eq!(path.cost, 1);
+        assert_eq!(path.nodes, vec![0, 3]);
+        assert_eq!(bfs([1], unweighted, |&node| node == 0), None);
+    }
+
+    #[test]
+    fn test_bfs_multiple_starts() {
+        // when
+        let path = bfs([0, 2], unweighted, |&node| node == 3).unwrap();
+
+        // then
+        assert_eq!(path.start(), Some(&0));
+        assert_eq!(path.steps(), 1);
+    }
+
+    #[test]
+    fn test_bfs_all() {
+        // when
+        let explored = bfs_all([1], unweighted);
+
+        // then
+        assert_eq!(explored.cost(&3), Some(2));
+        assert_eq!(explored.cost(&0), None);
+        assert_eq!(explored.path_to(&3).unwrap().nodes, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // when
+        let path = dijkstra([0], weighted, |&node| node == 3).unwrap();
+
+        // then
+        assert_eq!(
+            path,
+            Path {
+                cost: 3,
+                nodes: vec![0, 1, 2, 3]
+            }
+        );
+        assert_eq!(path.goal(), Some(&3));
+    }
+
+    #[test]
+    fn test_dijkstra_all() {
+        // when
+        let explored = dijkstra_all([0], weighted);
+
+        // then
+        let mut costs: Vec<_> = explored.costs().map(|(&node, cost)| (node, cost)).collect();
+        costs.sort();
+        assert_eq!(costs, vec![(0, 0), (1, 1), (2, 2), (3, 3)]);
+    }
+
+    #[test]
+    fn test_astar() {
+        // given
+        // A wall on x=2 with a gap at y=5
+        let open = |&(x, y): &(i32, i32)| {
+            (0..=5).contains(&x) && (0..=5).contains(&y) && (x != 2 || y == 5)
+        };
+        let neighbours = |&(x, y): &(i32, i32)| {
+            [(1, 0), (-1, 0), (0, 1), (0, -1)]
+                .into_iter()
+                .map(move |(dx, dy)| ((x + dx, y + dy), 1))
+                .filter(|(next, _)| open(next))
+        };
+        let manhattan = |&(x, y): &(i32, i32)| (4 - x).abs() + y.abs();
+
+        // when
+        let path = astar([(0, 0)], neighbours, manhattan, |&node| node == (4, 0)).unwrap();
+
+        // then
+        assert_eq!(path.cost, 14);
+        assert_eq!(path.steps(), 14);
+        assert!(path.nodes.contains(&(2, 5)));
+    }
+}
@@ -0,0 +1,435 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use lib_rs::params::RawParams;
+use lib_rs::utils::DEFAULT_PROFILE;
+use lib_rs::Part;
+
+pub const USAGE: &str = "\
+Usage: console <command> [options]
+
+Commands:
+  run <year> <day>     Solve a single day
+      --part <1|2>     Solve only one part
+      --input <path>   Read the input from a file, `-` reads stdin
+      --param <k=v>    Override a puzzle constant, e.g. --param row=10,
+                       repeatable
+      --example <n>    Solve the n-th example of the puzzle text and
+                       compare with its answers
+  all [year]           Solve every implemented day
+  list [year]          List implemented days
+  verify [year]        Compare answers with data/<year>/answers.json
+  record <year> [day]  Store the current answers in data/<year>/answers.json
+
+Options:
+  --format <text|json|markdown>   Output format, defaults to text
+  --profile <name>                Input profile, data/<year>/<day>/<name>.txt
+                                  or the older data/<year>/.<name>/<day>.txt.
+                                  `run` defaults to data/<year>/<day>.txt, the
+                                  other commands go through every profile
+  --bigboy                        Same as --profile bigboy
+  --data-dir <path>               Directory with <year>/<day>.txt inputs, overrides
+                                  AOC_DATA_DIR and data_dir in .aoc.json
+  -h, --help                      Print this message";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Args {
+    pub command: Command,
+    pub format: Format,
+    pub data_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    Run {
+        year: usize,
+        day: usize,
+        part: Option<Part>,
+        input: Input,
+        params: RawParams,
+    },
+    All {
+        year: Option<usize>,
+        profile: Option<String>,
+    },
+    List {
+        year: Option<usize>,
+    },
+    Verify {
+        year: Option<usize>,
+        profile: Option<String>,
+    },
+    Record {
+        year: usize,
+        day: Option<usize>,
+        profile: Option<String>,
+    },
+    Help,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    Data {
+        profile: String,
+    },
+    File(PathBuf),
+    Stdin,
+    /// Index into the examples of the day, `--example` counts from 1.
+    Example(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Markdown,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArgsError(String);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+fn error<T>(message: impl Into<String>) -> Result<T, ArgsError> {
+    Err(ArgsError(message.into()))
+}
+
+fn parse_number(name: &str, value: &str) -> Result<usize, ArgsError> {
+    value
+        .parse()
+        .or_else(|_| error(format!("{name} must be a number, got {value:?}")))
+}
+
+fn parse_part(value: &str) -> Result<Part, ArgsError> {
+    match value {
+        "1" => Ok(Part::First),
+        "2" => Ok(Part::Second),
+        _ => error(format!("Part must be 1 or 2, got {value:?}")),
+    }
+}
+
+fn parse_format(value: &str) -> Result<Format, ArgsError> {
+    match value {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "markdown" | "md" => Ok(Format::Markdown),
+        _ => error(format!("Unknown format {value:?}")),
+    }
+}
+
+/// Parses the arguments following the binary name.
+///
+/// The old `<year> <day> [bigboy]` form is still accepted as a shorthand for `run`.
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Args, ArgsError> {
+    let mut format = Format::Text;
+    let mut data_dir = None;
+    let mut part = None;
+    let mut input = None;
+    let mut profile = None;
+    let mut params = RawParams::new();
+    let mut positional = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value_of = |flag: &str| {
+            args.next()
+                .map_or_else(|| error(format!("Missing value for {flag}")), Ok)
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                return Ok(Args {
+                    command: Command::Help,
+                    format,
+                    data_dir,
+                })
+            }
+            "--format" => format = parse_format(&value_of("--format")?)?,
+            "--part" => part = Some(parse_part(&value_of("--part")?)?),
+            "--input" => {
+                input = Some(match value_of("--input")?.as_str() {
+                    "-" => Input::Stdin,
+                    path => Input::File(PathBuf::from(path)),
+                })
+            }
+            "--example" => {
+                let number = parse_number("Example", &value_of("--example")?)?;
+                if number == 0 {
+                    return error("Examples are numbered from 1");
+                }
+                input = Some(Input::Example(number - 1));
+            }
+            "--data-dir" => data_dir = Some(PathBuf::from(value_of("--data-dir")?)),
+            "--profile" => profile = Some(value_of("--profile")?),
+            "--param" => {
+                let pair = value_of("--param")?;
+                let Some((key, value)) = RawParams::parse_pair(&pair) else {
+                    return error(format!("Parameter must be key=value, got {pair:?}"));
+                };
+                params.insert(key, value);
+            }
+            "--bigboy" => profile = Some("bigboy".to_string()),
+            flag if flag.starts_with("--") => return error(format!("Unknown option {flag}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.iter().map(String::as_str);
+    let name = positional.next();
+    if name.is_some_and(|name| name.parse::<usize>().is_ok()) {
+        let year = parse_number("Year", name.unwrap_or_default())?;
+        let day = parse_number("Day", positional.next().unwrap_or_default())?;
+        let profile = match positional.next() {
+            Some(_) => "bigboy".to_string(),
+            None => profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+        };
+        return Ok(Args {
+            command: Command::Run {
+                year,
+                day,
+                part,
+                input: input.unwrap_or(Input::Data { profile }),
+                params,
+            },
+            format,
+            data_dir,
+        });
+    }
+
+    if !params.is_empty() && name != Some("run") {
+        return error("--param can only be used with run");
+    }
+    let year = |value: Option<&str>| value.map(|year| parse_number("Year", year)).transpose();
+    let command = match name {
+        None => Command::Help,
+        Some("run") => {
+            let (Some(year), Some(day)) = (positional.next(), positional.next()) else {
+                return error("Usage: console run <year> <day>");
+            };
+            Command::Run {
+                year: parse_number("Year", year)?,
+                day: parse_number("Day", day)?,
+                part,
+                input: input.unwrap_or(Input::Data {
+                    profile: profile.unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+                }),
+                params,
+            }
+        }
+        Some("all") => Command::All {
+            year: year(positional.next())?,
+            profile,
+        },
+        Some("list") => Command::List {
+            year: year(positional.next())?,
+        },
+        Some("verify") => Command::Verify {
+            year: year(positional.next())?,
+            profile,
+        },
+        Some("record") => {
+            let Some(year) = positional.next() else {
+                return error("Usage: console record <year> [day]");
+            };
+            Command::Record {
+                year: parse_number("Year", year)?,
+                day: positional
+                    .next()
+                    .map(|day| parse_number("Day", day))
+                    .transpose()?,
+                profile,
+            }
+        }
+        Some(other) => return error(format!("Unknown command {other:?}")),
+    };
+    if let Some(extra) = positional.next() {
+        return error(format!("Unexpected argument {extra:?}"));
+    }
+    Ok(Args {
+        command,
+        format,
+        data_dir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_str(args: &str) -> Result<Args, ArgsError> {
+        parse(args.split_whitespace().map(str::to_string))
+    }
+
+    #[test]
+    fn test_parse_run() {
+        // when
+        let args = parse_str("run 2022 6 --part 2 --input - --format json").unwrap();
+
+        // then
+        assert_eq!(
+            args,
+            Args {
+                command: Command::Run {
+                    year: 2022,
+                    day: 6,
+                    part: Some(Part::Second),
+                    input: Input::Stdin,
+                    params: RawParams::new(),
+                },
+                format: Format::Json,
+                data_dir: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_run_params() {
+        // when
+        let args = parse_str("run 2022 15 --param row=10 --param limit=20").unwrap();
+
+        // then
+        match args.command {
+            Command::Run { params, .. } => assert_eq!(
+                params,
+                [("limit", "20"), ("row", "10")].into_iter().collect()
+            ),
+            command => panic!("Unexpected command {command:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_run_example() {
+        let input = |args| match parse_str(args).map(|args| args.command) {
+            Ok(Command::Run { input, .. }) => Ok(input),
+            Ok(command) => panic!("Unexpected command {command:?}"),
+            Err(err) => Err(err),
+        };
+
+        assert_eq!(input("run 2022 6 --example 2"), Ok(Input::Example(1)));
+        assert_eq!(
+            input("run 2022 6 --example 0"),
+            error("Examples are numbered from 1")
+        );
+    }
+
+    #[test]
+    fn test_parse_run_profile() {
+        let input = |args| match parse_str(args).unwrap().command {
+            Command::Run { input, .. } => input,
+            command => panic!("Unexpected command {command:?}"),
+        };
+        let data = |profile: &str| Input::Data {
+            profile: profile.to_string(),
+        };
+
+        assert_eq!(input("run 2022 16"), data("default"));
+        assert_eq!(input("run 2022 16 --profile alice"), data("alice"));
+        assert_eq!(input("run 2022 16 --bigboy"), data("bigboy"));
+    }
+
+    #[test]
+    fn test_parse_legacy_positional() {
+        // when
+        let args = parse_str("2021 3 bigboy").unwrap();
+
+        // then
+        assert_eq!(
+            args.command,
+            Command::Run {
+                year: 2021,
+                day: 3,
+                part: None,
+                input: Input::Data {
+                    profile: "bigboy".to_string()
+                },
+                params: RawParams::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_year_filters() {
+        assert_eq!(
+            parse_str("all").unwrap().command,
+            Command::All {
+                year: None,
+                profile: None
+            }
+        );
+        assert_eq!(
+            parse_str("verify 2023 --profile alice --format markdown").unwrap(),
+            Args {
+                command: Command::Verify {
+                    year: Some(2023),
+                    profile: Some("alice".to_string()),
+                },
+                format: Format::Markdown,
+                data_dir: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_data_dir() {
+        // when
+        let args = parse_str("all 2022 --data-dir /home/alice/aoc").unwrap();
+
+        // then
+        assert_eq!(args.data_dir, Some(PathBuf::from("/home/alice/aoc")));
+    }
+
+    #[test]
+    fn test_parse_record() {
+        assert_eq!(
+            parse_str("record 2022 10").unwrap().command,
+            Command::Record {
+                year: 2022,
+                day: Some(10),
+                profile: None,
+            }
+        );
+        assert_eq!(
+            parse_str("record").unwrap_err().to_string(),
+            "Usage: console record <year> [day]"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_str("run 2022"),
+            error("Usage: console run <year> <day>")
+        );
+        assert_eq!(
+            parse_str("run 2022 x"),
+            error("Day must be a number, got \"x\"")
+        );
+        assert_eq!(
+            parse_str("run 2022 1 --part 3"),
+            error("Part must be 1 or 2, got \"3\"")
+        );
+        assert_eq!(
+            parse_str("list --format"),
+            error("Missing value for --format")
+        );
+        assert_eq!(
+            parse_str("frobnicate"),
+            error("Unknown command \"frobnicate\"")
+        );
+        assert_eq!(
+            parse_str("run 2022 15 --param row"),
+            error("Parameter must be key=value, got \"row\"")
+        );
+        assert_eq!(
+            parse_str("all 2022 --param row=10"),
+            error("--param can only be used with run")
+        );
+        assert_eq!(
+            parse_str("list 2022 2023"),
+            error("Unexpected argument \"2023\"")
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use serde::Serialize;
+
+use super::args::Format;
+use lib_rs::utils::DEFAULT_PROFILE;
+
+/// Outcome of a single part, the unit every command reports.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartRow {
+    pub year: usize,
+    pub day: usize,
+    /// Input profile from the data directory, `None` for `--input` files and stdin.
+    pub profile: Option<String>,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub duration_ms: f64,
+    /// Picture the answer was read from, for the days that draw one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub drawing: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct YearDays {
+    pub year: usize,
+    pub days: Vec<usize>,
+}
+
+impl PartRow {
+    pub fn is_failure(&self) -> bool {
+        self.error.is_some() || self.status == Some(Status::Fail)
+    }
+}
+
+fn status_label(status: Status) -> &'static str {
+    match status {
+        Status::Pass => "pass",
+        Status::Fail => "FAIL",
+        Status::Missing => "missing",
+    }
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default() + "\n"
+}
+
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+pub fn render_parts(rows: &[PartRow], format: Format) -> String {
+    match format {
+        Format::Text => parts_text(rows),
+        Format::Json => json(rows),
+        Format::Markdown => parts_markdown(rows),
+    }
+}
+
+fn parts_text(rows: &[PartRow]) -> String {
+    let mut out = String::new();
+    for row in rows {
+        let status = row
+            .status
+            .map(|status| format!("[{}] ", status_label(status)))
+            .unwrap_or_default();
+        let profile = match row.profile.as_deref() {
+            Some(DEFAULT_PROFILE) | None => String::new(),
+            Some(profile) => format!(" [{profile}]"),
+        };
+        let label = format!(
+            "{status}{} day {:>2} part {}{profile}",
+            row.year, row.day, row.part
+        );
+        let line = match (&row.answer, &row.error) {
+            (_, Some(error)) => format!("{label} failed: {error}"),
+            (Some(answer), None) if answer.contains('\n') => {
+                format!("{label} ({:.1} ms):\n{answer}", row.duration_ms)
+            }
+            (Some(answer), None) => format!("{label}: {answer} ({:.1} ms)", row.duration_ms),
+            (None, None) => label,
+        };
+        out.push_str(&line);
+        out.push('\n');
+        if let Some(drawing) = &row.drawing {
+            out.push_str(drawing.trim_end_matches('\n'));
+            out.push('\n');
+        }
+        if let (Some(Status::Fail), Some(expected)) = (row.status, &row.expected) {
+            out.push_str(&format!("    expected: {expected}\n"));
+        }
+    }
+    if rows.iter().any(|row| row.status.is_some()) {
+        out.push_str(&summary(rows));
+        out.push('\n');
+    }
+    out
+}
+
+fn parts_markdown(rows: &[PartRow]) -> String {
+    let verify = rows.iter().any(|row| row.status.is_some());
+    let mut out = String::from("| Year | Day | Profile | Part | Answer | Time (ms) |");
+    out.push_str(if verify { " Status |\n" } else { "\n" });
+    out.push_str("|------|-----|---------|------|--------|-----------|");
+    out.push_str(if verify { "--------|\n" } else { "\n" });
+
+    for row in rows {
+        let answer = match (&row.answer, &row.error) {
+            (_, Some(error)) => format!("error: {}", markdown_cell(error)),
+            (Some(answer), None) => format!("`{}`", markdown_cell(answer)),
+            (None, None) => String::new(),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {:.1} |",
+            row.year,
+            row.day,
+            markdown_cell(row.profile.as_deref().unwrap_or_default()),
+            row.part,
+            answer,
+            row.duration_ms
+        ));
+        if let Some(status) = row.status {
+            out.push_str(&format!(" {} |", status_label(status)));
+        }
+        out.push('\n');
+    }
+    if verify {
+        out.push('\n');
+        out.push_str(&summary(rows));
+        out.push('\n');
+    }
+    out
+}
+
+fn summary(rows: &[PartRow]) -> String {
+    let count = |status| rows.iter().filter(|row| row.status == Some(status)).count();
+    format!(
+        "{} passed, {} failed, {} missing",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Missing)
+    )
+}
+
+pub fn render_list(years: &[YearDays], format: Format) -> String {
+    let days = |days: &[usize]| {
+        days.iter()
+            .map(|day| day.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match format {
+        Format::Text => years
+            .iter()
+            .map(|year| format!("{}: {}\n", year.year, days(&year.days)))
+            .collect(),
+        Format::Json => json(years),
+        Format::Markdown => {
+            let mut out = String::from("| Year | Days |\n|------|------|\n");
+            for year in years {
+                out.push_str(&format!("| {} | {} |\n", year.year, days(&year.days)));
+            }
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(day: usize, answer: &str, status: Option<Status>) -> PartRow {
+        PartRow {
+            year: 2022,
+            day,
+            profile: Some(DEFAULT_PROFILE.to_string()),
+            part: 1,
+            answer: Some(answer.to_string()),
+            error: None,
+            duration_ms: 1.25,
+            drawing: None,
+            expected: Some("7".to_string()),
+            status,
+        }
+    }
+
+    #[test]
+    fn test_text_verify() {
+        // given
+        let rows = vec![
+            row(6, "7", Some(Status::Pass)),
+            row(7, "8", Some(Status::Fail)),
+            PartRow {
+                profile: Some("alice".to_string()),
+                ..row(7, "9", Some(Status::Pass))
+            },
+        ];
+
+        // when
+        let text = render_parts(&rows, Format::Text);
+
+        // then
+        assert_eq!(
+            text,
+            "[pass] 2022 day  6 part 1: 7 (1.2 ms)\n\
+             [FAIL] 2022 day  7 part 1: 8 (1.2 ms)\n    expected: 7\n\
+             [pass] 2022 day  7 part 1 [alice]: 9 (1.2 ms)\n\
+             2 passed, 1 failed, 0 missing\n"
+        );
+    }
+
+    #[test]
+    fn test_text_drawing() {
+        // given
+        let rows = vec![PartRow {
+            drawing: Some("#..#\n####\n".to_string()),
+            ..row(10, "H", None)
+        }];
+
+        // when
+        let text = render_parts(&rows, Format::Text);
+
+        // then
+        assert_eq!(
+            text,
+            "2022 day 10 part 1: H (1.2 ms)\n\
+             #..#\n\
+             ####\n"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes_cells() {
+        // given
+        let rows = vec![row(10, "#|.\n.#.", None)];
+
+        // when
+        let markdown = render_parts(&rows, Format::Markdown);
+
+        // then
+        assert_eq!(
+            markdown,
+            "| Year | Day | Profile | Part | Answer | Time (ms) |\n\
+             |------|-----|---------|------|--------|-----------|\n\
+             | 2022 | 10 | default | 1 | `#\\|.<br>.#.` | 1.2 |\n"
+        );
+    }
+
+    #[test]
+    fn test_json_list() {
+        // given
+        let years = vec![YearDays {
+            year: 2023,
+            days: vec![1, 2],
+        }];
+
+        // when
+        let json = render_list(&years, Format::Json);
+
+        // then
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json).unwrap(),
+            serde_json::json!([{ "year": 2023, "days": [1, 2] }])
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use crate::Part;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    NotImplemented {
+        year: usize,
+        day: usize,
+        part: Part,
+    },
+    /// Line and column are 1-based, `text` is the offending part of the input.
+    ParseError {
+        line: usize,
+        column: usize,
+        text: String,
+    },
+    NoSolution,
+    Overflow,
+    InvalidParam {
+        key: String,
+        message: String,
+    },
+    MissingExample {
+        year: usize,
+        day: usize,
+        index: usize,
+    },
+}
+
+impl SolveError {
+    pub fn parse(line: usize, column: usize, text: &str) -> Self {
+        SolveError::ParseError {
+            line,
+            column,
+            text: text.to_string(),
+        }
+    }
+
+    pub fn invalid_param(key: &str, message: &str) -> Self {
+        SolveError::InvalidParam {
+            key: key.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Parse error pointing at `text` within `input`.
+    ///
+    /// Works best when `text` is a subslice of `input`, otherwise the first
+    /// occurrence of `text` is reported.
+    pub fn at(input: &str, text: &str) -> Self {
+        let start = input.as_ptr() as usize;
+        let ptr = text.as_ptr() as usize;
+        let offset = if ptr >= start && ptr + text.len() <= start + input.len() {
+            ptr - start
+        } else {
+            input.find(text).unwrap_or(0)
+        };
+
+        let before = &input[..offset];
+        let line = before.matches('\n').count() + 1;
+        let column = offset - before.rfind('\n').map_or(0, |idx| idx + 1) + 1;
+        SolveError::parse(line, column, text)
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::NotImplemented { year, day, part } => {
+                write!(f, "Year {year} Day {day} Part {part:?} not implemented")
+            }
+            SolveError::ParseError { line, column, text } => {
+                write!(f, "Cannot parse {text:?} at line {line}, column {column}")
+            }
+            SolveError::NoSolution => write!(f, "No solution found"),
+            SolveError::Overflow => write!(f, "Arithmetic overflow"),
+            SolveError::InvalidParam { key, message } => {
+                write!(f, "Invalid parameter {key}: {message}")
+            }
+            SolveError::MissingExample { year, day, index } => {
+                write!(f, "Year {year} Day {day} has no example {index}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_subslice() {
+        // given
+        let input = "forward 5\ndown x\nup 3";
+        let text = &input[15..16];
+
+        // when
+        let error = SolveError::at(input, text);
+
+        // then
+        assert_eq!(error, SolveError::parse(2, 6, "x"));
+    }
+
+    #[test]
+    fn test_at_copied_text() {
+        // given
+        let input = "1,2\n3,?,4";
+        let text = String::from("?");
+
+        // when
+        let error = SolveError::at(input, &text);
+
+        // then
+        assert_eq!(error, SolveError::parse(2, 3, "?"));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            SolveError::parse(3, 1, "abc").to_string(),
+            "Cannot parse \"abc\" at line 3, column 1"
+        );
+    }
+}
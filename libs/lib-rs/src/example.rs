@@ -0,0 +1,111 @@
+use serde::{Serialize, Serializer};
+use wasm_bindgen::prelude::*;
+
+use crate::params::RawParams;
+use crate::Part;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_EXAMPLE: &'static str = r#"
+export interface Example {
+    input: string;
+    params: Record<string, string>;
+    first?: string;
+    second?: string;
+}
+"#;
+
+/// An example input from the puzzle text along with the answers it should produce.
+///
+/// Some puzzles give a different example for each part, the missing answer is `None`.
+/// `params` are the puzzle constants the example uses instead of the real ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Example {
+    pub input: &'static str,
+    #[serde(serialize_with = "serialize_params")]
+    pub params: &'static [(&'static str, &'static str)],
+    pub first: Option<&'static str>,
+    pub second: Option<&'static str>,
+}
+
+fn serialize_params<S: Serializer>(
+    params: &&'static [(&'static str, &'static str)],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(params.iter().copied())
+}
+
+impl Example {
+    pub const fn new(input: &'static str) -> Self {
+        Example {
+            input,
+            params: &[],
+            first: None,
+            second: None,
+        }
+    }
+
+    pub const fn params(self, params: &'static [(&'static str, &'static str)]) -> Self {
+        Example { params, ..self }
+    }
+
+    pub const fn first(self, answer: &'static str) -> Self {
+        Example {
+            first: Some(answer),
+            ..self
+        }
+    }
+
+    pub const fn second(self, answer: &'static str) -> Self {
+        Example {
+            second: Some(answer),
+            ..self
+        }
+    }
+
+    pub fn expected(&self, part: Part) -> Option<&'static str> {
+        match part {
+            Part::First => self.first,
+            Part::Second => self.second,
+        }
+    }
+
+    pub fn raw_params(&self) -> RawParams {
+        self.params.iter().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        // when
+        let example = Example::new("3,4,3,1,2")
+            .params(&[("first_days", "18")])
+            .first("26");
+
+        // then
+        assert_eq!(example.expected(Part::First), Some("26"));
+        assert_eq!(example.expected(Part::Second), None);
+        assert_eq!(
+            example.raw_params(),
+            [("first_days", "18")].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn test_serialize() {
+        // given
+        let example = Example::new("1\n2").params(&[("row", "10")]).second("3");
+
+        // when
+        let json = serde_json::to_string(&example).unwrap();
+
+        // then
+        assert_eq!(
+            json,
+            r#"{"input":"1\n2","params":{"row":"10"},"first":null,"second":"3"}"#
+        );
+    }
+}
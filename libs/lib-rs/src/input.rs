@@ -0,0 +1,176 @@
+use itertools::Itertools;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt;
+
+/// What [`normalize`] had to fix in a puzzle input.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InputChanges {
+    /// A leading UTF-8 byte order mark was removed.
+    pub bom: bool,
+    /// Windows (`\r\n`) and old Mac (`\r`) line endings converted to `\n`.
+    pub line_endings: usize,
+    /// Tabs replaced with a single space.
+    pub tabs: usize,
+    /// Blank lines removed from the end, a single final newline is not counted.
+    pub trailing_blank_lines: usize,
+    /// Lines that had trailing spaces or tabs cut off.
+    pub trimmed_lines: usize,
+}
+
+impl InputChanges {
+    pub fn is_empty(&self) -> bool {
+        *self == InputChanges::default()
+    }
+}
+
+impl fmt::Display for InputChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut changes = Vec::new();
+        if self.bom {
+            changes.push("removed byte order mark".to_string());
+        }
+        if self.line_endings > 0 {
+            changes.push(format!("converted {} line endings", self.line_endings));
+        }
+        if self.tabs > 0 {
+            changes.push(format!("replaced {} tabs", self.tabs));
+        }
+        if self.trailing_blank_lines > 0 {
+            changes.push(format!(
+                "removed {} trailing blank lines",
+                self.trailing_blank_lines
+            ));
+        }
+        if self.trimmed_lines > 0 {
+            changes.push(format!(
+                "trimmed trailing whitespace from {} lines",
+                self.trimmed_lines
+            ));
+        }
+        match changes.is_empty() {
+            true => write!(f, "no changes"),
+            false => write!(f, "{}", changes.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Normalized<'a> {
+    pub text: Cow<'a, str>,
+    pub changes: InputChanges,
+}
+
+fn line_breaks(text: &str) -> usize {
+    text.matches('\n').count() + text.matches('\r').count() - text.matches("\r\n").count()
+}
+
+/// Brings pasted input into the shape every parser expects: `\n` line endings,
+/// no BOM, no tabs and no trailing whitespace on any line.
+///
+/// Leading whitespace is significant (e.g. 2022 day 5 drawing) and is kept.
+pub fn normalize(input: &str) -> Normalized<'_> {
+    let mut changes = InputChanges::default();
+
+    let mut text = input;
+    if let Some(rest) = text.strip_prefix('\u{feff}') {
+        changes.bom = true;
+        text = rest;
+    }
+
+    // Cut after the last line with content, its own trailing whitespace is trimmed below
+    let content = text.trim_end().len();
+    let end = text[content..]
+        .find(['\r', '\n'])
+        .map_or(text.len(), |idx| content + idx);
+    changes.trailing_blank_lines = line_breaks(&text[end..]).saturating_sub(1);
+    let text = &text[..end];
+
+    // Every `\r` is either half of a `\r\n` or a line ending on its own
+    changes.line_endings = text.matches('\r').count();
+    let text = match changes.line_endings {
+        0 => Cow::Borrowed(text),
+        _ => Cow::Owned(text.replace("\r\n", "\n").replace('\r', "\n")),
+    };
+
+    changes.trimmed_lines = text
+        .split('\n')
+        .filter(|line| line.len() != line.trim_end().len())
+        .count();
+    let text = match changes.trimmed_lines {
+        0 => text,
+        _ => Cow::Owned(text.split('\n').map(str::trim_end).join("\n")),
+    };
+
+    // Tabs cut off with a line end are counted as trimmed, not replaced
+    changes.tabs = text.matches('\t').count();
+    let text = match changes.tabs {
+        0 => text,
+        _ => Cow::Owned(text.replace('\t', " ")),
+    };
+    Normalized { text, changes }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_input_is_borrowed() {
+        // given
+        let input = "    [D]\n1 2\n";
+
+        // when
+        let normalized = normalize(input);
+
+        // then
+        assert!(matches!(normalized.text, Cow::Borrowed("    [D]\n1 2")));
+        assert!(normalized.changes.is_empty());
+    }
+
+    #[test]
+    fn test_pasted_windows_input() {
+        // given
+        let input = "\u{feff}1\t2\r\n3\r4\r\n\r\n\r\n";
+
+        // when
+        let normalized = normalize(input);
+
+        // then
+        assert_eq!(normalized.text, "1 2\n3\n4");
+        assert_eq!(
+            normalized.changes,
+            InputChanges {
+                bom: true,
+                line_endings: 2,
+                tabs: 1,
+                trailing_blank_lines: 2,
+                trimmed_lines: 0,
+            }
+        );
+        assert_eq!(
+            normalized.changes.to_string(),
+            "removed byte order mark, converted 2 line endings, replaced 1 tabs, removed 2 trailing blank lines"
+        );
+    }
+
+    #[test]
+    fn test_trailing_whitespace_on_each_line() {
+        // given
+        let input = "1  \n2\t\n";
+
+        // when
+        let normalized = normalize(input);
+
+        // then
+        assert_eq!(normalized.text, "1\n2");
+        assert_eq!(normalized.changes.trimmed_lines, 2);
+        assert_eq!(normalized.changes.tabs, 0);
+        assert_eq!(
+            normalize(" \t[D] \r\n1\t2").text,
+            "  [D]\n1 2",
+            "leading whitespace is kept"
+        );
+    }
+}
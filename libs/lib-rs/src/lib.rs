@@ -4,12 +4,29 @@ extern crate js_sys;
 
 use wasm_bindgen::prelude::*;
 
+use crate::common::parse::parse_value;
+use crate::error::SolveError;
+use crate::input::Normalized;
+use crate::params::RawParams;
+use crate::report::SolveReport;
+
+pub mod answers;
 mod aoc_2021;
 mod aoc_2022;
 mod aoc_2023;
-mod common;
+pub mod common;
+pub mod error;
+pub mod example;
+pub mod input;
+pub mod params;
+pub mod registry;
+pub mod report;
+pub mod solution;
 pub mod utils;
 
+pub use crate::aoc_2021::day16::Packet;
+pub use crate::aoc_2021::day18::SnailfishNumber;
+
 #[macro_export]
 macro_rules! log {
     ( $( $t:tt )* ) => {
@@ -38,107 +55,287 @@ pub enum Part {
 
 #[allow(unused)]
 #[wasm_bindgen]
-pub fn run(year: usize, day: usize, part: Part, input: &str) -> String {
+pub fn run(year: usize, day: usize, part: Part, input: &str) -> Result<String, JsError> {
+    run_with(year, day, part, input, JsValue::UNDEFINED)
+}
+
+/// Like [`run`], `params` is an object such as `{ row: 10, limit: 20 }` overriding
+/// the defaults listed by [`params`].
+#[allow(unused)]
+#[wasm_bindgen]
+pub fn run_with(
+    year: usize,
+    day: usize,
+    part: Part,
+    input: &str,
+    params: JsValue,
+) -> Result<String, JsError> {
+    utils::set_panic_hook();
+    let params = raw_params(&params)?;
+    let input = input::normalize(input);
+    if !input.changes.is_empty() {
+        log!("Normalized input: {}", input.changes);
+    }
+    run_normalized(year, day, part, &input, &params).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Solves a single part after normalizing the input, see [`input::normalize`].
+pub fn run_base(
+    year: usize,
+    day: usize,
+    part: Part,
+    input: &str,
+    params: &RawParams,
+) -> Result<String, SolveError> {
+    run_normalized(year, day, part, &input::normalize(input), params)
+}
+
+fn run_normalized(
+    year: usize,
+    day: usize,
+    part: Part,
+    input: &Normalized,
+    params: &RawParams,
+) -> Result<String, SolveError> {
+    registry::find(year, day)
+        .ok_or(SolveError::NotImplemented { year, day, part })?
+        .run(&input.text, part, params)
+}
+
+fn raw_params(params: &JsValue) -> Result<RawParams, JsError> {
+    if params.is_undefined() || params.is_null() {
+        return Ok(RawParams::new());
+    }
+    let object = params
+        .dyn_ref::<js_sys::Object>()
+        .ok_or_else(|| JsError::new("Parameters must be an object"))?;
+    js_sys::Object::entries(object)
+        .iter()
+        .map(|entry| {
+            let entry = js_sys::Array::from(&entry);
+            let key = entry.get(0).as_string().unwrap_or_default();
+            let value = entry.get(1);
+            let value = value
+                .as_string()
+                .or_else(|| value.as_f64().map(|number| number.to_string()))
+                .ok_or_else(|| {
+                    JsError::new(&format!("Parameter {key} must be a string or a number"))
+                })?;
+            Ok((key, value))
+        })
+        .collect()
+}
+
+fn find_entry(year: usize, day: usize) -> Result<&'static registry::Entry, JsError> {
+    registry::find(year, day)
+        .ok_or_else(|| JsError::new(&format!("Year {year} Day {day} not implemented")))
+}
+
+/// Lists the parameters of a day as `{ key, default, description }` objects.
+#[allow(unused)]
+#[wasm_bindgen]
+pub fn params(year: usize, day: usize) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(&find_entry(year, day)?.params())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Lists the examples of a day as `Example` objects.
+#[allow(unused)]
+#[wasm_bindgen]
+pub fn examples(year: usize, day: usize) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(find_entry(year, day)?.examples())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Solves a part of the example at `index` of [`examples`].
+#[allow(unused)]
+#[wasm_bindgen]
+pub fn run_example(year: usize, day: usize, index: usize, part: Part) -> Result<String, JsError> {
     utils::set_panic_hook();
-    run_base(year, day, part, input)
-}
-
-pub fn run_base(year: usize, day: usize, part: Part, input: &str) -> String {
-    match (year, day, part) {
-        // 2021
-        (2021, 1, Part::First) => aoc_2021::day01::run_first(input),
-        (2021, 1, Part::Second) => aoc_2021::day01::run_second(input),
-        (2021, 2, Part::First) => aoc_2021::day02::run_first(input),
-        (2021, 2, Part::Second) => aoc_2021::day02::run_second(input),
-        (2021, 3, Part::First) => aoc_2021::day03::run_first(input),
-        (2021, 3, Part::Second) => aoc_2021::day03::run_second(input),
-        (2021, 4, Part::First) => aoc_2021::day04::run_first(input),
-        (2021, 4, Part::Second) => aoc_2021::day04::run_second(input),
-        (2021, 5, Part::First) => aoc_2021::day05::run_first(input),
-        (2021, 5, Part::Second) => aoc_2021::day05::run_second(input),
-        (2021, 6, Part::First) => aoc_2021::day06::run_first(input),
-        (2021, 6, Part::Second) => aoc_2021::day06::run_second(input),
-        (2021, 7, Part::First) => aoc_2021::day07::run_first(input),
-        (2021, 7, Part::Second) => aoc_2021::day07::run_second(input),
-        (2021, 8, Part::First) => aoc_2021::day08::run_first(input),
-        (2021, 8, Part::Second) => aoc_2021::day08::run_second(input),
-        (2021, 9, Part::First) => aoc_2021::day09::run_first(input),
-        (2021, 9, Part::Second) => aoc_2021::day09::run_second(input),
-        (2021, 10, Part::First) => aoc_2021::day10::run_first(input),
-        (2021, 10, Part::Second) => aoc_2021::day10::run_second(input),
-        (2021, 11, Part::First) => aoc_2021::day11::run_first(input),
-        (2021, 11, Part::Second) => aoc_2021::day11::run_second(input),
-        (2021, 12, Part::First) => aoc_2021::day12::run_first(input),
-        (2021, 12, Part::Second) => aoc_2021::day12::run_second(input),
-        (2021, 13, Part::First) => aoc_2021::day13::run_first(input),
-        (2021, 13, Part::Second) => aoc_2021::day13::run_second(input),
-        (2021, 14, Part::First) => aoc_2021::day14::run_first(input),
-        (2021, 14, Part::Second) => aoc_2021::day14::run_second(input),
-        (2021, 15, Part::First) => aoc_2021::day15::run_first(input),
-        (2021, 15, Part::Second) => aoc_2021::day15::run_second(input),
-        (2021, 16, Part::First) => aoc_2021::day16::run_first(input),
-        (2021, 16, Part::Second) => aoc_2021::day16::run_second(input),
-        (2021, 17, Part::First) => aoc_2021::day17::run_first(input),
-        (2021, 17, Part::Second) => aoc_2021::day17::run_second(input),
-        (2021, 18, Part::First) => aoc_2021::day18::run_first(input),
-        (2021, 18, Part::Second) => aoc_2021::day18::run_second(input),
-
-        // 2022
-        (2022, 1, Part::First) => aoc_2022::day01::run_first(input),
-        (2022, 1, Part::Second) => aoc_2022::day01::run_second(input),
-        (2022, 2, Part::First) => aoc_2022::day02::run_first(input),
-        (2022, 2, Part::Second) => aoc_2022::day02::run_second(input),
-        (2022, 3, Part::First) => aoc_2022::day03::run_first(input),
-        (2022, 3, Part::Second) => aoc_2022::day03::run_second(input),
-        (2022, 4, Part::First) => aoc_2022::day04::run_first(input),
-        (2022, 4, Part::Second) => aoc_2022::day04::run_second(input),
-        (2022, 5, Part::First) => aoc_2022::day05::run_first(input),
-        (2022, 5, Part::Second) => aoc_2022::day05::run_second(input),
-        (2022, 6, Part::First) => aoc_2022::day06::run_first(input),
-        (2022, 6, Part::Second) => aoc_2022::day06::run_second(input),
-        (2022, 7, Part::First) => aoc_2022::day07::run_first(input),
-        (2022, 7, Part::Second) => aoc_2022::day07::run_second(input),
-        (2022, 8, Part::First) => aoc_2022::day08::run_first(input),
-        (2022, 8, Part::Second) => aoc_2022::day08::run_second(input),
-        (2022, 9, Part::First) => aoc_2022::day09::run_first(input),
-        (2022, 9, Part::Second) => aoc_2022::day09::run_second(input),
-        (2022, 10, Part::First) => aoc_2022::day10::run_first(input),
-        (2022, 10, Part::Second) => aoc_2022::day10::run_second(input),
-        (2022, 11, Part::First) => aoc_2022::day11::run_first(input),
-        (2022, 11, Part::Second) => aoc_2022::day11::run_second(input),
-        (2022, 12, Part::First) => aoc_2022::day12::run_first(input),
-        (2022, 12, Part::Second) => aoc_2022::day12::run_second(input),
-        (2022, 13, Part::First) => aoc_2022::day13::run_first(input),
-        (2022, 13, Part::Second) => aoc_2022::day13::run_second(input),
-        (2022, 14, Part::First) => aoc_2022::day14::run_first(input),
-        (2022, 14, Part::Second) => aoc_2022::day14::run_second(input),
-        (2022, 15, Part::First) => aoc_2022::day15::run_first(input),
-        (2022, 15, Part::Second) => aoc_2022::day15::run_second(input),
-        (2022, 16, Part::First) => aoc_2022::day16::run_first(input),
-        (2022, 16, Part::Second) => aoc_2022::day16::run_second(input),
-        (2022, 17, Part::First) => aoc_2022::day17::run_first(input),
-        (2022, 17, Part::Second) => aoc_2022::day17::run_second(input),
-        (2022, 18, Part::First) => aoc_2022::day18::run_first(input),
-        (2022, 18, Part::Second) => aoc_2022::day18::run_second(input),
-        (2022, 19, Part::First) => aoc_2022::day19::run_first(input),
-        (2022, 19, Part::Second) => aoc_2022::day19::run_second(input),
-        (2022, 20, Part::First) => aoc_2022::day20::run_first(input),
-        (2022, 20, Part::Second) => aoc_2022::day20::run_second(input),
-        (2022, 21, Part::First) => aoc_2022::day21::run_first(input),
-        (2022, 21, Part::Second) => aoc_2022::day21::run_second(input),
-
-        // 2023
-        (2023, 1, Part::First) => aoc_2023::day01::run_first(input),
-        (2023, 1, Part::Second) => aoc_2023::day01::run_second(input),
-        (2023, 2, Part::First) => aoc_2023::day02::run_first(input),
-        (2023, 2, Part::Second) => aoc_2023::day02::run_second(input),
-        (2023, 3, Part::First) => aoc_2023::day03::run_first(input),
-        (2023, 3, Part::Second) => aoc_2023::day03::run_second(input),
-        (2023, 4, Part::First) => aoc_2023::day04::run_first(input),
-        (2023, 4, Part::Second) => aoc_2023::day04::run_second(input),
-        (2023, 5, Part::First) => aoc_2023::day05::run_first(input),
-        (2023, 5, Part::Second) => aoc_2023::day05::run_second(input),
-
-        (_, _, _) => panic!("Year {year} Day {day} Part {part:?} not implemented"),
+    run_example_base(year, day, index, part).map_err(|err| JsError::new(&err.to_string()))
+}
+
+pub fn run_example_base(
+    year: usize,
+    day: usize,
+    index: usize,
+    part: Part,
+) -> Result<String, SolveError> {
+    registry::find(year, day)
+        .ok_or(SolveError::NotImplemented { year, day, part })?
+        .run_example(index, part)
+}
+
+/// Adds the snailfish numbers of `numbers`, one per line, and returns the reduced sum.
+#[allow(unused)]
+#[wasm_bindgen]
+pub fn snailfish_sum(numbers: &str) -> Result<String, JsError> {
+    snailfish_sum_base(numbers)
+        .map(|sum| sum.to_string())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Magnitude of a single snailfish number, such as `[[1,2],[[3,4],5]]`.
+#[allow(unused)]
+#[wasm_bindgen]
+pub fn snailfish_magnitude(number: &str) -> Result<u32, JsError> {
+    number
+        .trim()
+        .parse::<SnailfishNumber>()
+        .map(|number| number.magnitude())
+        .map_err(|err| JsError::new(&err.to_string()))
+}
+
+pub fn snailfish_sum_base(numbers: &str) -> Result<SnailfishNumber, SolveError> {
+    let input = input::normalize(numbers);
+    input
+        .text
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| parse_value::<SnailfishNumber>(&input.text, line))
+        .sum()
+}
+
+/// Parses the input once and solves both parts, see [`SolveReport`].
+///
+/// Returns a `SolveReport` object. Throws only when the day is not implemented
+/// or `params` are invalid, parse and part errors are reported inside the object.
+#[allow(unused)]
+#[wasm_bindgen]
+pub fn solve(year: usize, day: usize, input: &str, params: JsValue) -> Result<JsValue, JsError> {
+    utils::set_panic_hook();
+    let params = raw_params(&params)?;
+    let report =
+        solve_base(year, day, input, &params).map_err(|err| JsError::new(&err.to_string()))?;
+    serde_wasm_bindgen::to_value(&report).map_err(|err| JsError::new(&err.to_string()))
+}
+
+pub fn solve_base(
+    year: usize,
+    day: usize,
+    input: &str,
+    params: &RawParams,
+) -> Result<SolveReport, SolveError> {
+    let entry = registry::find(year, day).ok_or(SolveError::NotImplemented {
+        year,
+        day,
+        part: Part::First,
+    })?;
+    let input = input::normalize(input);
+    Ok(SolveReport {
+        input_changes: input.changes,
+        ..entry.solve(&input.text, params)?
+    })
+}
+
+/// Lists every implemented `[year, day, part]` combination.
+#[wasm_bindgen]
+pub fn implemented() -> js_sys::Array {
+    registry::implemented()
+        .into_iter()
+        .map(|(year, day, part)| {
+            js_sys::Array::of3(&year.into(), &day.into(), &(part as u8).into())
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASTED: &str = "\u{feff}1000\r\n2000\r\n\r\n4000\r\n\r\n";
+
+    #[test]
+    fn test_run_base_normalizes_input() {
+        assert_eq!(
+            run_base(2022, 1, Part::First, PASTED, &RawParams::new()),
+            Ok("4000".to_string())
+        );
+    }
+
+    #[test]
+    fn test_solve_base_reports_changes() {
+        // when
+        let report = solve_base(2022, 1, PASTED, &RawParams::new()).unwrap();
+
+        // then
+        assert_eq!(report.first.unwrap().answer, Some("4000".to_string()));
+        assert_eq!(
+            report.input_changes.to_string(),
+            "removed byte order mark, converted 3 line endings, removed 1 trailing blank lines"
+        );
+    }
+
+    #[test]
+    fn test_run_base_with_params() {
+        // given
+        let params: RawParams = [("first_days", "18")].into_iter().collect();
+
+        // when
+        let result = run_base(2021, 6, Part::First, "3,4,3,1,2", &params);
+
+        // then
+        assert_eq!(result, Ok("26".to_string()));
+    }
+
+    #[test]
+    fn test_run_base_rejects_unknown_params() {
+        // given
+        let params: RawParams = [("rounds", "18")].into_iter().collect();
+
+        // when
+        let result = run_base(2021, 6, Part::First, "3,4,3,1,2", &params);
+
+        // then
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Invalid parameter rounds: expected one of first_days, second_days"
+        );
+    }
+
+    #[test]
+    fn test_run_example_base() {
+        assert_eq!(
+            run_example_base(2022, 15, 0, Part::Second),
+            Ok("56000011".to_string())
+        );
+        assert_eq!(
+            run_example_base(2015, 1, 0, Part::First),
+            Err(SolveError::NotImplemented {
+                year: 2015,
+                day: 1,
+                part: Part::First
+            })
+        );
+    }
+
+    #[test]
+    fn test_snailfish_public_api() {
+        // given
+        let number: SnailfishNumber = "[[1,2],[[3,4],5]]".parse().unwrap();
+
+        // when
+        let sum = snailfish_sum_base("[1,1]\r\n[2,2]\r\n[3,3]\r\n[4,4]\r\n[5,5]\r\n");
+
+        // then
+        assert_eq!(number.magnitude(), 143);
+        assert_eq!(
+            sum.map(|sum| sum.to_string()),
+            Ok("[[[[3,0],[5,3]],[4,4]],[5,5]]".to_string())
+        );
+        assert_eq!(
+            snailfish_sum_base("[1,1]\n[2,x]"),
+            Err(SolveError::parse(2, 1, "[2,x]"))
+        );
+    }
+
+    #[test]
+    fn test_packet_public_api() {
+        // given
+        let packet: Packet = "D2FE28".parse().unwrap();
+
+        // then
+        assert_eq!(packet.to_hex(), Ok("D2FE28".to_string()));
+        assert_eq!("D2FE2".parse::<Packet>(), Err(SolveError::parse(1, 5, "2")));
     }
 }
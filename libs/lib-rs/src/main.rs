@@ -1,25 +1,319 @@
-use lib_rs::utils::read_to_string;
-use lib_rs::{run_base, Part};
-use std::env;
+mod console;
+
+use console::args::{self, Command, Input, USAGE};
+use console::output::{self, PartRow, Status, YearDays};
+use lib_rs::answers::Answers;
+use lib_rs::example::Example;
+use lib_rs::input::normalize;
+use lib_rs::params::RawParams;
+use lib_rs::registry::{self, Entry};
+use lib_rs::report::PartReport;
+use lib_rs::utils::{profiles, read_input, set_data_dir, DEFAULT_PROFILE};
+use lib_rs::Part;
+use std::{env, fs, io, process};
+
+fn part_number(part: Part) -> u8 {
+    part as u8 + 1
+}
+
+fn to_part(number: u8) -> Part {
+    if number == 1 {
+        Part::First
+    } else {
+        Part::Second
+    }
+}
+
+fn load_input(year: usize, day: usize, input: &Input) -> Result<String, String> {
+    let text = match input {
+        Input::Data { profile } => {
+            return read_input(year as u32, day as u8, profile).map_err(|err| err.to_string())
+        }
+        Input::File(path) => fs::read_to_string(path)
+            .map_err(|err| format!("Cannot read {}: {err}", path.display()))?,
+        Input::Stdin => {
+            io::read_to_string(io::stdin()).map_err(|err| format!("Cannot read stdin: {err}"))?
+        }
+        Input::Example(index) => return example(year, day, *index).map(|e| e.input.to_string()),
+    };
+    let normalized = normalize(&text);
+    if !normalized.changes.is_empty() {
+        eprintln!("Normalized input: {}", normalized.changes);
+    }
+    Ok(normalized.text.into_owned())
+}
+
+fn example(year: usize, day: usize, index: usize) -> Result<&'static Example, String> {
+    registry::find(year, day)
+        .and_then(|entry| entry.examples().get(index))
+        .ok_or_else(|| format!("Year {year}, day {day} has no example {}", index + 1))
+}
+
+fn check(row: &mut PartRow, expected: Option<&str>) {
+    row.expected = expected.map(str::to_string);
+    row.status = Some(match (&row.expected, &row.answer) {
+        (None, _) => Status::Missing,
+        (Some(expected), Some(answer)) if expected == answer => Status::Pass,
+        _ => Status::Fail,
+    });
+}
+
+fn to_row(entry: &Entry, input: &Input, part: Part, report: PartReport) -> PartRow {
+    let profile = match input {
+        Input::Data { profile } => Some(profile.clone()),
+        Input::Example(index) => Some(format!("example {}", index + 1)),
+        Input::File(_) | Input::Stdin => None,
+    };
+    PartRow {
+        year: entry.year,
+        day: entry.day,
+        profile,
+        part: part_number(part),
+        answer: report.answer,
+        error: report.error,
+        drawing: report.drawing,
+        duration_ms: report.duration_ms,
+        expected: None,
+        status: None,
+    }
+}
+
+fn solve_entry(
+    entry: &Entry,
+    input: &Input,
+    part: Option<Part>,
+    params: &RawParams,
+) -> Vec<PartRow> {
+    let parts = part.map_or(vec![Part::First, Part::Second], |part| vec![part]);
+    let failed = |error: String, duration_ms: f64| {
+        parts
+            .iter()
+            .map(|&part| {
+                let report = PartReport {
+                    answer: None,
+                    error: Some(error.clone()),
+                    drawing: None,
+                    duration_ms,
+                };
+                to_row(entry, input, part, report)
+            })
+            .collect()
+    };
+
+    let text = match load_input(entry.year, entry.day, input) {
+        Ok(text) => text,
+        Err(error) => return failed(error, 0.0),
+    };
+    if let Some(part) = part {
+        let report = PartReport::timed(|| entry.run(&text, part, params));
+        return vec![to_row(entry, input, part, report)];
+    }
+
+    let report = match entry.solve(&text, params) {
+        Ok(report) => report,
+        Err(error) => return failed(error.to_string(), 0.0),
+    };
+    match (report.parse_error, report.first, report.second) {
+        (Some(error), _, _) => failed(error, report.parse_ms),
+        (None, Some(first), Some(second)) => vec![
+            to_row(entry, input, Part::First, first),
+            to_row(entry, input, Part::Second, second),
+        ],
+        _ => failed("Incomplete report".to_string(), report.parse_ms),
+    }
+}
+
+/// Solves an example and compares the answers with the ones from the puzzle text.
+///
+/// `overrides` win over the parameters the example needs, the answers are not
+/// compared then.
+fn solve_example(
+    entry: &Entry,
+    index: usize,
+    part: Option<Part>,
+    overrides: &RawParams,
+) -> Result<Vec<PartRow>, String> {
+    let example = example(entry.year, entry.day, index)?;
+    let params = example
+        .raw_params()
+        .iter()
+        .chain(overrides.iter())
+        .collect();
+    let mut rows = solve_entry(entry, &Input::Example(index), part, &params);
+    if overrides.is_empty() {
+        for row in &mut rows {
+            check(row, example.expected(to_part(row.part)));
+        }
+    }
+    Ok(rows)
+}
+
+/// Solves both parts of every input profile of a day, or only of `profile` when given.
+///
+/// Days without the requested profile are skipped, unless `answered` names it.
+fn solve_profiles(entry: &Entry, profile: Option<&str>, answered: &[&str]) -> Vec<PartRow> {
+    let mut present = profiles(entry.year as u32, entry.day as u8);
+    // Answered profiles without an input file fail with the missing input error
+    for answered in answered {
+        if !present.iter().any(|present| present == answered) {
+            present.push(answered.to_string());
+        }
+    }
+    let mut selected: Vec<String> = present
+        .into_iter()
+        .filter(|present| profile.is_none_or(|profile| present == profile))
+        .collect();
+    if selected.is_empty() && profile.is_none() {
+        // Surfaces the missing input error instead of silently skipping the day
+        selected.push(DEFAULT_PROFILE.to_string());
+    }
+    selected
+        .into_iter()
+        .flat_map(|profile| solve_entry(entry, &Input::Data { profile }, None, &RawParams::new()))
+        .collect()
+}
+
+fn entries(year: Option<usize>) -> Result<Vec<&'static Entry>, String> {
+    let entries: Vec<_> = registry::all()
+        .filter(|entry| year.is_none_or(|year| entry.year == year))
+        .collect();
+    match (year, entries.is_empty()) {
+        (Some(year), true) => {
+            let available = registry::years()
+                .iter()
+                .map(|year| year.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            Err(format!(
+                "Nothing is implemented for year {year}. Available years: {available}"
+            ))
+        }
+        _ => Ok(entries),
+    }
+}
+
+fn verify(entries: &[&'static Entry], profile: Option<&str>) -> Result<Vec<PartRow>, String> {
+    let mut rows = Vec::new();
+    for year in registry::years() {
+        let answers =
+            Answers::load(year).map_err(|err| format!("Cannot load answers for {year}: {err}"))?;
+        for entry in entries.iter().filter(|entry| entry.year == year) {
+            for mut row in solve_profiles(entry, profile, &answers.profiles(entry.day)) {
+                let profile = row.profile.as_deref().unwrap_or(DEFAULT_PROFILE);
+                let expected = answers.expected(entry.day, profile, to_part(row.part));
+                check(&mut row, expected);
+                rows.push(row);
+            }
+        }
+    }
+    Ok(rows)
+}
+
+/// Solves the selected days and stores every successful answer, overwriting older ones.
+fn record(year: usize, day: Option<usize>, profile: Option<&str>) -> Result<Vec<PartRow>, String> {
+    let entries: Vec<_> = entries(Some(year))?
+        .into_iter()
+        .filter(|entry| day.is_none_or(|day| entry.day == day))
+        .collect();
+    if entries.is_empty() {
+        return Err(format!("Year {year}, day {day:?} is not implemented"));
+    }
+
+    let mut answers =
+        Answers::load(year).map_err(|err| format!("Cannot load answers for {year}: {err}"))?;
+    let mut rows = Vec::new();
+    for entry in entries {
+        for row in solve_profiles(entry, profile, &[]) {
+            if let (Some(profile), Some(answer)) = (&row.profile, &row.answer) {
+                answers.record(entry.day, profile, to_part(row.part), answer);
+            }
+            rows.push(row);
+        }
+    }
+    answers
+        .save(year)
+        .map_err(|err| format!("Cannot save answers for {year}: {err}"))?;
+    Ok(rows)
+}
+
+fn list(year: Option<usize>) -> Result<Vec<YearDays>, String> {
+    let entries = entries(year)?;
+    Ok(registry::years()
+        .into_iter()
+        .map(|year| YearDays {
+            year,
+            days: entries
+                .iter()
+                .filter(|entry| entry.year == year)
+                .map(|entry| entry.day)
+                .collect(),
+        })
+        .filter(|year| !year.days.is_empty())
+        .collect())
+}
+
+fn execute(command: Command, format: args::Format) -> Result<bool, String> {
+    let rows = match command {
+        Command::Help => {
+            println!("{USAGE}");
+            return Ok(true);
+        }
+        Command::List { year } => {
+            print!("{}", output::render_list(&list(year)?, format));
+            return Ok(true);
+        }
+        Command::Run {
+            year,
+            day,
+            part,
+            input,
+            params,
+        } => {
+            let Some(entry) = registry::find(year, day) else {
+                let available = entries(Some(year))?
+                    .iter()
+                    .map(|entry| entry.day.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(format!(
+                    "Year {year}, day {day} is not implemented. Available days: {available}"
+                ));
+            };
+            match input {
+                Input::Example(index) => solve_example(entry, index, part, &params)?,
+                input => solve_entry(entry, &input, part, &params),
+            }
+        }
+        Command::All { year, profile } => entries(year)?
+            .into_iter()
+            .flat_map(|entry| solve_profiles(entry, profile.as_deref(), &[]))
+            .collect(),
+        Command::Verify { year, profile } => verify(&entries(year)?, profile.as_deref())?,
+        Command::Record { year, day, profile } => record(year, day, profile.as_deref())?,
+    };
+
+    print!("{}", output::render_parts(&rows, format));
+    Ok(!rows.iter().any(PartRow::is_failure))
+}
 
 fn main() {
-    let argv: Vec<String> = env::args().collect();
-    if argv.len() < 3 {
-        panic!("Usage: cargo run <year> <day> <bigboy>");
-    }
-
-    let year: u32 = argv[1].parse().expect("Year must be a number");
-    let day: u8 = argv[2].parse().expect("Day must be a number");
-    let bigboy = argv.get(3).is_some();
-    println!("Running year {year}, day {day}");
-
-    let input = read_to_string(year, day, bigboy);
-    println!(
-        "Part 1: \n{}",
-        run_base(year as usize, day as usize, Part::First, &input)
-    );
-    println!(
-        "Part 2: \n{}",
-        run_base(year as usize, day as usize, Part::Second, &input)
-    );
+    let args = match args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            process::exit(2);
+        }
+    };
+
+    if let Some(data_dir) = args.data_dir {
+        set_data_dir(data_dir);
+    }
+    match execute(args.command, args.format) {
+        Ok(true) => {}
+        Ok(false) => process::exit(1),
+        Err(err) => {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+    }
 }
@@ -0,0 +1,240 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::error::SolveError;
+
+/// Untyped `key=value` overrides, as passed on the command line or from JS.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawParams(BTreeMap<String, String>);
+
+impl RawParams {
+    pub fn new() -> Self {
+        RawParams::default()
+    }
+
+    /// Parses a single `key=value` pair.
+    pub fn parse_pair(pair: &str) -> Option<(String, String)> {
+        let (key, value) = pair.split_once('=')?;
+        let key = key.trim();
+        if key.is_empty() {
+            return None;
+        }
+        Some((key.to_string(), value.trim().to_string()))
+    }
+
+    pub fn insert(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.0.insert(key.into(), value.into());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for RawParams {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        RawParams(
+            iter.into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParamInfo {
+    pub key: &'static str,
+    pub default: String,
+    pub description: &'static str,
+}
+
+/// Puzzle constants of a day, defaulting to the values of the real puzzle.
+///
+/// Usually declared with [`params!`](crate::params!).
+pub trait Params: Default {
+    fn info() -> Vec<ParamInfo>;
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), SolveError>;
+
+    fn from_raw(raw: &RawParams) -> Result<Self, SolveError> {
+        let mut params = Self::default();
+        for (key, value) in raw.iter() {
+            params.set(key, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Days without parameters reject every override.
+impl Params for () {
+    fn info() -> Vec<ParamInfo> {
+        Vec::new()
+    }
+
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), SolveError> {
+        Err(SolveError::invalid_param(
+            key,
+            "this day takes no parameters",
+        ))
+    }
+}
+
+pub fn parse_param<T: FromStr>(key: &str, value: &str) -> Result<T, SolveError> {
+    value
+        .parse()
+        .map_err(|_| SolveError::invalid_param(key, &format!("cannot parse {value:?}")))
+}
+
+pub fn unknown_param(key: &str, info: &[ParamInfo]) -> SolveError {
+    let known: Vec<_> = info.iter().map(|param| param.key).collect();
+    SolveError::invalid_param(key, &format!("expected one of {}", known.join(", ")))
+}
+
+/// Declares a parameter struct with its defaults and implements [`Params`] for it.
+///
+/// ```ignore
+/// params! {
+///     pub struct Day06Params {
+///         /// Days simulated in part one
+///         first_days: i64 = 80,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! params {
+    (
+        pub struct $name:ident {
+            $(
+                #[doc = $doc:literal]
+                $field:ident: $ty:ty = $default:expr
+            ),* $(,)?
+        }
+    ) => {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            $(
+                #[doc = $doc]
+                pub $field: $ty,
+            )*
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                $name {
+                    $($field: $default,)*
+                }
+            }
+        }
+
+        impl $crate::params::Params for $name {
+            fn info() -> Vec<$crate::params::ParamInfo> {
+                let defaults = Self::default();
+                vec![$(
+                    $crate::params::ParamInfo {
+                        key: stringify!($field),
+                        default: defaults.$field.to_string(),
+                        description: $doc.trim(),
+                    },
+                )*]
+            }
+
+            fn set(&mut self, key: &str, value: &str) -> Result<(), $crate::error::SolveError> {
+                match key {
+                    $(stringify!($field) => self.$field = $crate::params::parse_param(key, value)?,)*
+                    _ => return Err($crate::params::unknown_param(key, &Self::info())),
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    params! {
+        pub struct TestParams {
+            /// Rounds to play
+            rounds: i64 = 20,
+            /// Row to scan
+            row: usize = 10,
+        }
+    }
+
+    #[test]
+    fn test_from_raw() {
+        // given
+        let raw: RawParams = [("rounds", "10000")].into_iter().collect();
+
+        // when
+        let params = TestParams::from_raw(&raw).unwrap();
+
+        // then
+        assert_eq!(
+            params,
+            TestParams {
+                rounds: 10000,
+                row: 10
+            }
+        );
+    }
+
+    #[test]
+    fn test_info() {
+        assert_eq!(
+            TestParams::info(),
+            vec![
+                ParamInfo {
+                    key: "rounds",
+                    default: "20".to_string(),
+                    description: "Rounds to play",
+                },
+                ParamInfo {
+                    key: "row",
+                    default: "10".to_string(),
+                    description: "Row to scan",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid() {
+        let raw = |key: &str, value: &str| [(key, value)].into_iter().collect::<RawParams>();
+
+        assert_eq!(
+            TestParams::from_raw(&raw("row", "-1"))
+                .unwrap_err()
+                .to_string(),
+            "Invalid parameter row: cannot parse \"-1\""
+        );
+        assert_eq!(
+            TestParams::from_raw(&raw("days", "1"))
+                .unwrap_err()
+                .to_string(),
+            "Invalid parameter days: expected one of rounds, row"
+        );
+        assert_eq!(
+            <()>::from_raw(&raw("days", "1")).unwrap_err().to_string(),
+            "Invalid parameter days: this day takes no parameters"
+        );
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        assert_eq!(
+            RawParams::parse_pair("row = 10"),
+            Some(("row".to_string(), "10".to_string()))
+        );
+        assert_eq!(RawParams::parse_pair("=10"), None);
+        assert_eq!(RawParams::parse_pair("row"), None);
+    }
+}
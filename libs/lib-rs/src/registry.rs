@@ -0,0 +1,293 @@
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::params::{ParamInfo, Params, RawParams};
+use crate::report::SolveReport;
+use crate::solution::Solution;
+use crate::{aoc_2021, aoc_2022, aoc_2023, Part};
+
+/// Type erased handle to a registered [`Solution`].
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub year: usize,
+    pub day: usize,
+    run: fn(&str, Part, &RawParams) -> Result<String, SolveError>,
+    solve: fn(&str, &RawParams) -> Result<SolveReport, SolveError>,
+    params: fn() -> Vec<ParamInfo>,
+    examples: &'static [Example],
+}
+
+fn run_raw<S: Solution>(input: &str, part: Part, raw: &RawParams) -> Result<String, SolveError> {
+    S::run_with(input, part, &S::Params::from_raw(raw)?)
+}
+
+fn solve_raw<S: Solution>(input: &str, raw: &RawParams) -> Result<SolveReport, SolveError> {
+    Ok(S::solve_with(input, &S::Params::from_raw(raw)?))
+}
+
+impl Entry {
+    pub const fn new<S: Solution>(year: usize, day: usize) -> Entry {
+        Entry {
+            year,
+            day,
+            run: run_raw::<S>,
+            solve: solve_raw::<S>,
+            params: S::Params::info,
+            examples: S::EXAMPLES,
+        }
+    }
+
+    /// Solves a single part, `params` override the defaults of the day.
+    pub fn run(&self, input: &str, part: Part, params: &RawParams) -> Result<String, SolveError> {
+        (self.run)(input, part, params)
+    }
+
+    pub fn solve(&self, input: &str, params: &RawParams) -> Result<SolveReport, SolveError> {
+        (self.solve)(input, params)
+    }
+
+    /// Parameters accepted by [`Entry::run`] with their defaults.
+    pub fn params(&self) -> Vec<ParamInfo> {
+        (self.params)()
+    }
+
+    pub fn examples(&self) -> &'static [Example] {
+        self.examples
+    }
+
+    /// Solves a part of the example at `index` with the parameters it needs.
+    pub fn run_example(&self, index: usize, part: Part) -> Result<String, SolveError> {
+        let example = self.examples.get(index).ok_or(SolveError::MissingExample {
+            year: self.year,
+            day: self.day,
+            index,
+        })?;
+        self.run(example.input, part, &example.raw_params())
+    }
+}
+
+/// Registers the solutions of a single year.
+///
+/// Expands to a `SOLUTIONS` table in the calling module, which is then
+/// picked up by [`all`]. The registry tests fail for any `dayNN.rs` left out:
+///
+/// ```ignore
+/// register! {
+///     2021;
+///     1 => day01::Day01,
+///     2 => day02::Day02,
+/// }
+/// ```
+#[macro_export]
+macro_rules! register {
+    ($year:literal; $($day:literal => $solution:path),* $(,)?) => {
+        pub const SOLUTIONS: &[$crate::registry::Entry] = &[
+            $($crate::registry::Entry::new::<$solution>($year, $day)),*
+        ];
+    };
+}
+
+pub fn all() -> impl Iterator<Item = &'static Entry> {
+    aoc_2021::SOLUTIONS
+        .iter()
+        .chain(aoc_2022::SOLUTIONS.iter())
+        .chain(aoc_2023::SOLUTIONS.iter())
+}
+
+pub fn find(year: usize, day: usize) -> Option<&'static Entry> {
+    all().find(|entry| entry.year == year && entry.day == day)
+}
+
+pub fn years() -> Vec<usize> {
+    let mut years: Vec<usize> = all().map(|entry| entry.year).collect();
+    years.dedup();
+    years
+}
+
+pub fn implemented() -> Vec<(usize, usize, Part)> {
+    all()
+        .flat_map(|entry| {
+            [Part::First, Part::Second]
+                .into_iter()
+                .map(|part| (entry.year, entry.day, part))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Answers;
+    use crate::utils::read_to_string;
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn test_find_registered_day() {
+        let entry = find(2022, 6).unwrap();
+        assert_eq!((entry.year, entry.day), (2022, 6));
+        assert_eq!(
+            entry.run(
+                "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+                Part::First,
+                &RawParams::new()
+            ),
+            Ok("7".to_string())
+        );
+    }
+
+    #[test]
+    fn test_solve_reports_both_parts() {
+        // given
+        let entry = find(2022, 6).unwrap();
+
+        // when
+        let report = entry
+            .solve("mjqjpqmgbljsphdztnvjfqwrcgsmlb", &RawParams::new())
+            .unwrap();
+
+        // then
+        assert_eq!(report.parse_error, None);
+        assert_eq!(report.first.unwrap().answer, Some("7".to_string()));
+        assert_eq!(report.second.unwrap().answer, Some("19".to_string()));
+    }
+
+    #[test]
+    fn test_solve_reports_parse_error() {
+        // given
+        let entry = find(2022, 2).unwrap();
+
+        // when
+        let report = entry.solve("A X\nB Q", &RawParams::new()).unwrap();
+
+        // then
+        assert_eq!(
+            report.parse_error,
+            Some("Cannot parse \"B Q\" at line 2, column 1".to_string())
+        );
+        assert_eq!(report.first, None);
+        assert_eq!(report.second, None);
+    }
+
+    #[test]
+    fn test_examples() {
+        for entry in all() {
+            assert!(
+                !entry.examples().is_empty(),
+                "{} day {} has no examples",
+                entry.year,
+                entry.day
+            );
+            for (index, example) in entry.examples().iter().enumerate() {
+                for part in [Part::First, Part::Second] {
+                    let Some(expected) = example.expected(part) else {
+                        continue;
+                    };
+                    assert_eq!(
+                        entry.run_example(index, part),
+                        Ok(expected.to_string()),
+                        "{} day {} example {index} {part:?}",
+                        entry.year,
+                        entry.day
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_real_inputs() {
+        for entry in all() {
+            let (year, day) = (entry.year, entry.day);
+            let answers = Answers::load(year)
+                .unwrap_or_else(|err| panic!("Cannot load answers for {year}: {err}"));
+            let mut checked = [false, false];
+            // Answers name their profiles, so a missing input fails instead of being skipped
+            for profile in answers.profiles(day) {
+                let expected =
+                    [Part::First, Part::Second].map(|part| answers.expected(day, profile, part));
+                let input = read_to_string(year as u32, day as u8, profile);
+                let report = entry.solve(&input, &RawParams::new()).unwrap();
+                for (idx, part) in [report.first, report.second].into_iter().enumerate() {
+                    let Some(expected) = expected[idx] else {
+                        continue;
+                    };
+                    let answer = part.map(|part| part.answer.ok_or(part.error.unwrap_or_default()));
+                    assert_eq!(
+                        answer,
+                        Some(Ok(expected.to_string())),
+                        "{year} day {day} profile {profile} part {}",
+                        idx + 1
+                    );
+                    checked[idx] = true;
+                }
+            }
+            assert_eq!(
+                checked,
+                [true, true],
+                "{year} day {day} has no recorded answer for both parts"
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_missing_example() {
+        assert_eq!(
+            find(2022, 6).unwrap().run_example(5, Part::First),
+            Err(SolveError::MissingExample {
+                year: 2022,
+                day: 6,
+                index: 5
+            })
+        );
+    }
+
+    #[test]
+    fn test_find_missing_day() {
+        assert!(find(2015, 1).is_none());
+    }
+
+    #[test]
+    fn test_every_day_registered() {
+        // given
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let numbered = |path: PathBuf, prefix: &str, suffix: &str| {
+            let name = path.file_name()?.to_str()?;
+            name.strip_prefix(prefix)?
+                .strip_suffix(suffix)?
+                .parse()
+                .ok()
+        };
+
+        // when
+        let mut files: Vec<(usize, usize)> = vec![];
+        for year_dir in fs::read_dir(src).unwrap() {
+            let year_dir = year_dir.unwrap().path();
+            let Some(year) = numbered(year_dir.clone(), "aoc_", "") else {
+                continue;
+            };
+            for file in fs::read_dir(year_dir).unwrap() {
+                if let Some(day) = numbered(file.unwrap().path(), "day", ".rs") {
+                    files.push((year, day));
+                }
+            }
+        }
+        files.sort();
+
+        // then
+        let registered: Vec<_> = all().map(|entry| (entry.year, entry.day)).collect();
+        assert_eq!(files, registered);
+    }
+
+    #[test]
+    fn test_implemented_lists_both_parts() {
+        let implemented = implemented();
+        assert!(implemented.contains(&(2021, 1, Part::First)));
+        assert!(implemented.contains(&(2021, 1, Part::Second)));
+        assert_eq!(implemented.len(), 2 * all().count());
+    }
+
+    #[test]
+    fn test_years() {
+        assert_eq!(years(), vec![2021, 2022, 2023]);
+    }
+}
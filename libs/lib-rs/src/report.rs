@@ -0,0 +1,87 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::error::SolveError;
+use crate::input::InputChanges;
+use crate::utils::Stopwatch;
+
+#[wasm_bindgen(typescript_custom_section)]
+const TS_SOLVE_REPORT: &'static str = r#"
+export interface PartReport {
+    answer?: string;
+    error?: string;
+    drawing?: string;
+    durationMs: number;
+}
+
+export interface InputChanges {
+    bom: boolean;
+    lineEndings: number;
+    tabs: number;
+    trailingBlankLines: number;
+    trimmedLines: number;
+}
+
+export interface SolveReport {
+    inputChanges: InputChanges;
+    parseMs: number;
+    parseError?: string;
+    first?: PartReport;
+    second?: PartReport;
+}
+"#;
+
+/// Outcome of parsing an input once and solving both parts from it.
+///
+/// When parsing fails, `parse_error` is set and neither part is attempted.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SolveReport {
+    /// Filled in by [`crate::solve_base`], which normalizes the raw input.
+    pub input_changes: InputChanges,
+    pub parse_ms: f64,
+    pub parse_error: Option<String>,
+    pub first: Option<PartReport>,
+    pub second: Option<PartReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartReport {
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    /// See [`crate::solution::Solution::part_two_drawn`].
+    pub drawing: Option<String>,
+    pub duration_ms: f64,
+}
+
+impl PartReport {
+    /// Runs `solve` and records its answer or error along with the time it took.
+    pub fn timed(solve: impl FnOnce() -> Result<String, SolveError>) -> Self {
+        PartReport::timed_drawn(|| solve().map(|answer| (answer, None)))
+    }
+
+    /// Like [`PartReport::timed`] for a part that also returns its drawing.
+    pub fn timed_drawn(
+        solve: impl FnOnce() -> Result<(String, Option<String>), SolveError>,
+    ) -> Self {
+        let stopwatch = Stopwatch::start();
+        let result = solve();
+        let duration_ms = stopwatch.elapsed_ms();
+
+        match result {
+            Ok((answer, drawing)) => PartReport {
+                answer: Some(answer),
+                error: None,
+                drawing,
+                duration_ms,
+            },
+            Err(err) => PartReport {
+                answer: None,
+                error: Some(err.to_string()),
+                drawing: None,
+                duration_ms,
+            },
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::error::SolveError;
+use crate::example::Example;
+use crate::input::InputChanges;
+use crate::params::Params;
+use crate::report::{PartReport, SolveReport};
+use crate::utils::Stopwatch;
+use crate::Part;
+
+/// A single day of Advent of Code.
+///
+/// The raw puzzle input is parsed once into [`Solution::Input`] and both parts
+/// are computed from that parsed value.
+///
+/// Days with puzzle constants that differ between the example and the real
+/// input expose them as [`Solution::Params`] and override `part_one_with` and
+/// `part_two_with`, other days use `()`.
+pub trait Solution {
+    type Input;
+    type Params: Params;
+
+    /// Examples from the puzzle text, checked by the registry tests.
+    const EXAMPLES: &'static [Example] = &[];
+
+    fn parse(input: &str) -> Result<Self::Input, SolveError>;
+
+    fn part_one(input: &Self::Input) -> Result<String, SolveError>;
+
+    fn part_two(input: &Self::Input) -> Result<String, SolveError>;
+
+    fn part_one_with(input: &Self::Input, _params: &Self::Params) -> Result<String, SolveError> {
+        Self::part_one(input)
+    }
+
+    fn part_two_with(input: &Self::Input, _params: &Self::Params) -> Result<String, SolveError> {
+        Self::part_two(input)
+    }
+
+    /// Part two along with the picture it reads its letters from, reported next to the answer
+    /// so it can still be checked by eye. Days that draw override this to get both from one
+    /// run, the others draw nothing.
+    fn part_two_drawn(
+        input: &Self::Input,
+        params: &Self::Params,
+    ) -> Result<(String, Option<String>), SolveError> {
+        Self::part_two_with(input, params).map(|answer| (answer, None))
+    }
+
+    fn run_with(input: &str, part: Part, params: &Self::Params) -> Result<String, SolveError> {
+        let parsed = Self::parse(input)?;
+        match part {
+            Part::First => Self::part_one_with(&parsed, params),
+            Part::Second => Self::part_two_with(&parsed, params),
+        }
+    }
+
+    fn run(input: &str, part: Part) -> Result<String, SolveError> {
+        Self::run_with(input, part, &Self::Params::default())
+    }
+
+    fn run_first(input: &str) -> Result<String, SolveError> {
+        Self::run(input, Part::First)
+    }
+
+    fn run_second(input: &str) -> Result<String, SolveError> {
+        Self::run(input, Part::Second)
+    }
+
+    /// Parses `input` once and solves both parts, timing every step.
+    fn solve_with(input: &str, params: &Self::Params) -> SolveReport {
+        let stopwatch = Stopwatch::start();
+        let parsed = Self::parse(input);
+        let parse_ms = stopwatch.elapsed_ms();
+
+        match parsed {
+            Ok(parsed) => SolveReport {
+                input_changes: InputChanges::default(),
+                parse_ms,
+                parse_error: None,
+                first: Some(PartReport::timed(|| Self::part_one_with(&parsed, params))),
+                second: Some(PartReport::timed_drawn(|| {
+                    Self::part_two_drawn(&parsed, params)
+                })),
+            },
+            Err(err) => SolveReport {
+                input_changes: InputChanges::default(),
+                parse_ms,
+                parse_error: Some(err.to_string()),
+                first: None,
+                second: None,
+            },
+        }
+    }
+
+    fn solve(input: &str) -> SolveReport {
+        Self::solve_with(input, &Self::Params::default())
+    }
+}
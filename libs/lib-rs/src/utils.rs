@@ -1,8 +1,47 @@
 extern crate web_sys;
 
 use crate::log;
-use std::fs::File;
-use std::io::Read;
+use serde::Deserialize;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::{env, fmt, fs, io};
+
+/// Measures elapsed wall clock time.
+///
+/// `std::time::Instant` panics on `wasm32-unknown-unknown`, so the browser
+/// build reads `performance.now()` instead.
+pub struct Stopwatch {
+    start: f64,
+}
+
+impl Stopwatch {
+    pub fn start() -> Self {
+        Stopwatch { start: now_ms() }
+    }
+
+    pub fn elapsed_ms(&self) -> f64 {
+        now_ms() - self.start
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map_or_else(js_sys::Date::now, |performance| performance.now())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+    use lazy_static::lazy_static;
+    use std::time::Instant;
+
+    lazy_static! {
+        static ref ORIGIN: Instant = Instant::now();
+    }
+    ORIGIN.elapsed().as_secs_f64() * 1000.0
+}
 
 #[allow(dead_code)]
 pub fn set_panic_hook() {
@@ -17,24 +56,299 @@ pub fn set_panic_hook() {
     log!("Panic hook set");
 }
 
-pub fn get_path(year: u32, day: u8, bigboy: bool) -> String {
-    use std::path::PathBuf;
-    let mut d = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
-    d.push("data/");
+/// Environment variable pointing at the data directory.
+pub const DATA_DIR_ENV: &str = "AOC_DATA_DIR";
+
+static DATA_DIR_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Overrides the data directory for the rest of the process, see [`data_dir`].
+pub fn set_data_dir(path: PathBuf) {
+    let _ = DATA_DIR_OVERRIDE.set(path);
+}
+
+#[derive(Deserialize)]
+struct Config {
+    data_dir: Option<PathBuf>,
+}
+
+/// Config files checked in order: `.aoc.json` in the working directory, then
+/// `aoc/config.json` in the user config directory.
+fn config_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from(".aoc.json")];
+    let config_home = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")));
+    if let Some(config_home) = config_home {
+        paths.push(config_home.join("aoc").join("config.json"));
+    }
+    paths
+}
+
+/// `data_dir` of the first readable config file, relative paths are resolved
+/// against the directory of that file.
+fn config_data_dir(paths: &[PathBuf]) -> Option<PathBuf> {
+    paths.iter().find_map(|path| {
+        let json = fs::read_to_string(path).ok()?;
+        let data_dir = serde_json::from_str::<Config>(&json).ok()?.data_dir?;
+        Some(path.parent().unwrap_or(Path::new("")).join(data_dir))
+    })
+}
+
+fn resolve_data_dir(
+    flag: Option<&Path>,
+    env_var: Option<OsString>,
+    config_paths: &[PathBuf],
+) -> PathBuf {
+    flag.map(Path::to_path_buf)
+        .or_else(|| env_var.filter(|dir| !dir.is_empty()).map(PathBuf::from))
+        .or_else(|| config_data_dir(config_paths))
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join("data"))
+}
+
+/// Directory holding the `<year>/<day>.txt` inputs and answers.
+///
+/// Resolved from [`set_data_dir`] (the `--data-dir` flag), then `AOC_DATA_DIR`,
+/// then `data_dir` in a config file, falling back to the `data` directory
+/// of this crate.
+pub fn data_dir() -> PathBuf {
+    resolve_data_dir(
+        DATA_DIR_OVERRIDE.get().map(PathBuf::as_path),
+        env::var_os(DATA_DIR_ENV),
+        &config_paths(),
+    )
+}
+
+/// Profile stored directly as `data/<year>/<day>.txt`.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Path of a named input, `data/<year>/<day>/<profile>.txt`, or
+/// `data/<year>/<day>.txt` for the [`DEFAULT_PROFILE`].
+///
+/// Named inputs still kept in the older `data/<year>/.<profile>/<day>.txt`
+/// layout are picked up when the new path has no file.
+pub fn get_path(year: u32, day: u8, profile: &str) -> PathBuf {
+    profile_path(&data_dir().join(year.to_string()), day, profile)
+}
+
+fn profile_path(year_dir: &Path, day: u8, profile: &str) -> PathBuf {
+    if profile == DEFAULT_PROFILE {
+        return year_dir.join(format!("{day}.txt"));
+    }
+    let path = year_dir
+        .join(day.to_string())
+        .join(format!("{profile}.txt"));
+    let legacy = legacy_profile_path(year_dir, day, profile);
+    match !path.is_file() && legacy.is_file() {
+        true => legacy,
+        false => path,
+    }
+}
+
+fn legacy_profile_path(year_dir: &Path, day: u8, profile: &str) -> PathBuf {
+    year_dir
+        .join(format!(".{profile}"))
+        .join(format!("{day}.txt"))
+}
+
+/// Every input profile present for a day, the default one first.
+pub fn profiles(year: u32, day: u8) -> Vec<String> {
+    profiles_in(&data_dir().join(year.to_string()), day)
+}
+
+fn profiles_in(year_dir: &Path, day: u8) -> Vec<String> {
+    let mut named: Vec<String> = fs::read_dir(year_dir.join(day.to_string()))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .filter(|profile| profile != DEFAULT_PROFILE)
+        .collect();
+    named.extend(
+        fs::read_dir(year_dir)
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| {
+                let name = entry.ok()?.file_name().into_string().ok()?;
+                let profile = name.strip_prefix('.')?;
+                legacy_profile_path(year_dir, day, profile)
+                    .is_file()
+                    .then(|| profile.to_string())
+            }),
+    );
+    named.sort();
+    named.dedup();
 
-    if bigboy {
-        format!("{}/{}/.bigboy/{}.txt", d.to_str().unwrap(), year, day)
-    } else {
-        format!("{}/{}/{}.txt", d.to_str().unwrap(), year, day)
+    let mut profiles = Vec::new();
+    if profile_path(year_dir, day, DEFAULT_PROFILE).is_file() {
+        profiles.push(DEFAULT_PROFILE.to_string());
     }
+    profiles.extend(named);
+    profiles
 }
 
+pub fn get_answers_path(year: usize) -> PathBuf {
+    data_dir().join(year.to_string()).join("answers.json")
+}
+
+#[derive(Debug)]
+pub struct InputError {
+    pub path: PathBuf,
+    pub source: io::Error,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Cannot read input at {}: {}. The data directory can be set with --data-dir, {DATA_DIR_ENV} or data_dir in .aoc.json",
+            self.path.display(),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn read_input(year: u32, day: u8, profile: &str) -> Result<String, InputError> {
+    let path = get_path(year, day, profile);
+    match fs::read_to_string(&path) {
+        Ok(input) => Ok(crate::input::normalize(&input).text.into_owned()),
+        Err(source) => Err(InputError { path, source }),
+    }
+}
+
+/// Like [`read_input`], for tests that cannot run without their input.
 #[allow(unused)]
-pub fn read_to_string(year: u32, day: u8, bigboy: bool) -> String {
-    let data_path = get_path(year, day, bigboy);
-    let mut fp =
-        File::open(&data_path).unwrap_or_else(|_| panic!("Cannot open file at {data_path}"));
-    let mut buf = String::new();
-    fp.read_to_string(&mut buf).unwrap();
-    buf.trim_end().to_string()
+pub fn read_to_string(year: u32, day: u8, profile: &str) -> String {
+    read_input(year, day, profile).unwrap_or_else(|err| panic!("{err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_config(name: &str, json: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-config-{}-{name}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        fs::write(&path, json).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_flag_wins() {
+        // given
+        let config = write_config("flag", r#"{ "data_dir": "/from/config" }"#);
+
+        // when
+        let dir = resolve_data_dir(
+            Some(Path::new("/from/flag")),
+            Some("/from/env".into()),
+            &[config],
+        );
+
+        // then
+        assert_eq!(dir, PathBuf::from("/from/flag"));
+    }
+
+    #[test]
+    fn test_env_before_config() {
+        // given
+        let config = write_config("env", r#"{ "data_dir": "/from/config" }"#);
+
+        // when
+        let dir = resolve_data_dir(None, Some("/from/env".into()), &[config]);
+
+        // then
+        assert_eq!(dir, PathBuf::from("/from/env"));
+    }
+
+    #[test]
+    fn test_config_relative_to_file() {
+        // given
+        let config = write_config("relative", r#"{ "data_dir": "inputs" }"#);
+
+        // when
+        let dir = resolve_data_dir(
+            None,
+            Some("".into()),
+            &[PathBuf::from("missing.json"), config.clone()],
+        );
+
+        // then
+        assert_eq!(dir, config.parent().unwrap().join("inputs"));
+    }
+
+    #[test]
+    fn test_default() {
+        // given
+        let config = write_config("default", r#"{ "other": 1 }"#);
+
+        // when
+        let dir = resolve_data_dir(None, None, &[config]);
+
+        // then
+        assert_eq!(dir, Path::new(env!("CARGO_MANIFEST_DIR")).join("data"));
+    }
+
+    #[test]
+    fn test_profiles() {
+        // given
+        let year_dir = env::temp_dir().join(format!("aoc-profiles-{}", std::process::id()));
+        fs::create_dir_all(year_dir.join("16")).unwrap();
+        fs::create_dir_all(year_dir.join(".bigboy")).unwrap();
+        for file in [
+            "16.txt",
+            "16/bigboy.txt",
+            "16/alice.txt",
+            "16/notes.md",
+            "17.txt",
+            ".bigboy/16.txt",
+            ".bigboy/17.txt",
+        ] {
+            fs::write(year_dir.join(file), "").unwrap();
+        }
+
+        // when
+        let profiles = profiles_in(&year_dir, 16);
+
+        // then
+        assert_eq!(profiles, vec!["default", "alice", "bigboy"]);
+        assert_eq!(
+            profile_path(&year_dir, 16, "alice"),
+            year_dir.join("16").join("alice.txt")
+        );
+        assert_eq!(profiles_in(&year_dir, 17), vec!["default", "bigboy"]);
+        assert_eq!(
+            profile_path(&year_dir, 17, "bigboy"),
+            year_dir.join(".bigboy").join("17.txt")
+        );
+        assert_eq!(
+            profile_path(&year_dir, 16, "bigboy"),
+            year_dir.join("16").join("bigboy.txt")
+        );
+        assert!(profiles_in(&year_dir, 18).is_empty());
+    }
+
+    #[test]
+    fn test_missing_input_error() {
+        // given
+        let source = io::Error::from(io::ErrorKind::NotFound);
+
+        // when
+        let error = InputError {
+            path: PathBuf::from("/nowhere/2022/1.txt"),
+            source,
+        };
+
+        // then
+        assert!(error
+            .to_string()
+            .starts_with("Cannot read input at /nowhere/2022/1.txt: entity not found."));
+    }
 }